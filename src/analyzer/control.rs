@@ -0,0 +1,92 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Stage an analysis is currently in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnalysisPhase {
+    /// Detecting the project type and reading its manifest
+    Detecting,
+    /// Walking the directory tree looking for source files
+    Discovering,
+    /// Reading and parsing the discovered source files
+    Parsing,
+    Done,
+}
+
+impl AnalysisPhase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnalysisPhase::Detecting => "detecting",
+            AnalysisPhase::Discovering => "discovering",
+            AnalysisPhase::Parsing => "parsing",
+            AnalysisPhase::Done => "done",
+        }
+    }
+}
+
+/// Snapshot of how far an analysis has come
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnalysisProgress {
+    pub phase: AnalysisPhase,
+    /// Source files discovered so far
+    pub files_seen: usize,
+    /// Source files parsed so far
+    pub files_parsed: usize,
+}
+
+type ProgressCallback = Arc<dyn Fn(&AnalysisProgress) + Send + Sync>;
+
+/// Shared handle for steering a running analysis from outside.
+///
+/// Cloning is cheap; all clones observe the same cancellation state, so the
+/// MCP server can keep one clone per in-flight request and cancel it while
+/// the analyzer is still walking the tree. An optional progress callback is
+/// invoked as files are discovered and parsed.
+#[derive(Clone, Default)]
+pub struct AnalysisControl {
+    cancelled: Arc<AtomicBool>,
+    progress: Option<ProgressCallback>,
+}
+
+impl AnalysisControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attach a callback that receives every progress update
+    pub fn with_progress(
+        mut self,
+        callback: impl Fn(&AnalysisProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// Requests cancellation. The analyzer stops at its next checkpoint.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Returns an error if cancellation was requested
+    pub fn check(&self) -> anyhow::Result<()> {
+        if self.is_cancelled() {
+            anyhow::bail!("Analysis cancelled");
+        }
+        Ok(())
+    }
+
+    /// Forwards a progress update to the callback, if any
+    pub fn report(&self, phase: AnalysisPhase, files_seen: usize, files_parsed: usize) {
+        if let Some(ref callback) = self.progress {
+            callback(&AnalysisProgress {
+                phase,
+                files_seen,
+                files_parsed,
+            });
+        }
+    }
+}
@@ -2,14 +2,24 @@ use anyhow::Result;
 use std::path::Path;
 use tree_sitter::{Node, Parser};
 
-use crate::types::{CSharpFile, ClassInfo, InterfaceInfo, MethodInfo, PropertyInfo};
+use super::symbols::location;
+use crate::types::{
+    CSharpFile, ClassInfo, EnumInfo, EnumMemberInfo, FieldInfo, InterfaceInfo, MethodInfo,
+    Parameter, PropertyInfo, TypeKind,
+};
 
-#[allow(dead_code)]
 pub struct CSharpAnalyzer {
     parser: Parser,
 }
 
-#[allow(dead_code)]
+/// Type declarations found in a file, nested ones included
+#[derive(Default)]
+struct Declarations {
+    classes: Vec<ClassInfo>,
+    interfaces: Vec<InterfaceInfo>,
+    enums: Vec<EnumInfo>,
+}
+
 impl CSharpAnalyzer {
     pub fn new() -> Result<Self> {
         let mut parser = Parser::new();
@@ -19,36 +29,42 @@ impl CSharpAnalyzer {
         Ok(Self { parser })
     }
 
+    #[allow(dead_code)]
     pub fn analyze_file(&mut self, path: &Path) -> Result<CSharpFile> {
         let source = std::fs::read_to_string(path)?;
+        self.analyze_source(path, &source)
+    }
+
+    /// Same as [`CSharpAnalyzer::analyze_file`] for source already in memory
+    pub fn analyze_source(&mut self, path: &Path, source: &str) -> Result<CSharpFile> {
         let tree = self
             .parser
-            .parse(&source, None)
+            .parse(source, None)
             .ok_or_else(|| anyhow::anyhow!("Failed to parse file"))?;
 
         let root = tree.root_node();
 
         // Extract namespace
-        let namespace = self.extract_namespace(&root, &source);
+        let namespace = self.extract_namespace(&root, source);
 
         // Extract using directives
-        let usings = self.extract_usings(&root, &source);
-
-        // Extract classes
-        let classes = self.extract_classes(&root, &source);
+        let usings = self.extract_usings(&root, source);
 
-        // Extract interfaces
-        let interfaces = self.extract_interfaces(&root, &source);
+        // Extract classes, structs, records, interfaces and enums
+        let mut declarations = Declarations::default();
+        self.walk_for_declarations(&root, source, None, &mut declarations);
 
         Ok(CSharpFile {
             path: path.to_path_buf(),
             namespace,
             usings,
-            classes,
-            interfaces,
+            classes: declarations.classes,
+            interfaces: declarations.interfaces,
+            enums: declarations.enums,
         })
     }
 
+    /// First namespace declared in the file, for [`CSharpFile::namespace`]
     fn extract_namespace(&self, node: &Node, source: &str) -> Option<String> {
         let mut cursor = node.walk();
 
@@ -56,12 +72,8 @@ impl CSharpAnalyzer {
             if child.kind() == "namespace_declaration"
                 || child.kind() == "file_scoped_namespace_declaration"
             {
-                // Find the name node
-                let mut child_cursor = child.walk();
-                for name_child in child.children(&mut child_cursor) {
-                    if name_child.kind() == "qualified_name" || name_child.kind() == "identifier" {
-                        return Some(name_child.utf8_text(source.as_bytes()).ok()?.to_string());
-                    }
+                if let Some(name) = child.child_by_field_name("name") {
+                    return Some(text(&name, source));
                 }
             }
         }
@@ -90,86 +102,162 @@ impl CSharpAnalyzer {
         usings
     }
 
-    fn extract_classes(&self, node: &Node, source: &str) -> Vec<ClassInfo> {
-        let mut classes = Vec::new();
-        self.walk_for_classes(node, source, &mut classes);
-        classes
-    }
-
-    fn walk_for_classes(&self, node: &Node, source: &str, classes: &mut Vec<ClassInfo>) {
-        if node.kind() == "class_declaration" {
-            if let Some(class_info) = self.parse_class(node, source) {
-                classes.push(class_info);
+    /// Collects type declarations, tagging each with its enclosing
+    /// namespace. Nested namespaces are joined with `.` (`Outer.Inner`).
+    fn walk_for_declarations(
+        &self,
+        node: &Node,
+        source: &str,
+        namespace: Option<&str>,
+        found: &mut Declarations,
+    ) {
+        let enclosing = namespace.map(str::to_string);
+        match node.kind() {
+            "class_declaration" => {
+                found
+                    .classes
+                    .extend(self.parse_class(node, source, TypeKind::Class, enclosing))
+            }
+            "struct_declaration" => {
+                found
+                    .classes
+                    .extend(self.parse_class(node, source, TypeKind::Struct, enclosing))
             }
+            "record_declaration" => {
+                found
+                    .classes
+                    .extend(self.parse_class(node, source, TypeKind::Record, enclosing))
+            }
+            "record_struct_declaration" => found.classes.extend(self.parse_class(
+                node,
+                source,
+                TypeKind::RecordStruct,
+                enclosing,
+            )),
+            "interface_declaration" => found
+                .interfaces
+                .extend(self.parse_interface(node, source, enclosing)),
+            "enum_declaration" => found.enums.extend(self.parse_enum(node, source, enclosing)),
+            _ => {}
         }
 
+        let qualified = match node.kind() {
+            "namespace_declaration" | "file_scoped_namespace_declaration" => node
+                .child_by_field_name("name")
+                .map(|name| match namespace {
+                    Some(outer) => format!("{}.{}", outer, text(&name, source)),
+                    None => text(&name, source),
+                }),
+            _ => None,
+        };
+        let namespace = qualified.as_deref().or(namespace);
+
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
-            self.walk_for_classes(&child, source, classes);
+            self.walk_for_declarations(&child, source, namespace, found);
         }
     }
 
-    fn parse_class(&self, node: &Node, source: &str) -> Option<ClassInfo> {
-        let mut name = String::new();
-        let mut modifiers = Vec::new();
-        let mut methods = Vec::new();
-        let mut properties = Vec::new();
-
-        let mut cursor = node.walk();
+    fn parse_class(
+        &self,
+        node: &Node,
+        source: &str,
+        kind: TypeKind,
+        namespace: Option<String>,
+    ) -> Option<ClassInfo> {
+        let name = text(&node.child_by_field_name("name")?, source);
+
+        let bases = self.extract_bases(node, source);
+        // C# doesn't mark which base is the class; by convention interface
+        // names are `I` + PascalCase, and structs have no base class at all
+        let (base_class, interfaces) = match bases.split_first() {
+            Some((first, rest))
+                if matches!(kind, TypeKind::Class | TypeKind::Record)
+                    && !is_interface_name(first) =>
+            {
+                (Some(first.clone()), rest.to_vec())
+            }
+            _ => (None, bases),
+        };
 
-        for child in node.children(&mut cursor) {
-            match child.kind() {
-                "modifier" => {
-                    if let Ok(text) = child.utf8_text(source.as_bytes()) {
-                        modifiers.push(text.to_string());
-                    }
-                }
-                "identifier" => {
-                    if let Ok(text) = child.utf8_text(source.as_bytes()) {
-                        name = text.to_string();
-                    }
-                }
-                "declaration_list" => {
-                    // Extract methods and properties from class body
-                    self.extract_members(&child, source, &mut methods, &mut properties);
-                }
-                _ => {}
+        let mut class = ClassInfo {
+            name,
+            namespace,
+            kind,
+            modifiers: self.extract_modifiers(node, source),
+            attributes: self.extract_attributes(node, source),
+            type_parameters: self.extract_type_parameters(node, source),
+            base_class,
+            interfaces,
+            constructors: vec![],
+            methods: vec![],
+            properties: vec![],
+            fields: vec![],
+            events: vec![],
+            location: location(node),
+        };
+
+        // Positional record parameters become public properties
+        if let Some(parameters) = node.child_by_field_name("parameters") {
+            let mut cursor = parameters.walk();
+            let declared = parameters
+                .named_children(&mut cursor)
+                .filter(|p| p.kind() == "parameter");
+            let extracted = self.extract_parameters(&parameters, source);
+            for (parameter, declared) in extracted.into_iter().zip(declared) {
+                class.properties.push(PropertyInfo {
+                    name: parameter.name,
+                    prop_type: parameter.param_type,
+                    modifiers: vec!["public".to_string()],
+                    attributes: vec![],
+                    has_getter: true,
+                    has_setter: kind == TypeKind::RecordStruct,
+                    location: location(&declared),
+                });
             }
         }
 
-        if !name.is_empty() {
-            Some(ClassInfo {
-                name,
-                modifiers,
-                base_class: None,
-                interfaces: vec![],
-                methods,
-                properties,
-            })
-        } else {
-            None
+        if let Some(body) = node.child_by_field_name("body") {
+            self.extract_members(&body, source, &mut class);
         }
+
+        Some(class)
     }
 
-    fn extract_members(
-        &self,
-        node: &Node,
-        source: &str,
-        methods: &mut Vec<MethodInfo>,
-        properties: &mut Vec<PropertyInfo>,
-    ) {
+    fn extract_members(&self, node: &Node, source: &str, class: &mut ClassInfo) {
         let mut cursor = node.walk();
 
         for child in node.children(&mut cursor) {
             match child.kind() {
                 "method_declaration" => {
                     if let Some(method) = self.parse_method(&child, source) {
-                        methods.push(method);
+                        class.methods.push(method);
+                    }
+                }
+                "constructor_declaration" => {
+                    if let Some(constructor) = self.parse_method(&child, source) {
+                        class.constructors.push(constructor);
                     }
                 }
                 "property_declaration" => {
                     if let Some(property) = self.parse_property(&child, source) {
-                        properties.push(property);
+                        class.properties.push(property);
+                    }
+                }
+                "field_declaration" => class.fields.extend(self.parse_fields(&child, source)),
+                "event_field_declaration" => class.events.extend(self.parse_fields(&child, source)),
+                "event_declaration" => {
+                    if let (Some(name), Some(event_type)) = (
+                        child.child_by_field_name("name"),
+                        child.child_by_field_name("type"),
+                    ) {
+                        class.events.push(FieldInfo {
+                            name: text(&name, source),
+                            field_type: text(&event_type, source),
+                            modifiers: self.extract_modifiers(&child, source),
+                            attributes: self.extract_attributes(&child, source),
+                            location: location(&child),
+                        });
                     }
                 }
                 _ => {}
@@ -177,159 +265,476 @@ impl CSharpAnalyzer {
         }
     }
 
+    /// Methods and constructors; constructors have an empty return type
     fn parse_method(&self, node: &Node, source: &str) -> Option<MethodInfo> {
-        let mut name = String::new();
-        let mut return_type = String::new();
-        let mut modifiers = Vec::new();
-        let mut is_async = false;
+        let name = text(&node.child_by_field_name("name")?, source);
+        let return_type = node
+            .child_by_field_name("type")
+            .map(|t| text(&t, source))
+            .unwrap_or_default();
+        let modifiers = self.extract_modifiers(node, source);
+        let parameters = node
+            .child_by_field_name("parameters")
+            .map(|p| self.extract_parameters(&p, source))
+            .unwrap_or_default();
+
+        Some(MethodInfo {
+            name,
+            return_type,
+            parameters,
+            is_async: modifiers.iter().any(|m| m == "async"),
+            modifiers,
+            attributes: self.extract_attributes(node, source),
+            type_parameters: self.extract_type_parameters(node, source),
+            location: location(node),
+        })
+    }
 
+    fn extract_parameters(&self, node: &Node, source: &str) -> Vec<Parameter> {
+        let mut parameters = Vec::new();
         let mut cursor = node.walk();
 
-        for child in node.children(&mut cursor) {
-            match child.kind() {
-                "modifier" => {
-                    if let Ok(text) = child.utf8_text(source.as_bytes()) {
-                        if text == "async" {
-                            is_async = true;
-                        }
-                        modifiers.push(text.to_string());
-                    }
+        let children: Vec<Node> = node.named_children(&mut cursor).collect();
+        for (index, child) in children.iter().enumerate() {
+            // `params T[] rest` is inlined into the list as its type and name
+            if child.kind() == "array_type" {
+                if let Some(name) = children.get(index + 1) {
+                    parameters.push(Parameter {
+                        name: text(name, source),
+                        param_type: text(child, source),
+                        modifiers: vec!["params".to_string()],
+                    });
                 }
-                "identifier" => {
-                    if let Ok(text) = child.utf8_text(source.as_bytes()) {
-                        if name.is_empty() {
-                            name = text.to_string();
-                        }
-                    }
-                }
-                "predefined_type" | "qualified_name" | "generic_name" => {
-                    if let Ok(text) = child.utf8_text(source.as_bytes()) {
-                        if return_type.is_empty() {
-                            return_type = text.to_string();
-                        }
-                    }
-                }
-                _ => {}
+                continue;
             }
-        }
-
-        if !name.is_empty() {
-            Some(MethodInfo {
-                name,
-                return_type: if return_type.is_empty() {
-                    "void".to_string()
-                } else {
-                    return_type
-                },
-                parameters: vec![],
+            if child.kind() != "parameter" {
+                continue;
+            }
+            let Some(name) = child.child_by_field_name("name") else {
+                continue;
+            };
+
+            let mut modifier_cursor = child.walk();
+            let modifiers = child
+                .children(&mut modifier_cursor)
+                .filter(|c| c.kind() == "parameter_modifier")
+                .map(|c| text(&c, source))
+                .collect();
+
+            parameters.push(Parameter {
+                name: text(&name, source),
+                // Untyped parameters only occur in lambdas
+                param_type: child
+                    .child_by_field_name("type")
+                    .map(|t| text(&t, source))
+                    .unwrap_or_default(),
                 modifiers,
-                is_async,
-            })
-        } else {
-            None
+            });
         }
+
+        parameters
     }
 
     fn parse_property(&self, node: &Node, source: &str) -> Option<PropertyInfo> {
-        let mut name = String::new();
-        let mut prop_type = String::new();
-        let mut has_getter = false;
-        let mut has_setter = false;
+        let name = text(&node.child_by_field_name("name")?, source);
+        let prop_type = text(&node.child_by_field_name("type")?, source);
 
-        let mut cursor = node.walk();
+        // `public int Total => items.Count;` is get-only
+        let mut has_getter = node.child_by_field_name("value").is_some()
+            && node.child_by_field_name("accessors").is_none();
+        let mut has_setter = false;
 
-        for child in node.children(&mut cursor) {
-            match child.kind() {
-                "identifier" => {
-                    if let Ok(text) = child.utf8_text(source.as_bytes()) {
-                        if name.is_empty() {
-                            name = text.to_string();
-                        }
+        if let Some(accessors) = node.child_by_field_name("accessors") {
+            let mut acc_cursor = accessors.walk();
+            for accessor in accessors.named_children(&mut acc_cursor) {
+                let mut token_cursor = accessor.walk();
+                for token in accessor.children(&mut token_cursor) {
+                    match token.kind() {
+                        "get" => has_getter = true,
+                        "set" | "init" => has_setter = true,
+                        _ => {}
                     }
                 }
-                "predefined_type" | "qualified_name" | "generic_name" => {
-                    if let Ok(text) = child.utf8_text(source.as_bytes()) {
-                        if prop_type.is_empty() {
-                            prop_type = text.to_string();
-                        }
-                    }
-                }
-                "accessor_list" => {
-                    // Check for get/set accessors
-                    let mut acc_cursor = child.walk();
-                    for accessor in child.children(&mut acc_cursor) {
-                        if accessor.kind() == "accessor_declaration" {
-                            if let Ok(text) = accessor.utf8_text(source.as_bytes()) {
-                                if text.contains("get") {
-                                    has_getter = true;
-                                }
-                                if text.contains("set") {
-                                    has_setter = true;
-                                }
-                            }
-                        }
-                    }
-                }
-                _ => {}
             }
         }
 
-        if !name.is_empty() && !prop_type.is_empty() {
-            Some(PropertyInfo {
-                name,
-                prop_type,
-                has_getter,
-                has_setter,
-            })
-        } else {
-            None
+        Some(PropertyInfo {
+            name,
+            prop_type,
+            modifiers: self.extract_modifiers(node, source),
+            attributes: self.extract_attributes(node, source),
+            has_getter,
+            has_setter,
+            location: location(node),
+        })
+    }
+
+    /// One entry per declarator: `private int a, b;` declares two fields
+    fn parse_fields(&self, node: &Node, source: &str) -> Vec<FieldInfo> {
+        let mut cursor = node.walk();
+        let Some(declaration) = node
+            .named_children(&mut cursor)
+            .find(|c| c.kind() == "variable_declaration")
+        else {
+            return Vec::new();
+        };
+        let field_type = declaration
+            .child_by_field_name("type")
+            .map(|t| text(&t, source))
+            .unwrap_or_default();
+        let modifiers = self.extract_modifiers(node, source);
+        let attributes = self.extract_attributes(node, source);
+
+        let mut fields = Vec::new();
+        let mut declarator_cursor = declaration.walk();
+        for declarator in declaration.named_children(&mut declarator_cursor) {
+            if declarator.kind() != "variable_declarator" {
+                continue;
+            }
+            let mut name_cursor = declarator.walk();
+            let name = declarator
+                .named_children(&mut name_cursor)
+                .find(|c| c.kind() == "identifier");
+            if let Some(name) = name {
+                fields.push(FieldInfo {
+                    name: text(&name, source),
+                    field_type: field_type.clone(),
+                    modifiers: modifiers.clone(),
+                    attributes: attributes.clone(),
+                    location: location(node),
+                });
+            }
         }
+
+        fields
     }
 
-    fn extract_interfaces(&self, node: &Node, source: &str) -> Vec<InterfaceInfo> {
-        let mut interfaces = Vec::new();
-        self.walk_for_interfaces(node, source, &mut interfaces);
-        interfaces
+    fn parse_interface(
+        &self,
+        node: &Node,
+        source: &str,
+        namespace: Option<String>,
+    ) -> Option<InterfaceInfo> {
+        let name = text(&node.child_by_field_name("name")?, source);
+
+        let mut members = ClassInfo {
+            name: name.clone(),
+            namespace: None,
+            kind: TypeKind::Class,
+            modifiers: vec![],
+            attributes: vec![],
+            type_parameters: vec![],
+            base_class: None,
+            interfaces: vec![],
+            constructors: vec![],
+            methods: vec![],
+            properties: vec![],
+            fields: vec![],
+            events: vec![],
+            location: location(node),
+        };
+        if let Some(body) = node.child_by_field_name("body") {
+            self.extract_members(&body, source, &mut members);
+        }
+
+        Some(InterfaceInfo {
+            name,
+            namespace,
+            modifiers: self.extract_modifiers(node, source),
+            attributes: self.extract_attributes(node, source),
+            type_parameters: self.extract_type_parameters(node, source),
+            interfaces: self.extract_bases(node, source),
+            methods: members.methods,
+            properties: members.properties,
+            location: location(node),
+        })
     }
 
-    fn walk_for_interfaces(&self, node: &Node, source: &str, interfaces: &mut Vec<InterfaceInfo>) {
-        if node.kind() == "interface_declaration" {
-            if let Some(interface_info) = self.parse_interface(node, source) {
-                interfaces.push(interface_info);
+    fn parse_enum(&self, node: &Node, source: &str, namespace: Option<String>) -> Option<EnumInfo> {
+        let name = text(&node.child_by_field_name("name")?, source);
+
+        let mut members = Vec::new();
+        if let Some(body) = node.child_by_field_name("body") {
+            let mut cursor = body.walk();
+            for member in body.named_children(&mut cursor) {
+                if let Some(member_name) = member.child_by_field_name("name") {
+                    members.push(EnumMemberInfo {
+                        name: text(&member_name, source),
+                        location: location(&member),
+                    });
+                }
             }
         }
 
-        let mut cursor = node.walk();
-        for child in node.children(&mut cursor) {
-            self.walk_for_interfaces(&child, source, interfaces);
-        }
+        Some(EnumInfo {
+            name,
+            namespace,
+            modifiers: self.extract_modifiers(node, source),
+            attributes: self.extract_attributes(node, source),
+            underlying_type: self.extract_bases(node, source).into_iter().next(),
+            members,
+            location: location(node),
+        })
     }
 
-    fn parse_interface(&self, node: &Node, source: &str) -> Option<InterfaceInfo> {
-        let mut name = String::new();
-        let mut methods = Vec::new();
+    /// `public`, `static`, `async`, `partial`, ...
+    fn extract_modifiers(&self, node: &Node, source: &str) -> Vec<String> {
+        let mut cursor = node.walk();
+        let modifiers = node
+            .children(&mut cursor)
+            .filter(|c| c.kind() == "modifier")
+            .map(|c| text(&c, source))
+            .collect();
+        modifiers
+    }
 
+    /// Each attribute of every attribute list, e.g. `[Required, MaxLength(50)]`
+    /// yields `Required` and `MaxLength(50)`
+    fn extract_attributes(&self, node: &Node, source: &str) -> Vec<String> {
+        let mut attributes = Vec::new();
         let mut cursor = node.walk();
 
-        for child in node.children(&mut cursor) {
-            match child.kind() {
-                "identifier" => {
-                    if let Ok(text) = child.utf8_text(source.as_bytes()) {
-                        name = text.to_string();
-                    }
-                }
-                "declaration_list" => {
-                    let mut properties = Vec::new();
-                    self.extract_members(&child, source, &mut methods, &mut properties);
+        for list in node.children(&mut cursor) {
+            if list.kind() != "attribute_list" {
+                continue;
+            }
+            let mut list_cursor = list.walk();
+            for attribute in list.named_children(&mut list_cursor) {
+                if attribute.kind() == "attribute" {
+                    let attribute = text(&attribute, source);
+                    attributes.push(attribute.split_whitespace().collect::<Vec<_>>().join(" "));
                 }
-                _ => {}
             }
         }
 
-        if !name.is_empty() {
-            Some(InterfaceInfo { name, methods })
-        } else {
-            None
-        }
+        attributes
+    }
+
+    fn extract_type_parameters(&self, node: &Node, source: &str) -> Vec<String> {
+        let Some(list) = node.child_by_field_name("type_parameters") else {
+            return Vec::new();
+        };
+
+        let mut cursor = list.walk();
+        let parameters = list
+            .named_children(&mut cursor)
+            .filter_map(|p| p.child_by_field_name("name"))
+            .map(|name| text(&name, source))
+            .collect();
+        parameters
+    }
+
+    /// Types in the base list, as written; a record's primary constructor
+    /// base (`Person(Name)`) is reduced to its type
+    fn extract_bases(&self, node: &Node, source: &str) -> Vec<String> {
+        let Some(list) = node.child_by_field_name("bases") else {
+            return Vec::new();
+        };
+
+        let mut cursor = list.walk();
+        let bases = list
+            .named_children(&mut cursor)
+            .map(|base| {
+                let base = text(&base, source);
+                match base.find('(') {
+                    Some(args) => base[..args].trim().to_string(),
+                    None => base,
+                }
+            })
+            .collect();
+        bases
+    }
+}
+
+fn text(node: &Node, source: &str) -> String {
+    node.utf8_text(source.as_bytes()).unwrap_or("").to_string()
+}
+
+/// `IDisposable`, `IRepository<T>`: `I` followed by an upper-case letter.
+/// Only the type's own name counts, not its namespace or type arguments.
+fn is_interface_name(name: &str) -> bool {
+    let name = name.split('<').next().unwrap_or(name);
+    let name = name.rsplit('.').next().unwrap_or(name);
+    let mut chars = name.chars();
+    chars.next() == Some('I') && chars.next().is_some_and(|c| c.is_ascii_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+namespace Shop;
+
+[Route("/cart")]
+public sealed partial class Cart<TItem> : ComponentBase, IDisposable where TItem : class
+{
+    private readonly List<TItem> items = new(), removed = new();
+    public event EventHandler? Changed;
+
+    public Cart(ILogger<Cart<TItem>> logger) { }
+
+    [Parameter, EditorRequired]
+    public string? Title { get; init; }
+    public int Count => items.Count;
+
+    public async Task<TItem?> FindAsync<TKey>(TKey id, CancellationToken ct = default) => null;
+    public static void Add(this List<TItem> list, params TItem[] extra) { }
+    public void Dispose() { }
+}
+
+public interface IRepository<T> : IDisposable
+{
+    T? Get(int id);
+    int Count { get; }
+}
+
+public record Person(string Name, int? Age) : Entity(Name);
+public readonly record struct Point(int X, int Y);
+public struct Size : IEquatable<Size> { public int Width; }
+
+[Flags]
+public enum Access : byte { None = 0, Read = 1, Write = 2 }
+"#;
+
+    fn analyze() -> CSharpFile {
+        CSharpAnalyzer::new()
+            .unwrap()
+            .analyze_source(Path::new("Cart.cs"), SOURCE)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_extracts_class_declaration_details() {
+        let file = analyze();
+        assert_eq!(file.namespace.as_deref(), Some("Shop"));
+
+        let cart = &file.classes[0];
+        assert_eq!(cart.name, "Cart");
+        assert_eq!(cart.kind, TypeKind::Class);
+        assert_eq!(cart.modifiers, vec!["public", "sealed", "partial"]);
+        assert_eq!(cart.attributes, vec!["Route(\"/cart\")"]);
+        assert_eq!(cart.type_parameters, vec!["TItem"]);
+        assert_eq!(cart.base_class.as_deref(), Some("ComponentBase"));
+        assert_eq!(cart.interfaces, vec!["IDisposable"]);
+
+        let fields: Vec<(&str, &str)> = cart
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.field_type.as_str()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![("items", "List<TItem>"), ("removed", "List<TItem>")]
+        );
+        assert_eq!(cart.fields[0].modifiers, vec!["private", "readonly"]);
+        assert_eq!(cart.events[0].name, "Changed");
+        assert_eq!(cart.events[0].field_type, "EventHandler?");
+
+        assert_eq!(cart.constructors.len(), 1);
+        assert_eq!(cart.constructors[0].return_type, "");
+        assert_eq!(
+            cart.constructors[0].parameters[0].param_type,
+            "ILogger<Cart<TItem>>"
+        );
+
+        let title = &cart.properties[0];
+        assert_eq!(title.prop_type, "string?");
+        assert_eq!(title.attributes, vec!["Parameter", "EditorRequired"]);
+        assert!(title.has_getter && title.has_setter);
+        let count = &cart.properties[1];
+        assert!(count.has_getter && !count.has_setter);
+
+        let find = &cart.methods[0];
+        assert_eq!(find.return_type, "Task<TItem?>");
+        assert!(find.is_async);
+        assert_eq!(find.type_parameters, vec!["TKey"]);
+        let parameters: Vec<(&str, &str)> = find
+            .parameters
+            .iter()
+            .map(|p| (p.name.as_str(), p.param_type.as_str()))
+            .collect();
+        assert_eq!(
+            parameters,
+            vec![("id", "TKey"), ("ct", "CancellationToken")]
+        );
+
+        let add = &cart.methods[1];
+        assert_eq!(add.parameters[0].modifiers, vec!["this"]);
+        assert_eq!(add.parameters[1].modifiers, vec!["params"]);
+        assert_eq!(add.parameters[1].param_type, "TItem[]");
+    }
+
+    #[test]
+    fn test_extracts_records_structs_interfaces_and_enums() {
+        let file = analyze();
+
+        let kinds: Vec<(&str, TypeKind)> = file
+            .classes
+            .iter()
+            .map(|c| (c.name.as_str(), c.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("Cart", TypeKind::Class),
+                ("Person", TypeKind::Record),
+                ("Point", TypeKind::RecordStruct),
+                ("Size", TypeKind::Struct),
+            ]
+        );
+
+        let person = &file.classes[1];
+        assert_eq!(person.base_class.as_deref(), Some("Entity"));
+        let properties: Vec<(&str, &str)> = person
+            .properties
+            .iter()
+            .map(|p| (p.name.as_str(), p.prop_type.as_str()))
+            .collect();
+        assert_eq!(properties, vec![("Name", "string"), ("Age", "int?")]);
+
+        let size = &file.classes[3];
+        assert_eq!(size.base_class, None);
+        assert_eq!(size.interfaces, vec!["IEquatable<Size>"]);
+        assert_eq!(size.fields[0].name, "Width");
+
+        let repository = &file.interfaces[0];
+        assert_eq!(repository.type_parameters, vec!["T"]);
+        assert_eq!(repository.interfaces, vec!["IDisposable"]);
+        assert_eq!(repository.methods[0].return_type, "T?");
+        assert_eq!(repository.properties[0].name, "Count");
+
+        let access = &file.enums[0];
+        assert_eq!(access.attributes, vec!["Flags"]);
+        assert_eq!(access.underlying_type.as_deref(), Some("byte"));
+        let members: Vec<&str> = access.members.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(members, vec!["None", "Read", "Write"]);
+    }
+
+    #[test]
+    fn test_splits_bases_with_qualified_type_arguments() {
+        let source = r#"
+class A : Base<My.IThing>, IDisposable { }
+class B : IRepository<My.Order> { }
+class C : OwningComponentBase<Data.IRepo> { }
+"#;
+        let file = CSharpAnalyzer::new()
+            .unwrap()
+            .analyze_source(Path::new("Bases.cs"), source)
+            .unwrap();
+
+        let a = &file.classes[0];
+        assert_eq!(a.base_class.as_deref(), Some("Base<My.IThing>"));
+        assert_eq!(a.interfaces, vec!["IDisposable"]);
+
+        let b = &file.classes[1];
+        assert_eq!(b.base_class, None);
+        assert_eq!(b.interfaces, vec!["IRepository<My.Order>"]);
+
+        let c = &file.classes[2];
+        assert_eq!(
+            c.base_class.as_deref(),
+            Some("OwningComponentBase<Data.IRepo>")
+        );
     }
 }
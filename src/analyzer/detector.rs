@@ -1,5 +1,21 @@
 use crate::types::ProjectType;
-use std::path::Path;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directories never searched for nested projects
+const SKIPPED_DIRS: &[&str] = &[
+    "node_modules",
+    "target",
+    "bin",
+    "obj",
+    "vendor",
+    "dist",
+    "build",
+    "__pycache__",
+];
+
+/// How deep below the root nested projects of another type are looked for
+const NESTED_PROJECT_DEPTH: usize = 3;
 
 /// Detects the type of project based on configuration files present
 pub struct ProjectDetector;
@@ -9,10 +25,11 @@ impl ProjectDetector {
     pub fn detect(path: &Path) -> ProjectType {
         // Check for various project indicators (in priority order)
 
-        // .NET: .csproj, .fsproj, .sln
+        // .NET: .csproj, .fsproj, .sln, .slnx
         if Self::has_extension(path, "csproj")
             || Self::has_extension(path, "fsproj")
             || Self::has_extension(path, "sln")
+            || Self::has_extension(path, "slnx")
         {
             return ProjectType::DotNet;
         }
@@ -56,6 +73,235 @@ impl ProjectDetector {
         ProjectType::Unknown
     }
 
+    /// Directories under `path` that are projects of their own: the members
+    /// of a Cargo, npm/Yarn/pnpm or Go workspace, then projects of another
+    /// type nested in it (a Node frontend next to a .NET backend). When
+    /// `path` itself is no project, every nested project counts. Directories
+    /// a workspace excludes are skipped.
+    pub fn subprojects(path: &Path, project_type: &ProjectType) -> Vec<PathBuf> {
+        let (mut projects, excluded) = Self::workspace_members(path);
+
+        let mut nested = Vec::new();
+        Self::find_nested_projects(path, project_type, 1, &mut nested);
+        for dir in nested {
+            if !projects.iter().chain(&excluded).any(|p| dir.starts_with(p)) {
+                projects.push(dir);
+            }
+        }
+
+        projects
+    }
+
+    /// Whether a Cargo, npm/Yarn/pnpm or Go workspace at `root` lists `path`
+    /// as one of its members
+    pub fn is_workspace_member(root: &Path, path: &Path) -> bool {
+        Self::workspace_members(root).0.iter().any(|m| m == path)
+    }
+
+    /// Members and excluded directories of the workspaces rooted at `path`
+    fn workspace_members(path: &Path) -> (Vec<PathBuf>, Vec<PathBuf>) {
+        let mut members = Vec::new();
+        let mut all_excluded = Vec::new();
+        let mut add = |patterns: Vec<String>, manifest: &str| {
+            let (excluded, included): (Vec<String>, Vec<String>) =
+                patterns.into_iter().partition(|p| p.starts_with('!'));
+            let excluded: Vec<PathBuf> = excluded
+                .iter()
+                .flat_map(|p| Self::expand_pattern(path, &p[1..]))
+                .collect();
+            for pattern in included {
+                for dir in Self::expand_pattern(path, &pattern) {
+                    if dir != path
+                        && dir.join(manifest).is_file()
+                        && !excluded.contains(&dir)
+                        && !members.contains(&dir)
+                    {
+                        members.push(dir);
+                    }
+                }
+            }
+            all_excluded.extend(excluded);
+        };
+
+        // Cargo: `[workspace] members = ["crates/*"]`, minus `exclude`
+        if let Some(workspace) = fs::read_to_string(path.join("Cargo.toml"))
+            .ok()
+            .and_then(|c| c.parse::<toml::Table>().ok())
+            .and_then(|t| t.get("workspace").cloned())
+        {
+            let list = |key: &str| -> Vec<String> {
+                workspace
+                    .get(key)
+                    .and_then(|v| v.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            };
+            let mut patterns = list("members");
+            patterns.extend(list("exclude").into_iter().map(|p| format!("!{}", p)));
+            add(patterns, "Cargo.toml");
+        }
+
+        // npm and Yarn: `"workspaces": [...]` or `"workspaces": { "packages": [...] }`
+        if let Some(package) = fs::read_to_string(path.join("package.json"))
+            .ok()
+            .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+        {
+            let workspaces = &package["workspaces"];
+            let patterns = workspaces
+                .as_array()
+                .or_else(|| workspaces["packages"].as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect();
+            add(patterns, "package.json");
+        }
+
+        // pnpm: `packages:` list in pnpm-workspace.yaml
+        if let Ok(content) = fs::read_to_string(path.join("pnpm-workspace.yaml")) {
+            let patterns = content
+                .lines()
+                .skip_while(|line| line.trim_end() != "packages:")
+                .skip(1)
+                .take_while(|line| line.starts_with([' ', '-']) || line.trim().is_empty())
+                .filter_map(|line| line.trim().strip_prefix('-'))
+                .map(|p| p.trim().trim_matches(['\'', '"']).to_string())
+                .collect();
+            add(patterns, "package.json");
+        }
+
+        // Go: `use ./api` or a `use ( ... )` block in go.work
+        if let Ok(content) = fs::read_to_string(path.join("go.work")) {
+            let mut in_use = false;
+            let mut patterns = Vec::new();
+            for line in content.lines() {
+                let line = line.split("//").next().unwrap_or_default().trim();
+                match line.strip_prefix("use") {
+                    Some(rest) if rest.trim() == "(" => in_use = true,
+                    Some(rest) if rest.starts_with(char::is_whitespace) => {
+                        patterns.push(rest.trim().to_string())
+                    }
+                    _ if line == ")" => in_use = false,
+                    _ if in_use && !line.is_empty() => patterns.push(line.to_string()),
+                    _ => {}
+                }
+            }
+            add(patterns, "go.mod");
+        }
+
+        (members, all_excluded)
+    }
+
+    /// `crates/*`, `packages/**` or `./api` below `root`, as existing directories
+    fn expand_pattern(root: &Path, pattern: &str) -> Vec<PathBuf> {
+        let mut dirs = vec![root.to_path_buf()];
+
+        let pattern = pattern
+            .trim()
+            .trim_start_matches("./")
+            .trim_end_matches('/');
+        for component in pattern.split('/').filter(|c| !c.is_empty() && *c != ".") {
+            let mut next = Vec::new();
+            for dir in &dirs {
+                if component == "**" {
+                    next.push(dir.clone());
+                    Self::descendants(dir, NESTED_PROJECT_DEPTH + 1, &mut next);
+                } else if component.contains('*') {
+                    next.extend(
+                        Self::child_dirs(dir)
+                            .into_iter()
+                            .filter(|d| Self::matches_wildcard(component, &Self::dir_name(d))),
+                    );
+                } else {
+                    next.push(dir.join(component));
+                }
+            }
+            dirs = next;
+        }
+
+        dirs.retain(|d| d.is_dir());
+        dirs
+    }
+
+    /// `*` matches any run of characters
+    fn matches_wildcard(pattern: &str, name: &str) -> bool {
+        let parts: Vec<&str> = pattern.split('*').collect();
+        let (first, last) = (parts[0], parts[parts.len() - 1]);
+        if !name.starts_with(first) || !name[first.len()..].ends_with(last) {
+            return false;
+        }
+        let mut rest = &name[first.len()..name.len() - last.len()];
+        for part in &parts[1..parts.len() - 1] {
+            match rest.find(part) {
+                Some(i) => rest = &rest[i + part.len()..],
+                None => return false,
+            }
+        }
+        true
+    }
+
+    /// Directories below `dir` that may hold projects, sorted by name
+    fn child_dirs(dir: &Path) -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.is_dir())
+            .filter(|p| {
+                let name = Self::dir_name(p);
+                !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_str())
+            })
+            .collect();
+        dirs.sort();
+        dirs
+    }
+
+    fn descendants(dir: &Path, depth: usize, found: &mut Vec<PathBuf>) {
+        if depth == 0 {
+            return;
+        }
+        for child in Self::child_dirs(dir) {
+            found.push(child.clone());
+            Self::descendants(&child, depth - 1, found);
+        }
+    }
+
+    fn dir_name(dir: &Path) -> String {
+        dir.file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    /// Projects below `dir` whose type differs from `parent_type`; the search
+    /// stops at the first project on each path
+    fn find_nested_projects(
+        dir: &Path,
+        parent_type: &ProjectType,
+        depth: usize,
+        found: &mut Vec<PathBuf>,
+    ) {
+        if depth > NESTED_PROJECT_DEPTH {
+            return;
+        }
+        for child in Self::child_dirs(dir) {
+            match Self::detect(&child) {
+                ProjectType::Unknown => {
+                    Self::find_nested_projects(&child, parent_type, depth + 1, found)
+                }
+                child_type
+                    if child_type != *parent_type || *parent_type == ProjectType::Unknown =>
+                {
+                    found.push(child)
+                }
+                _ => {}
+            }
+        }
+    }
+
     /// Check if directory contains a file with the given extension
     fn has_extension(path: &Path, ext: &str) -> bool {
         if let Ok(entries) = std::fs::read_dir(path) {
@@ -122,6 +368,90 @@ mod tests {
         assert_eq!(ProjectDetector::detect(dir.path()), ProjectType::Node);
     }
 
+    #[test]
+    fn test_workspace_members() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\", \"tools/cli\"]\nexclude = [\"crates/old\"]\n",
+        )
+        .unwrap();
+        for crate_dir in ["crates/core", "crates/http-api", "crates/old", "tools/cli"] {
+            fs::create_dir_all(root.join(crate_dir)).unwrap();
+            fs::write(root.join(crate_dir).join("Cargo.toml"), "[package]").unwrap();
+        }
+        // Not a crate, so not a member
+        fs::create_dir_all(root.join("crates/notes")).unwrap();
+
+        fs::write(
+            root.join("pnpm-workspace.yaml"),
+            "packages:\n  - 'apps/*'\n  - \"!apps/legacy\"\n",
+        )
+        .unwrap();
+        for app in ["apps/web", "apps/legacy"] {
+            fs::create_dir_all(root.join(app)).unwrap();
+            fs::write(root.join(app).join("package.json"), "{}").unwrap();
+        }
+
+        fs::write(
+            root.join("go.work"),
+            "go 1.22\n\nuse (\n\t./services/worker // jobs\n)\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("services/worker")).unwrap();
+        fs::write(root.join("services/worker/go.mod"), "module worker").unwrap();
+
+        let members: Vec<String> = ProjectDetector::subprojects(root, &ProjectType::Rust)
+            .iter()
+            .map(|p| p.strip_prefix(root).unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            members,
+            vec![
+                "crates/core",
+                "crates/http-api",
+                "tools/cli",
+                "apps/web",
+                "services/worker"
+            ]
+        );
+        assert!(ProjectDetector::matches_wildcard(
+            "lib-*-core",
+            "lib-http-core"
+        ));
+        assert!(!ProjectDetector::matches_wildcard("lib-*", "app-http"));
+    }
+
+    #[test]
+    fn test_polyglot_repository() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("backend/src/Api")).unwrap();
+        fs::write(root.join("backend/src/Api/Api.csproj"), "<Project />").unwrap();
+        fs::create_dir_all(root.join("frontend/node_modules/left-pad")).unwrap();
+        fs::write(root.join("frontend/package.json"), "{}").unwrap();
+        fs::write(
+            root.join("frontend/node_modules/left-pad/package.json"),
+            "{}",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("docs")).unwrap();
+
+        assert_eq!(ProjectDetector::detect(root), ProjectType::Unknown);
+        assert_eq!(
+            ProjectDetector::subprojects(root, &ProjectType::Unknown),
+            vec![root.join("backend/src/Api"), root.join("frontend")]
+        );
+
+        // Projects of the root's own type are part of it
+        fs::write(root.join("package.json"), "{}").unwrap();
+        assert_eq!(
+            ProjectDetector::subprojects(root, &ProjectType::Node),
+            vec![root.join("backend/src/Api")]
+        );
+    }
+
     #[test]
     fn test_detect_unknown() {
         let dir = tempdir().unwrap();
@@ -3,50 +3,191 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
+use super::control::{AnalysisControl, AnalysisPhase};
 use super::detector::ProjectDetector;
+use super::{lockfile, msbuild, solution, symbols};
 #[allow(unused_imports)]
 use crate::types::{
     Dependency, Project, ProjectMetadata, ProjectType, SourceFile, Symbol, SymbolKind,
 };
 
+/// How many levels of nested projects are described below the analyzed one
+const MAX_PROJECT_DEPTH: usize = 3;
+
 /// Generic project analyzer that works with any project type
 pub struct GenericAnalyzer;
 
 impl GenericAnalyzer {
-    /// Analyze a project directory and return a generic Project struct
-    pub async fn analyze(path: &Path) -> Result<Project> {
+    /// Analyze a project directory and return a generic Project struct.
+    ///
+    /// Walks and parses the tree synchronously; async callers should run it
+    /// on a blocking thread (`tokio::task::spawn_blocking`).
+    #[allow(dead_code)]
+    pub fn analyze(path: &Path) -> Result<Project> {
+        Self::analyze_with(path, &AnalysisControl::new())
+    }
+
+    /// Same as [`GenericAnalyzer::analyze`], but stops with an error as soon as
+    /// `control` is cancelled and reports progress through it.
+    pub fn analyze_with(path: &Path, control: &AnalysisControl) -> Result<Project> {
+        control.report(AnalysisPhase::Detecting, 0, 0);
+
+        let mut project = Self::describe(path, 0)?;
+
+        // Source files of every language in the tree, found in one walk
+        let mut extensions = Vec::new();
+        Self::collect_extensions(&project, &mut extensions);
+        let found = Self::find_and_analyze_files(path, &extensions, control)?;
+
+        control.report(AnalysisPhase::Done, found.len(), found.len());
+
+        for file in found {
+            if let Some(file) = Self::assign(&mut project, file) {
+                tracing::debug!("No project claims {:?}", file.path);
+            }
+        }
+
+        Ok(project)
+    }
+
+    /// The project at `path` without files: its own manifest, then the
+    /// workspace members and nested projects of other types below it.
+    fn describe(path: &Path, depth: usize) -> Result<Project> {
         // Detect project type
         let project_type = ProjectDetector::detect(path);
 
-        eprintln!("DEBUG: Detected project type: {:?}", project_type);
-
-        // Get project info based on type
-        let (name, version, dependencies, metadata) = match project_type {
-            ProjectType::DotNet => Self::parse_dotnet_project(path)?,
-            ProjectType::Rust => Self::parse_rust_project(path)?,
-            ProjectType::Node => Self::parse_node_project(path)?,
-            ProjectType::Python => Self::parse_python_project(path)?,
-            ProjectType::Go => Self::parse_go_project(path)?,
-            ProjectType::Java => Self::parse_java_project(path)?,
-            ProjectType::Php => Self::parse_php_project(path)?,
-            ProjectType::Unknown => Self::parse_unknown_project(path)?,
+        tracing::debug!("Detected project type of {:?}: {:?}", path, project_type);
+
+        let mut project = match solution::find_solution(path) {
+            Some(solution) if project_type == ProjectType::DotNet => {
+                Self::describe_solution(path, &solution)?
+            }
+            _ => {
+                // Get project info based on type
+                let (name, version, dependencies, metadata) = match project_type {
+                    ProjectType::DotNet => Self::parse_dotnet_project(path)?,
+                    ProjectType::Rust => Self::parse_rust_project(path)?,
+                    ProjectType::Node => Self::parse_node_project(path)?,
+                    ProjectType::Python => Self::parse_python_project(path)?,
+                    ProjectType::Go => Self::parse_go_project(path)?,
+                    ProjectType::Java => Self::parse_java_project(path)?,
+                    ProjectType::Php => Self::parse_php_project(path)?,
+                    ProjectType::Unknown => Self::parse_unknown_project(path)?,
+                };
+                Project {
+                    path: path.to_path_buf(),
+                    name,
+                    project_type: project_type.clone(),
+                    version,
+                    dependencies,
+                    files: Vec::new(),
+                    metadata,
+                    subprojects: Vec::new(),
+                    references: Vec::new(),
+                }
+            }
         };
 
-        // Find and analyze source files
-        let extensions = ProjectDetector::get_source_extensions(&project_type);
-        let files = Self::find_and_analyze_files(path, &extensions)?;
+        if depth < MAX_PROJECT_DEPTH {
+            for dir in ProjectDetector::subprojects(path, &project_type) {
+                match Self::describe(&dir, depth + 1) {
+                    Ok(subproject) => project.subprojects.push(subproject),
+                    Err(e) => tracing::warn!("Skipping {:?}: {}", dir, e),
+                }
+            }
+        }
+
+        // .NET projects read their lockfile per project file
+        if project_type != ProjectType::DotNet {
+            lockfile::apply(path, &project_type, &mut project.dependencies);
+
+            // A workspace lockfile also lists the members and what they
+            // declare themselves; neither is transitive for the root
+            let members = &project.subprojects;
+            project.dependencies.retain(|d| {
+                !d.transitive
+                    || !members.iter().any(|m| {
+                        m.name == d.name || m.direct_dependencies().any(|md| md.name == d.name)
+                    })
+            });
+        }
+
+        Ok(project)
+    }
+
+    /// A solution becomes a project named after it, with one sub-project
+    /// per listed project. Its dependencies are those of all projects,
+    /// once each.
+    fn describe_solution(path: &Path, solution: &Path) -> Result<Project> {
+        let mut subprojects = Vec::new();
+        for project_file in solution::project_paths(solution)? {
+            match Self::parse_dotnet_project_file(&project_file) {
+                Ok(project) => subprojects.push(project),
+                Err(e) => tracing::warn!("Skipping {:?}: {}", project_file, e),
+            }
+        }
+
+        let mut dependencies: Vec<Dependency> = Vec::new();
+        for dependency in subprojects.iter().flat_map(|p| &p.dependencies) {
+            if !dependencies.iter().any(|d| d.name == dependency.name) {
+                dependencies.push(dependency.clone());
+            }
+        }
 
         Ok(Project {
             path: path.to_path_buf(),
-            name,
-            project_type,
-            version,
+            name: solution
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("Unknown")
+                .to_string(),
+            project_type: ProjectType::DotNet,
+            version: None,
             dependencies,
-            files,
-            metadata,
+            files: Vec::new(),
+            metadata: ProjectMetadata::default(),
+            subprojects,
+            references: Vec::new(),
         })
     }
 
+    fn collect_extensions(project: &Project, extensions: &mut Vec<&'static str>) {
+        for ext in ProjectDetector::get_source_extensions(&project.project_type) {
+            if !extensions.contains(&ext) {
+                extensions.push(ext);
+            }
+        }
+        for subproject in &project.subprojects {
+            Self::collect_extensions(subproject, extensions);
+        }
+    }
+
+    /// Hands `file` to the innermost project containing it whose language
+    /// it is in; a file no project takes is given back.
+    fn assign(project: &mut Project, file: SourceFile) -> Option<SourceFile> {
+        if !file.path.starts_with(&project.path) {
+            return Some(file);
+        }
+
+        let mut file = file;
+        let mut subprojects: Vec<&mut Project> = project.subprojects.iter_mut().collect();
+        subprojects.sort_by_key(|p| std::cmp::Reverse(p.path.components().count()));
+        for subproject in subprojects {
+            match Self::assign(subproject, file) {
+                Some(rejected) => file = rejected,
+                None => return None,
+            }
+        }
+
+        let extensions = ProjectDetector::get_source_extensions(&project.project_type);
+        if extensions.contains(&file.language.as_str()) {
+            project.files.push(file);
+            None
+        } else {
+            Some(file)
+        }
+    }
+
     // ========================================================================
     // Project-specific parsers
     // ========================================================================
@@ -56,27 +197,64 @@ impl GenericAnalyzer {
     ) -> Result<(String, Option<String>, Vec<Dependency>, ProjectMetadata)> {
         // Find .csproj file
         let csproj = Self::find_file_with_extension(path, "csproj")?;
-        let content = fs::read_to_string(&csproj)?;
+        let project = Self::parse_dotnet_project_file(&csproj)?;
+
+        Ok((
+            project.name,
+            project.version,
+            project.dependencies,
+            project.metadata,
+        ))
+    }
 
-        let name = csproj
+    /// A `.csproj`/`.fsproj`/`.vbproj` as a project without files, rooted
+    /// at its directory
+    fn parse_dotnet_project_file(project_file: &Path) -> Result<Project> {
+        let evaluation = msbuild::evaluate(project_file)?;
+
+        let name = project_file
             .file_stem()
             .and_then(|s| s.to_str())
             .unwrap_or("Unknown")
             .to_string();
 
-        // Parse target framework
-        let target_framework = Self::extract_xml_value(&content, "TargetFramework")
-            .or_else(|| Self::extract_xml_value(&content, "TargetFrameworks"));
-
-        // Parse packages
-        let dependencies = Self::parse_nuget_packages(&content);
-
+        let property = |name: &str| evaluation.property(name).map(String::from);
+        let target_frameworks = evaluation.target_frameworks();
         let metadata = ProjectMetadata {
-            target_framework,
+            target_framework: target_frameworks.first().cloned(),
+            target_frameworks,
+            sdk: evaluation.sdk.clone(),
+            output_type: property("OutputType"),
+            nullable: property("Nullable"),
+            lang_version: property("LangVersion"),
+            implicit_usings: property("ImplicitUsings"),
             ..Default::default()
         };
 
-        Ok((name, None, dependencies, metadata))
+        let version = property("Version");
+        let directory = project_file.parent().unwrap_or(Path::new("."));
+        let mut dependencies = evaluation.packages;
+        lockfile::apply(directory, &ProjectType::DotNet, &mut dependencies);
+
+        // Names of referenced projects: `../Shared/Shared.csproj` → `Shared`
+        let references = evaluation
+            .project_references
+            .iter()
+            .filter_map(|r| Path::new(r).file_stem().and_then(|s| s.to_str()))
+            .map(String::from)
+            .collect();
+
+        Ok(Project {
+            path: directory.to_path_buf(),
+            name,
+            project_type: ProjectType::DotNet,
+            version,
+            dependencies,
+            files: Vec::new(),
+            metadata,
+            subprojects: Vec::new(),
+            references,
+        })
     }
 
     fn parse_rust_project(
@@ -85,9 +263,11 @@ impl GenericAnalyzer {
         let cargo_toml = path.join("Cargo.toml");
         let content = fs::read_to_string(&cargo_toml).context("Failed to read Cargo.toml")?;
 
-        // Simple TOML parsing for name and version
-        let name =
-            Self::extract_toml_value(&content, "name").unwrap_or_else(|| "Unknown".to_string());
+        // Simple TOML parsing for name and version; a virtual workspace
+        // manifest has no name and goes by its directory
+        let name = Self::extract_toml_value(&content, "name")
+            .or_else(|| Some(path.file_name()?.to_str()?.to_string()))
+            .unwrap_or_else(|| "Unknown".to_string());
         let version = Self::extract_toml_value(&content, "version");
         let edition = Self::extract_toml_value(&content, "edition");
 
@@ -125,6 +305,7 @@ impl GenericAnalyzer {
                     name: name.clone(),
                     version: version.as_str().unwrap_or("*").to_string(),
                     dev_only: false,
+                    ..Default::default()
                 });
             }
         }
@@ -135,6 +316,7 @@ impl GenericAnalyzer {
                     name: name.clone(),
                     version: version.as_str().unwrap_or("*").to_string(),
                     dev_only: true,
+                    ..Default::default()
                 });
             }
         }
@@ -183,9 +365,7 @@ impl GenericAnalyzer {
                 name = n;
             }
             version = Self::extract_toml_value(&content, "version");
-
-            // Parse dependencies from pyproject.toml
-            // (simplified - real parsing would need proper TOML parser)
+            dependencies = Self::parse_pyproject_dependencies(&content);
         }
 
         // Try requirements.txt
@@ -207,10 +387,14 @@ impl GenericAnalyzer {
                     (line.to_string(), "*".to_string())
                 };
 
+                if dependencies.iter().any(|d| d.name == pkg_name) {
+                    continue;
+                }
                 dependencies.push(Dependency {
                     name: pkg_name,
                     version: pkg_version,
                     dev_only: false,
+                    ..Default::default()
                 });
             }
         }
@@ -256,13 +440,24 @@ impl GenericAnalyzer {
                 continue;
             }
 
-            if in_require && !line.is_empty() && !line.starts_with("//") {
+            // `require example.com/mod v1.2.3` outside a block
+            let line = match line.strip_prefix("require ") {
+                Some(single) if !in_require => single.trim(),
+                _ if in_require => line,
+                _ => continue,
+            };
+
+            if !line.is_empty() && !line.starts_with("//") {
                 let parts: Vec<&str> = line.split_whitespace().collect();
                 if parts.len() >= 2 {
+                    // go.mod versions are the selected ones; `// indirect`
+                    // marks modules only other modules require
                     dependencies.push(Dependency {
                         name: parts[0].to_string(),
                         version: parts[1].to_string(),
                         dev_only: false,
+                        resolved: Some(parts[1].to_string()),
+                        transitive: line.ends_with("// indirect"),
                     });
                 }
             }
@@ -338,6 +533,7 @@ impl GenericAnalyzer {
                     name: pkg_name.clone(),
                     version: pkg_version.as_str().unwrap_or("*").to_string(),
                     dev_only: false,
+                    ..Default::default()
                 });
             }
         }
@@ -348,6 +544,7 @@ impl GenericAnalyzer {
                     name: pkg_name.clone(),
                     version: pkg_version.as_str().unwrap_or("*").to_string(),
                     dev_only: true,
+                    ..Default::default()
                 });
             }
         }
@@ -543,37 +740,90 @@ impl GenericAnalyzer {
         None
     }
 
-    fn parse_nuget_packages(content: &str) -> Vec<Dependency> {
+    /// `[project]` dependencies and `[dependency-groups]` (PEP 621, 735),
+    /// or Poetry's dependency tables; groups are development dependencies
+    fn parse_pyproject_dependencies(content: &str) -> Vec<Dependency> {
+        let Ok(pyproject) = content.parse::<toml::Table>() else {
+            return Vec::new();
+        };
         let mut deps = Vec::new();
 
-        // Match <PackageReference Include="name" Version="version" />
-        for line in content.lines() {
-            if line.contains("PackageReference") {
-                let include = Self::extract_xml_attr(line, "Include");
-                let version = Self::extract_xml_attr(line, "Version");
+        let requirements = |value: Option<&toml::Value>| -> Vec<String> {
+            value
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|r| r.as_str().map(String::from))
+                .collect()
+        };
+        let project = pyproject.get("project");
+        for requirement in requirements(project.and_then(|p| p.get("dependencies"))) {
+            deps.extend(Self::parse_pep508(&requirement, false));
+        }
+        for group in pyproject
+            .get("dependency-groups")
+            .and_then(|g| g.as_table())
+            .into_iter()
+            .flat_map(|g| g.values())
+        {
+            for requirement in requirements(Some(group)) {
+                deps.extend(Self::parse_pep508(&requirement, true));
+            }
+        }
 
-                if let Some(name) = include {
-                    deps.push(Dependency {
-                        name,
-                        version: version.unwrap_or_else(|| "*".to_string()),
-                        dev_only: false,
-                    });
+        // `fastapi = "^0.110"` or `fastapi = { version = "^0.110", extras = [...] }`
+        let poetry = pyproject.get("tool").and_then(|t| t.get("poetry"));
+        let mut tables = vec![(poetry.and_then(|p| p.get("dependencies")), false)];
+        tables.push((poetry.and_then(|p| p.get("dev-dependencies")), true));
+        for group in poetry
+            .and_then(|p| p.get("group"))
+            .and_then(|g| g.as_table())
+            .into_iter()
+            .flat_map(|g| g.values())
+        {
+            tables.push((group.get("dependencies"), true));
+        }
+        for (table, dev_only) in tables {
+            for (name, spec) in table.and_then(|t| t.as_table()).into_iter().flatten() {
+                if name == "python" {
+                    continue;
                 }
+                let version = spec
+                    .as_str()
+                    .or_else(|| spec.get("version").and_then(|v| v.as_str()))
+                    .unwrap_or("*");
+                deps.push(Dependency {
+                    name: name.clone(),
+                    version: version.to_string(),
+                    dev_only,
+                    ..Default::default()
+                });
             }
         }
 
         deps
     }
 
-    fn extract_xml_attr(line: &str, attr: &str) -> Option<String> {
-        let pattern = format!("{}=\"", attr);
-        if let Some(start) = line.find(&pattern) {
-            let value_start = start + pattern.len();
-            if let Some(end) = line[value_start..].find('"') {
-                return Some(line[value_start..value_start + end].to_string());
-            }
+    /// `fastapi[all]>=0.100; python_version >= "3.8"` → `fastapi`, `>=0.100`
+    fn parse_pep508(requirement: &str, dev_only: bool) -> Option<Dependency> {
+        let requirement = requirement.split(';').next()?.trim();
+        let end = requirement
+            .find(|c: char| !(c.is_alphanumeric() || "-_.".contains(c)))
+            .unwrap_or(requirement.len());
+        if end == 0 {
+            return None;
         }
-        None
+        let mut spec = requirement[end..].trim();
+        if spec.starts_with('[') {
+            spec = spec.split_once(']').map_or("", |(_, rest)| rest.trim());
+        }
+
+        Some(Dependency {
+            name: requirement[..end].to_string(),
+            version: if spec.is_empty() { "*" } else { spec }.to_string(),
+            dev_only,
+            ..Default::default()
+        })
     }
 
     fn parse_cargo_dependencies(content: &str) -> Vec<Dependency> {
@@ -606,6 +856,7 @@ impl GenericAnalyzer {
                         name,
                         version,
                         dev_only: in_dev_deps,
+                        ..Default::default()
                     });
                 }
             }
@@ -615,30 +866,29 @@ impl GenericAnalyzer {
     }
 
     fn parse_cargo_dep_line(line: &str) -> Option<(String, String)> {
-        // Handle: name = "version" or name = { version = "1.0", features = [...] }
-        let parts: Vec<&str> = line.splitn(2, '=').collect();
-        if parts.len() == 2 {
-            let name = parts[0].trim().to_string();
-            let value = parts[1].trim();
-
-            // Simple version string
-            if value.starts_with('"') {
-                let version = value.trim_matches('"').to_string();
-                return Some((name, version));
-            }
-
-            // Object with version key
-            if value.starts_with('{') {
-                if let Some(ver) = Self::extract_toml_value(value, "version") {
-                    return Some((name, ver));
-                }
-            }
-        }
-        None
+        // Handle: name = "version" or name = { version = "1.0", features = [...] };
+        // git, path and workspace dependencies have no version
+        let table = line.parse::<toml::Table>().ok()?;
+        let (name, value) = table.into_iter().next()?;
+        let version = match value {
+            toml::Value::String(version) => version,
+            toml::Value::Table(spec) => spec
+                .get("version")
+                .and_then(|v| v.as_str())
+                .unwrap_or("*")
+                .to_string(),
+            _ => return None,
+        };
+        Some((name, version))
     }
 
-    fn find_and_analyze_files(path: &Path, extensions: &[&str]) -> Result<Vec<SourceFile>> {
-        let mut files = Vec::new();
+    fn find_and_analyze_files(
+        path: &Path,
+        extensions: &[&str],
+        control: &AnalysisControl,
+    ) -> Result<Vec<SourceFile>> {
+        // Discover first so the parse phase has a known total to report against
+        let mut paths = Vec::new();
 
         for entry in WalkDir::new(path)
             .follow_links(false)
@@ -655,23 +905,44 @@ impl GenericAnalyzer {
                     && name != "vendor"
             })
         {
+            control.check()?;
+
             let entry = entry?;
             let file_path = entry.path();
 
             if let Some(ext) = file_path.extension().and_then(|e| e.to_str()) {
                 if extensions.contains(&ext) {
-                    let metadata = fs::metadata(file_path)?;
-
-                    files.push(SourceFile {
-                        path: file_path.to_path_buf(),
-                        language: ext.to_string(),
-                        size_bytes: metadata.len(),
-                        symbols: Vec::new(), // TODO: Parse symbols with tree-sitter
-                    });
+                    paths.push((file_path.to_path_buf(), ext.to_string()));
+                    control.report(AnalysisPhase::Discovering, paths.len(), 0);
                 }
             }
         }
 
+        let mut files = Vec::with_capacity(paths.len());
+
+        for (file_path, ext) in paths.iter() {
+            control.check()?;
+
+            let metadata = fs::metadata(file_path)?;
+
+            // A file that can't be read or parsed still counts, just without an outline
+            let symbols = fs::read_to_string(file_path)
+                .map_err(anyhow::Error::from)
+                .and_then(|source| symbols::extract_symbols(file_path, ext, &source))
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to extract symbols from {:?}: {}", file_path, e);
+                    Vec::new()
+                });
+
+            files.push(SourceFile {
+                path: file_path.clone(),
+                language: ext.clone(),
+                size_bytes: metadata.len(),
+                symbols,
+            });
+            control.report(AnalysisPhase::Parsing, paths.len(), files.len());
+        }
+
         Ok(files)
     }
 }
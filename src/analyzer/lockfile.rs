@@ -0,0 +1,782 @@
+//! Lockfiles: the versions a project's dependencies actually resolved to,
+//! and the packages they pulled in.
+//!
+//! Each project type reads its own lockfiles, found in the project directory
+//! or the closest parent that has one (workspaces keep a single lockfile at
+//! their root, which also lists the other members' packages). Parents count
+//! up to the repository root, or when their workspace lists the project;
+//! a lockfile elsewhere above it belongs to something else.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+use super::detector::ProjectDetector;
+use crate::types::{Dependency, ProjectType};
+
+/// Directories whose presence marks a repository root
+const VCS_DIRS: &[&str] = &[".git", ".hg", ".svn"];
+
+/// A package as a lockfile pins it
+#[derive(Debug, Default)]
+struct Locked {
+    name: String,
+    version: String,
+    /// Declared ranges it satisfies, where the lockfile records them
+    ranges: Vec<String>,
+    /// Whether the lockfile itself says the package is direct
+    direct: Option<bool>,
+    dev: bool,
+}
+
+/// The closest lockfile that may belong to the project at `path`: its own,
+/// one within the same repository, or one at a workspace root listing it
+fn find(path: &Path, project_type: &ProjectType) -> Option<std::path::PathBuf> {
+    let repository = path
+        .ancestors()
+        .find(|dir| VCS_DIRS.iter().any(|vcs| dir.join(vcs).exists()));
+
+    for dir in path.ancestors() {
+        let related = dir == path
+            || repository.is_some_and(|root| dir.starts_with(root))
+            || ProjectDetector::is_workspace_member(dir, path);
+        if related {
+            let found = lockfiles(project_type)
+                .iter()
+                .map(|name| dir.join(name))
+                .find(|file| file.is_file());
+            if found.is_some() {
+                return found;
+            }
+        }
+        if Some(dir) == repository {
+            break;
+        }
+    }
+
+    None
+}
+
+/// Lockfiles by project type, in order of preference
+fn lockfiles(project_type: &ProjectType) -> &'static [&'static str] {
+    match project_type {
+        ProjectType::Rust => &["Cargo.lock"],
+        ProjectType::Node => &["package-lock.json", "pnpm-lock.yaml", "yarn.lock"],
+        ProjectType::Python => &["uv.lock", "poetry.lock"],
+        ProjectType::Go => &["go.sum"],
+        ProjectType::Php => &["composer.lock"],
+        ProjectType::DotNet => &["packages.lock.json"],
+        ProjectType::Java | ProjectType::Unknown => &[],
+    }
+}
+
+/// Sets `resolved` on the declared dependencies and, when the lockfile is
+/// the project's own, appends its other packages as transitive ones.
+/// Without a lockfile nothing changes.
+pub fn apply(path: &Path, project_type: &ProjectType, dependencies: &mut Vec<Dependency>) {
+    let Some(lockfile) = find(path, project_type) else {
+        return;
+    };
+
+    let locked = match read(&lockfile) {
+        Ok(locked) => locked,
+        Err(e) => {
+            tracing::warn!("Skipping lockfile {:?}: {:#}", lockfile, e);
+            return;
+        }
+    };
+    let key = |name: &str| package_key(project_type, name);
+
+    for dependency in dependencies.iter_mut() {
+        let candidates: Vec<&Locked> = locked
+            .iter()
+            .filter(|l| key(&l.name) == key(&dependency.name) && l.direct != Some(false))
+            .collect();
+        if let Some(version) = resolve(&candidates, &dependency.version) {
+            dependency.resolved = Some(version);
+        }
+    }
+
+    if lockfile.parent() != Some(path) {
+        return;
+    }
+    for package in &locked {
+        let known = dependencies
+            .iter()
+            .any(|d| key(&d.name) == key(&package.name));
+        if known || package.direct == Some(true) {
+            continue;
+        }
+        dependencies.push(Dependency {
+            name: package.name.clone(),
+            version: package.version.clone(),
+            dev_only: package.dev,
+            resolved: Some(package.version.clone()),
+            transitive: true,
+        });
+    }
+}
+
+fn read(lockfile: &Path) -> Result<Vec<Locked>> {
+    let content = fs::read_to_string(lockfile)?;
+    let name = lockfile
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+
+    match name {
+        "Cargo.lock" | "poetry.lock" | "uv.lock" => toml_packages(&content),
+        "package-lock.json" => npm(&content),
+        "pnpm-lock.yaml" => Ok(pnpm(&content)),
+        "yarn.lock" => Ok(yarn(&content)),
+        "go.sum" => Ok(go_sum(&content)),
+        "composer.lock" => composer(&content),
+        "packages.lock.json" => nuget(&content),
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// The locked version of a declared dependency: the one recorded for its
+/// range, else the newest compatible one, else the newest
+fn resolve(candidates: &[&Locked], declared: &str) -> Option<String> {
+    if let Some(exact) = candidates
+        .iter()
+        .find(|l| l.ranges.iter().any(|r| r == declared))
+    {
+        return Some(exact.version.clone());
+    }
+    let compatible: Vec<&&Locked> = candidates
+        .iter()
+        .filter(|l| is_compatible(declared, &l.version))
+        .collect();
+    let pool: Vec<&&Locked> = if compatible.is_empty() {
+        candidates.iter().collect()
+    } else {
+        compatible
+    };
+    pool.into_iter()
+        .max_by_key(|l| version_key(&l.version))
+        .map(|l| l.version.clone())
+}
+
+/// Whether `version` shares the significant part of `declared`: its major
+/// version, or its minor version when the major is 0 (`^0.8` → `0.8.x`)
+fn is_compatible(declared: &str, version: &str) -> bool {
+    let wanted = version_key(declared);
+    let significant = wanted
+        .iter()
+        .position(|n| *n != 0)
+        .map_or(wanted.len(), |i| i + 1);
+    version_key(version).starts_with(&wanted[..significant])
+}
+
+/// `v1.2.10-beta` → `[1, 2, 10]`, for comparing versions
+fn version_key(version: &str) -> Vec<u64> {
+    let start = version
+        .find(|c: char| c.is_ascii_digit())
+        .unwrap_or(version.len());
+    version[start..]
+        .split(['-', '+', ' ', ','])
+        .next()
+        .unwrap_or_default()
+        .split('.')
+        .map_while(|part| part.parse().ok())
+        .collect()
+}
+
+/// Package names as the ecosystem compares them
+fn package_key(project_type: &ProjectType, name: &str) -> String {
+    let name = name.to_lowercase();
+    match project_type {
+        // PEP 503: runs of `-`, `_` and `.` are equivalent
+        ProjectType::Python => name
+            .split(['-', '_', '.'])
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("-"),
+        ProjectType::Rust => name.replace('_', "-"),
+        _ => name,
+    }
+}
+
+/// `[[package]]` tables of `Cargo.lock`, `poetry.lock` and `uv.lock`;
+/// workspace members and the project itself have no registry source
+fn toml_packages(content: &str) -> Result<Vec<Locked>> {
+    let lock: toml::Value = toml::from_str(content).context("Invalid TOML")?;
+    let packages = lock.get("package").and_then(|p| p.as_array());
+
+    Ok(packages
+        .into_iter()
+        .flatten()
+        .filter(|package| match package.get("source") {
+            Some(source) => ["editable", "virtual", "path", "directory"]
+                .iter()
+                .all(|local| source.get(local).is_none()),
+            None => package.get("category").is_some() || package.get("files").is_some(),
+        })
+        .filter_map(|package| {
+            Some(Locked {
+                name: package.get("name")?.as_str()?.to_string(),
+                version: package.get("version")?.as_str()?.to_string(),
+                dev: package.get("category").and_then(|c| c.as_str()) == Some("dev"),
+                ..Default::default()
+            })
+        })
+        .collect())
+}
+
+/// `package-lock.json`: `packages` keyed by `node_modules/` path (v2, v3),
+/// or nested `dependencies` (v1). Packages nested under another one are
+/// always transitive.
+fn npm(content: &str) -> Result<Vec<Locked>> {
+    let lock: serde_json::Value = serde_json::from_str(content).context("Invalid JSON")?;
+    let mut locked = Vec::new();
+
+    if let Some(packages) = lock["packages"].as_object() {
+        for (key, package) in packages {
+            let Some((_, name)) = key.rsplit_once("node_modules/") else {
+                // The root package and workspace folders
+                continue;
+            };
+            let Some(version) = package["version"].as_str() else {
+                continue;
+            };
+            locked.push(Locked {
+                name: package["name"].as_str().unwrap_or(name).to_string(),
+                version: version.to_string(),
+                direct: (key.matches("node_modules/").count() > 1).then_some(false),
+                dev: package["dev"].as_bool().unwrap_or(false),
+                ..Default::default()
+            });
+        }
+    } else {
+        npm_v1(&lock["dependencies"], true, &mut locked);
+    }
+
+    Ok(locked)
+}
+
+fn npm_v1(dependencies: &serde_json::Value, top_level: bool, locked: &mut Vec<Locked>) {
+    for (name, package) in dependencies.as_object().into_iter().flatten() {
+        if let Some(version) = package["version"].as_str() {
+            locked.push(Locked {
+                name: name.clone(),
+                version: version.to_string(),
+                direct: (!top_level).then_some(false),
+                dev: package["dev"].as_bool().unwrap_or(false),
+                ..Default::default()
+            });
+        }
+        npm_v1(&package["dependencies"], false, locked);
+    }
+}
+
+/// `pnpm-lock.yaml` package keys: `/react@18.2.0` (v6), `react@18.2.0(...)`
+/// (v9) or `/react/18.2.0` (v5)
+fn pnpm(content: &str) -> Vec<Locked> {
+    let mut locked: Vec<Locked> = Vec::new();
+    let mut in_packages = false;
+
+    for line in content.lines() {
+        let indent = line.len() - line.trim_start().len();
+        let trimmed = line.trim();
+        if indent == 0 && !trimmed.is_empty() {
+            in_packages = trimmed == "packages:";
+            continue;
+        }
+        if !in_packages {
+            continue;
+        }
+        if indent == 2 && trimmed.ends_with(':') {
+            let key = trimmed.trim_end_matches(':').trim_matches(['\'', '"']);
+            let key = key.trim_start_matches('/');
+            let key = key.split('(').next().unwrap_or(key);
+            let split = match key.rfind('@').filter(|i| *i > 0) {
+                Some(at) => Some((&key[..at], &key[at + 1..])),
+                None => key.rsplit_once('/'),
+            };
+            if let Some((name, version)) = split {
+                locked.push(Locked {
+                    name: name.to_string(),
+                    version: version.split('_').next().unwrap_or(version).to_string(),
+                    ..Default::default()
+                });
+            }
+        } else if indent == 4 && trimmed == "dev: true" {
+            if let Some(last) = locked.last_mut() {
+                last.dev = true;
+            }
+        }
+    }
+
+    locked
+}
+
+/// `yarn.lock` entries: a header listing the ranges (`react@^18.2.0,
+/// react@^18.0.0:`), then `version "18.2.0"` (v1) or `version: 18.2.0` (berry)
+fn yarn(content: &str) -> Vec<Locked> {
+    let mut locked: Vec<Locked> = Vec::new();
+
+    for line in content.lines() {
+        if line.starts_with(['#', ' ']) || !line.trim_end().ends_with(':') {
+            if let Some(version) = line.strip_prefix("  version") {
+                if let Some(last) = locked.last_mut() {
+                    last.version = version
+                        .trim_start_matches(':')
+                        .trim()
+                        .trim_matches('"')
+                        .to_string();
+                }
+            }
+            continue;
+        }
+
+        let specs: Vec<(&str, &str)> = line
+            .trim_end()
+            .trim_end_matches(':')
+            .split(", ")
+            .filter_map(|spec| {
+                let spec = spec.trim().trim_matches('"');
+                let at = spec.rfind('@').filter(|i| *i > 0)?;
+                Some((&spec[..at], spec[at + 1..].trim_start_matches("npm:")))
+            })
+            .collect();
+        let Some((name, _)) = specs.first() else {
+            continue;
+        };
+        // The project itself and its workspaces
+        if specs
+            .iter()
+            .any(|(_, range)| range.starts_with("workspace:"))
+        {
+            continue;
+        }
+        locked.push(Locked {
+            name: name.to_string(),
+            ranges: specs.iter().map(|(_, range)| range.to_string()).collect(),
+            ..Default::default()
+        });
+    }
+
+    locked.retain(|l| !l.version.is_empty());
+    locked
+}
+
+/// Modules of `go.sum` whose source was downloaded, the newest version of
+/// each; `/go.mod`-only lines are for versions the build did not select
+fn go_sum(content: &str) -> Vec<Locked> {
+    let mut locked: Vec<Locked> = Vec::new();
+
+    for line in content.lines() {
+        let mut parts = line.split_whitespace();
+        let (Some(module), Some(version)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        if version.ends_with("/go.mod") {
+            continue;
+        }
+        match locked.iter_mut().find(|l| l.name == module) {
+            Some(existing) if version_key(version) > version_key(&existing.version) => {
+                existing.version = version.to_string();
+            }
+            Some(_) => {}
+            None => locked.push(Locked {
+                name: module.to_string(),
+                version: version.to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+
+    locked
+}
+
+/// `composer.lock`: `packages` and `packages-dev`
+fn composer(content: &str) -> Result<Vec<Locked>> {
+    let lock: serde_json::Value = serde_json::from_str(content).context("Invalid JSON")?;
+    let mut locked = Vec::new();
+
+    for (section, dev) in [("packages", false), ("packages-dev", true)] {
+        for package in lock[section].as_array().into_iter().flatten() {
+            if let (Some(name), Some(version)) =
+                (package["name"].as_str(), package["version"].as_str())
+            {
+                locked.push(Locked {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                    dev,
+                    ..Default::default()
+                });
+            }
+        }
+    }
+
+    Ok(locked)
+}
+
+/// NuGet `packages.lock.json`: packages per target framework, typed
+/// `Direct`, `Transitive`, `CentralTransitive` or `Project`
+fn nuget(content: &str) -> Result<Vec<Locked>> {
+    let lock: serde_json::Value = serde_json::from_str(content).context("Invalid JSON")?;
+    let mut locked: Vec<Locked> = Vec::new();
+
+    for framework in lock["dependencies"]
+        .as_object()
+        .into_iter()
+        .flat_map(|f| f.values())
+    {
+        for (name, package) in framework.as_object().into_iter().flatten() {
+            let kind = package["type"].as_str().unwrap_or_default();
+            let Some(version) = package["resolved"].as_str() else {
+                continue;
+            };
+            if kind == "Project" || locked.iter().any(|l| l.name.eq_ignore_ascii_case(name)) {
+                continue;
+            }
+            locked.push(Locked {
+                name: name.clone(),
+                version: version.to_string(),
+                direct: Some(kind == "Direct"),
+                ..Default::default()
+            });
+        }
+    }
+
+    Ok(locked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dependency(name: &str, version: &str) -> Dependency {
+        Dependency {
+            name: name.to_string(),
+            version: version.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn summary(dependencies: &[Dependency]) -> Vec<(&str, Option<&str>, bool)> {
+        dependencies
+            .iter()
+            .map(|d| (d.name.as_str(), d.resolved.as_deref(), d.transitive))
+            .collect()
+    }
+
+    fn apply_lockfile(
+        name: &str,
+        content: &str,
+        project_type: ProjectType,
+        mut dependencies: Vec<Dependency>,
+    ) -> Vec<Dependency> {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(name), content).unwrap();
+        fs::create_dir(dir.path().join(".git")).unwrap();
+
+        // A workspace member only gets the versions of its own dependencies
+        let member = dir.path().join("crates/member");
+        fs::create_dir_all(&member).unwrap();
+        let mut member_dependencies = dependencies.clone();
+        apply(&member, &project_type, &mut member_dependencies);
+        assert_eq!(member_dependencies.len(), dependencies.len());
+
+        apply(dir.path(), &project_type, &mut dependencies);
+        for (member, root) in member_dependencies.iter().zip(&dependencies) {
+            assert_eq!(member.resolved, root.resolved);
+        }
+        dependencies
+    }
+
+    #[test]
+    fn test_cargo_lock() {
+        let lock = r#"
+version = 3
+
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = ["rand 0.8.5", "serde"]
+
+[[package]]
+name = "rand"
+version = "0.7.3"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "rand"
+version = "0.8.5"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "serde_json"
+version = "1.0.117"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "itoa"
+version = "1.0.11"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+        let dependencies = apply_lockfile(
+            "Cargo.lock",
+            lock,
+            ProjectType::Rust,
+            vec![dependency("rand", "0.8"), dependency("serde-json", "1")],
+        );
+        assert_eq!(
+            summary(&dependencies),
+            vec![
+                ("rand", Some("0.8.5"), false),
+                ("serde-json", Some("1.0.117"), false),
+                ("itoa", Some("1.0.11"), true),
+            ]
+        );
+        assert_eq!(dependencies[0].version_in_use(), "0.8.5");
+        assert_eq!(dependencies[0].version, "0.8");
+    }
+
+    #[test]
+    fn test_node_lockfiles() {
+        let package_lock = r#"{
+  "lockfileVersion": 3,
+  "packages": {
+    "": { "name": "web", "dependencies": { "react": "^18.2.0" } },
+    "node_modules/react": { "version": "18.3.1" },
+    "node_modules/loose-envify": { "version": "1.4.0" },
+    "node_modules/vitest": { "version": "1.6.0", "dev": true },
+    "node_modules/vitest/node_modules/react": { "version": "17.0.2", "dev": true }
+  }
+}"#;
+        let dependencies = apply_lockfile(
+            "package-lock.json",
+            package_lock,
+            ProjectType::Node,
+            vec![dependency("react", "^18.2.0")],
+        );
+        assert_eq!(
+            summary(&dependencies),
+            vec![
+                ("react", Some("18.3.1"), false),
+                ("loose-envify", Some("1.4.0"), true),
+                ("vitest", Some("1.6.0"), true),
+            ]
+        );
+        assert!(dependencies[2].dev_only);
+
+        let pnpm_lock = r#"lockfileVersion: '9.0'
+
+importers:
+  .:
+    dependencies:
+      react:
+        specifier: ^18.2.0
+        version: 18.3.1
+
+packages:
+  '@babel/core@7.24.0':
+    resolution: {integrity: sha512-x}
+  react@18.3.1:
+    resolution: {integrity: sha512-y}
+  react-dom@18.3.1(react@18.3.1):
+    resolution: {integrity: sha512-z}
+"#;
+        let dependencies = apply_lockfile(
+            "pnpm-lock.yaml",
+            pnpm_lock,
+            ProjectType::Node,
+            vec![dependency("react", "^18.2.0")],
+        );
+        assert_eq!(
+            summary(&dependencies),
+            vec![
+                ("react", Some("18.3.1"), false),
+                ("@babel/core", Some("7.24.0"), true),
+                ("react-dom", Some("18.3.1"), true),
+            ]
+        );
+
+        let yarn_lock = r#"# yarn lockfile v1
+
+"@types/react@^18.0.0", "@types/react@^18.2.0":
+  version "18.3.3"
+  dependencies:
+    csstype "^3.0.2"
+
+lodash@^3.0.0:
+  version "3.10.1"
+
+lodash@^4.17.0:
+  version "4.17.21"
+"#;
+        let dependencies = apply_lockfile(
+            "yarn.lock",
+            yarn_lock,
+            ProjectType::Node,
+            vec![
+                dependency("lodash", "^4.17.0"),
+                dependency("@types/react", "^18.2.0"),
+            ],
+        );
+        assert_eq!(
+            summary(&dependencies),
+            vec![
+                ("lodash", Some("4.17.21"), false),
+                ("@types/react", Some("18.3.3"), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_python_go_php_and_nuget_lockfiles() {
+        let uv_lock = r#"
+version = 1
+
+[[package]]
+name = "api"
+version = "0.1.0"
+source = { editable = "." }
+
+[[package]]
+name = "fastapi"
+version = "0.111.0"
+source = { registry = "https://pypi.org/simple" }
+
+[[package]]
+name = "typing-extensions"
+version = "4.12.2"
+source = { registry = "https://pypi.org/simple" }
+"#;
+        let dependencies = apply_lockfile(
+            "uv.lock",
+            uv_lock,
+            ProjectType::Python,
+            vec![
+                dependency("FastAPI", ">=0.100"),
+                dependency("typing_extensions", "*"),
+            ],
+        );
+        assert_eq!(
+            summary(&dependencies),
+            vec![
+                ("FastAPI", Some("0.111.0"), false),
+                ("typing_extensions", Some("4.12.2"), false),
+            ]
+        );
+
+        let go_sum = "github.com/gin-gonic/gin v1.9.0 h1:a=\n\
+github.com/gin-gonic/gin v1.9.0/go.mod h1:b=\n\
+github.com/gin-gonic/gin v1.10.0 h1:c=\n\
+golang.org/x/net v0.20.0/go.mod h1:d=\n\
+github.com/goccy/go-json v0.10.2 h1:e=\n";
+        let dependencies = apply_lockfile(
+            "go.sum",
+            go_sum,
+            ProjectType::Go,
+            vec![dependency("github.com/gin-gonic/gin", "v1.10.0")],
+        );
+        assert_eq!(
+            summary(&dependencies),
+            vec![
+                ("github.com/gin-gonic/gin", Some("v1.10.0"), false),
+                ("github.com/goccy/go-json", Some("v0.10.2"), true),
+            ]
+        );
+
+        let composer_lock = r#"{
+  "packages": [
+    { "name": "laravel/framework", "version": "v11.9.2" },
+    { "name": "symfony/console", "version": "v7.1.1" }
+  ],
+  "packages-dev": [{ "name": "phpunit/phpunit", "version": "11.2.1" }]
+}"#;
+        let dependencies = apply_lockfile(
+            "composer.lock",
+            composer_lock,
+            ProjectType::Php,
+            vec![dependency("laravel/framework", "^11.0")],
+        );
+        assert_eq!(
+            summary(&dependencies),
+            vec![
+                ("laravel/framework", Some("v11.9.2"), false),
+                ("symfony/console", Some("v7.1.1"), true),
+                ("phpunit/phpunit", Some("11.2.1"), true),
+            ]
+        );
+        assert!(dependencies[2].dev_only);
+
+        let packages_lock = r#"{
+  "version": 1,
+  "dependencies": {
+    "net8.0": {
+      "Serilog": { "type": "Direct", "requested": "[3.1.0, )", "resolved": "3.1.1" },
+      "System.Memory": { "type": "Transitive", "resolved": "4.5.5" },
+      "Shared": { "type": "Project" }
+    },
+    "net9.0": {
+      "Serilog": { "type": "Direct", "requested": "[3.1.0, )", "resolved": "3.1.1" }
+    }
+  }
+}"#;
+        let dependencies = apply_lockfile(
+            "packages.lock.json",
+            packages_lock,
+            ProjectType::DotNet,
+            vec![dependency("serilog", "3.1.0")],
+        );
+        assert_eq!(
+            summary(&dependencies),
+            vec![
+                ("serilog", Some("3.1.1"), false),
+                ("System.Memory", Some("4.5.5"), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_without_lockfile_nothing_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut dependencies = vec![dependency("serde", "1")];
+        apply(dir.path(), &ProjectType::Rust, &mut dependencies);
+        assert_eq!(summary(&dependencies), vec![("serde", None, false)]);
+    }
+
+    #[test]
+    fn test_lockfile_search_stays_within_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let lock = r#"
+[[package]]
+name = "serde"
+version = "1.0.203"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+        fs::write(dir.path().join("Cargo.lock"), lock).unwrap();
+
+        // A lockfile above an unrelated project, or above its repository, is ignored
+        let unrelated = dir.path().join("projects/app");
+        fs::create_dir_all(&unrelated).unwrap();
+        let mut dependencies = vec![dependency("serde", "1")];
+        apply(&unrelated, &ProjectType::Rust, &mut dependencies);
+        assert_eq!(summary(&dependencies), vec![("serde", None, false)]);
+
+        fs::create_dir(unrelated.join(".git")).unwrap();
+        apply(&unrelated, &ProjectType::Rust, &mut dependencies);
+        assert_eq!(summary(&dependencies), vec![("serde", None, false)]);
+
+        // A workspace root listing the project shares its lockfile
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        )
+        .unwrap();
+        let member = dir.path().join("crates/core");
+        fs::create_dir_all(&member).unwrap();
+        fs::write(member.join("Cargo.toml"), "[package]\nname = \"core\"\n").unwrap();
+        apply(&member, &ProjectType::Rust, &mut dependencies);
+        assert_eq!(
+            summary(&dependencies),
+            vec![("serde", Some("1.0.203"), false)]
+        );
+    }
+}
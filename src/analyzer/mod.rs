@@ -1,12 +1,15 @@
+pub mod control;
 pub mod csharp;
 pub mod detector;
 pub mod generic;
-pub mod project;
+pub mod lockfile;
+pub mod msbuild;
+pub mod solution;
+pub mod symbols;
 
+pub use control::{AnalysisControl, AnalysisPhase, AnalysisProgress};
 #[allow(unused_imports)]
 pub use csharp::CSharpAnalyzer;
 #[allow(unused_imports)]
 pub use detector::ProjectDetector;
 pub use generic::GenericAnalyzer;
-#[allow(unused_imports)]
-pub use project::ProjectAnalyzer;
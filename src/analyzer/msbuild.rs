@@ -0,0 +1,909 @@
+//! A small MSBuild evaluator for project files.
+//!
+//! It covers the part of MSBuild evaluation that decides a project's
+//! properties and references: `Directory.Build.props` and
+//! `Directory.Packages.props` are imported ahead of the project, `<Import>`s
+//! are followed, properties are evaluated before items, `$(Property)`
+//! references are expanded and `Condition`s are checked. Targets, tasks and
+//! property functions other than `GetPathOfFileAbove` are out of scope.
+
+use anyhow::{Context, Result};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::types::Dependency;
+
+/// Files MSBuild imports implicitly, found in the project directory or the
+/// closest parent that has one
+const IMPLICIT_IMPORTS: &[&str] = &["Directory.Build.props", "Directory.Packages.props"];
+
+/// Imports nested deeper than this are assumed to be a cycle
+const MAX_IMPORT_DEPTH: usize = 16;
+
+fn property_reference() -> &'static Regex {
+    static PROPERTY: OnceLock<Regex> = OnceLock::new();
+    PROPERTY.get_or_init(|| Regex::new(r"\$\(\s*([A-Za-z_][A-Za-z0-9_\-]*)\s*\)").unwrap())
+}
+
+fn file_above() -> &'static Regex {
+    static FILE_ABOVE: OnceLock<Regex> = OnceLock::new();
+    FILE_ABOVE.get_or_init(|| {
+        Regex::new(r"\$\(\[MSBuild\]::GetPathOfFileAbove\(\s*'?([^',)]+?)'?\s*(?:,\s*'?([^')]*?)'?\s*)?\)\)")
+            .unwrap()
+    })
+}
+
+/// The evaluated properties and references of one project file
+#[derive(Debug, Clone, Default)]
+pub struct Evaluation {
+    /// Property values by lowercase name, as MSBuild names are case-insensitive
+    properties: HashMap<String, String>,
+    /// `PackageReference`s and `GlobalPackageReference`s with resolved versions
+    pub packages: Vec<Dependency>,
+    /// `ProjectReference` paths as written, with `/` separators
+    pub project_references: Vec<String>,
+    /// The project SDK without its version: `Microsoft.NET.Sdk.Web`
+    pub sdk: Option<String>,
+}
+
+impl Evaluation {
+    /// The value of a property, if it is set and not empty
+    pub fn property(&self, name: &str) -> Option<&str> {
+        self.properties
+            .get(&name.to_lowercase())
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+    }
+
+    /// `TargetFramework`, or each of `TargetFrameworks`, or the
+    /// `TargetFrameworkVersion` of a .NET Framework project as a moniker
+    pub fn target_frameworks(&self) -> Vec<String> {
+        if let Some(framework) = self.property("TargetFramework") {
+            return vec![framework.to_string()];
+        }
+        if let Some(frameworks) = self.property("TargetFrameworks") {
+            return split_list(frameworks);
+        }
+        // `v4.7.2` → `net472`
+        self.property("TargetFrameworkVersion")
+            .map(|v| vec![format!("net{}", v.trim_start_matches('v').replace('.', ""))])
+            .unwrap_or_default()
+    }
+}
+
+/// Evaluates `project_file` with its implicit and explicit imports.
+///
+/// A project listing `TargetFrameworks` without a `TargetFramework` is also
+/// evaluated once per framework, the way restore does, so references that
+/// only apply to one framework are still found.
+pub fn evaluate(project_file: &Path) -> Result<Evaluation> {
+    let mut evaluation = Evaluator::new(project_file, HashMap::new()).run()?;
+
+    if evaluation.property("TargetFramework").is_none() {
+        let frameworks = split_list(evaluation.property("TargetFrameworks").unwrap_or_default());
+        for framework in frameworks {
+            let global = HashMap::from([("targetframework".to_string(), framework)]);
+            let inner = Evaluator::new(project_file, global).run()?;
+            for package in inner.packages {
+                if !evaluation.packages.iter().any(|p| p.name == package.name) {
+                    evaluation.packages.push(package);
+                }
+            }
+            for reference in inner.project_references {
+                if !evaluation.project_references.contains(&reference) {
+                    evaluation.project_references.push(reference);
+                }
+            }
+        }
+    }
+
+    Ok(evaluation)
+}
+
+/// An XML element with its attributes, text and child elements
+#[derive(Debug, Clone, Default)]
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    text: String,
+    children: Vec<Element>,
+}
+
+impl Element {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn parse(content: &str) -> Result<Element> {
+        let mut reader = Reader::from_str(content);
+        let mut stack = vec![Element::default()];
+
+        loop {
+            match reader.read_event()? {
+                Event::Start(start) => stack.push(Self::open(&start)?),
+                Event::Empty(start) => {
+                    let element = Self::open(&start)?;
+                    stack.last_mut().unwrap().children.push(element);
+                }
+                Event::End(_) if stack.len() > 1 => {
+                    let element = stack.pop().unwrap();
+                    stack.last_mut().unwrap().children.push(element);
+                }
+                Event::Text(text) => stack.last_mut().unwrap().text.push_str(&text.unescape()?),
+                Event::CData(data) => stack
+                    .last_mut()
+                    .unwrap()
+                    .text
+                    .push_str(&String::from_utf8_lossy(&data)),
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        // The document's children; the root is the one element among them
+        stack
+            .swap_remove(0)
+            .children
+            .into_iter()
+            .next()
+            .context("No root element")
+    }
+
+    fn open(start: &quick_xml::events::BytesStart) -> Result<Element> {
+        let mut element = Element {
+            name: String::from_utf8_lossy(start.local_name().as_ref()).into_owned(),
+            ..Default::default()
+        };
+        for attribute in start.attributes().flatten() {
+            element.attributes.push((
+                String::from_utf8_lossy(attribute.key.local_name().as_ref()).into_owned(),
+                attribute.unescape_value()?.into_owned(),
+            ));
+        }
+        Ok(element)
+    }
+}
+
+/// An item being evaluated: its type, identity and metadata
+#[derive(Debug)]
+struct Item {
+    kind: String,
+    include: String,
+    /// Metadata by lowercase name
+    metadata: HashMap<String, String>,
+}
+
+struct Evaluator {
+    project_file: PathBuf,
+    properties: HashMap<String, String>,
+    /// Global properties, which the project cannot override
+    global: HashSet<String>,
+    /// Item groups in evaluation order, with the directory of their file
+    item_groups: Vec<(PathBuf, Element)>,
+    /// Directory of the file being evaluated, for relative paths
+    directory: PathBuf,
+    depth: usize,
+    sdk: Option<String>,
+}
+
+impl Evaluator {
+    fn new(project_file: &Path, global: HashMap<String, String>) -> Self {
+        let directory = project_file
+            .parent()
+            .unwrap_or(Path::new("."))
+            .to_path_buf();
+        let mut evaluator = Self {
+            project_file: project_file.to_path_buf(),
+            global: global.keys().cloned().collect(),
+            properties: global,
+            item_groups: Vec::new(),
+            directory: directory.clone(),
+            depth: 0,
+            sdk: None,
+        };
+
+        let name = project_file
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default();
+        let file = project_file
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default();
+        evaluator.set("MSBuildProjectName", name);
+        evaluator.set("MSBuildProjectFile", file);
+        evaluator.set("MSBuildProjectDirectory", &directory.to_string_lossy());
+        evaluator.set("Configuration", "Debug");
+        evaluator.set("Platform", "AnyCPU");
+        evaluator
+    }
+
+    /// Evaluates properties across the implicit imports and the project,
+    /// then items with the final property values
+    fn run(mut self) -> Result<Evaluation> {
+        let directory = self.directory.clone();
+        for name in IMPLICIT_IMPORTS {
+            if let Some(file) = find_above(&directory, name) {
+                self.import(&file);
+            }
+        }
+        let project_file = self.project_file.clone();
+        self.load(&project_file)?;
+
+        let items = self.evaluate_items();
+        let central: HashMap<String, &str> = items
+            .iter()
+            .filter(|i| i.kind == "PackageVersion")
+            .filter_map(|i| {
+                Some((
+                    i.include.to_lowercase(),
+                    i.metadata.get("version")?.as_str(),
+                ))
+            })
+            .collect();
+
+        let mut packages = Vec::new();
+        let mut project_references = Vec::new();
+        for item in &items {
+            let metadata = |name: &str| {
+                item.metadata
+                    .get(name)
+                    .map(|v| v.trim())
+                    .filter(|v| !v.is_empty())
+            };
+            match item.kind.as_str() {
+                "PackageReference" | "GlobalPackageReference" => {
+                    let version = metadata("versionoverride")
+                        .or(metadata("version"))
+                        .or(central.get(&item.include.to_lowercase()).copied())
+                        .unwrap_or("*");
+                    // Build-time only packages: analyzers, source link, ...
+                    let dev_only = item.kind == "GlobalPackageReference"
+                        || metadata("privateassets").is_some_and(|a| a.eq_ignore_ascii_case("all"));
+                    packages.push(Dependency {
+                        name: item.include.clone(),
+                        version: version.to_string(),
+                        dev_only,
+                        ..Default::default()
+                    });
+                }
+                "ProjectReference" => project_references.push(item.include.replace('\\', "/")),
+                _ => {}
+            }
+        }
+
+        Ok(Evaluation {
+            properties: self.properties,
+            packages,
+            project_references,
+            sdk: self.sdk,
+        })
+    }
+
+    /// Evaluates an imported file; a missing or broken import is skipped
+    fn import(&mut self, file: &Path) {
+        if self.depth >= MAX_IMPORT_DEPTH {
+            tracing::warn!("Import depth exceeded at {:?}", file);
+            return;
+        }
+        if let Err(e) = self.load(file) {
+            tracing::warn!("Skipping import {:?}: {}", file, e);
+        }
+    }
+
+    fn load(&mut self, file: &Path) -> Result<()> {
+        let content = fs::read_to_string(file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        let root = Element::parse(&content)
+            .with_context(|| format!("Failed to parse {}", file.display()))?;
+
+        if file == self.project_file {
+            self.sdk = sdk(&root);
+        }
+
+        let outer = std::mem::replace(
+            &mut self.directory,
+            file.parent().unwrap_or(Path::new(".")).to_path_buf(),
+        );
+        self.depth += 1;
+        self.set_file_properties();
+        self.evaluate_properties(&root.children);
+        self.depth -= 1;
+        self.directory = outer;
+        self.set_file_properties();
+        Ok(())
+    }
+
+    /// `MSBuildThisFileDirectory` for the file being evaluated
+    fn set_file_properties(&mut self) {
+        let mut directory = self.directory.to_string_lossy().into_owned();
+        if !directory.ends_with('/') {
+            directory.push('/');
+        }
+        self.set("MSBuildThisFileDirectory", &directory);
+    }
+
+    /// The property pass over a file's top-level elements, in order
+    fn evaluate_properties(&mut self, elements: &[Element]) {
+        for element in elements {
+            match element.name.as_str() {
+                "PropertyGroup" if self.condition(element) => {
+                    for property in &element.children {
+                        if self.condition(property) {
+                            let value = self.expand(property.text.trim());
+                            self.set(&property.name, &value);
+                        }
+                    }
+                }
+                "ItemGroup" => self
+                    .item_groups
+                    .push((self.directory.clone(), element.clone())),
+                "Import" if self.condition(element) => {
+                    if let Some(file) = element
+                        .attribute("Project")
+                        .and_then(|p| self.import_path(p))
+                    {
+                        self.import(&file);
+                    }
+                }
+                "ImportGroup" if self.condition(element) => {
+                    self.evaluate_properties(&element.children)
+                }
+                "Choose" => {
+                    let branch =
+                        element
+                            .children
+                            .iter()
+                            .find(|branch| match branch.name.as_str() {
+                                "When" => self.condition(branch),
+                                _ => branch.name == "Otherwise",
+                            });
+                    if let Some(branch) = branch {
+                        self.evaluate_properties(&branch.children);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// The item pass: every item group whose condition holds once all
+    /// properties are known
+    fn evaluate_items(&self) -> Vec<Item> {
+        let mut items: Vec<Item> = Vec::new();
+
+        for (directory, group) in &self.item_groups {
+            let holds = |element: &Element| self.condition_in(element, directory);
+            if !holds(group) {
+                continue;
+            }
+
+            for element in group.children.iter().filter(|e| holds(e)) {
+                let mut metadata: HashMap<String, String> = element
+                    .attributes
+                    .iter()
+                    .filter(|(key, _)| {
+                        !["Include", "Update", "Remove", "Exclude", "Condition"]
+                            .contains(&key.as_str())
+                    })
+                    .map(|(key, value)| (key.to_lowercase(), self.expand(value)))
+                    .collect();
+                for child in element.children.iter().filter(|c| holds(c)) {
+                    metadata.insert(child.name.to_lowercase(), self.expand(child.text.trim()));
+                }
+
+                let same = |item: &Item, names: &[String]| {
+                    item.kind == element.name
+                        && names.iter().any(|n| n.eq_ignore_ascii_case(&item.include))
+                };
+                if let Some(include) = element.attribute("Include") {
+                    for name in split_list(&self.expand(include)) {
+                        items.push(Item {
+                            kind: element.name.clone(),
+                            include: name,
+                            metadata: metadata.clone(),
+                        });
+                    }
+                } else if let Some(update) = element.attribute("Update") {
+                    let names = split_list(&self.expand(update));
+                    for item in items.iter_mut().filter(|i| same(i, &names)) {
+                        item.metadata.extend(metadata.clone());
+                    }
+                } else if let Some(remove) = element.attribute("Remove") {
+                    let names = split_list(&self.expand(remove));
+                    items.retain(|i| !same(i, &names));
+                }
+            }
+        }
+
+        items
+    }
+
+    fn set(&mut self, name: &str, value: &str) {
+        let key = name.to_lowercase();
+        if !self.global.contains(&key) {
+            self.properties.insert(key, value.to_string());
+        }
+    }
+
+    /// Replaces `$(Name)` with the property's value, or nothing if unset
+    fn expand(&self, text: &str) -> String {
+        property_reference()
+            .replace_all(text, |captures: &regex::Captures| {
+                self.properties
+                    .get(&captures[1].to_lowercase())
+                    .cloned()
+                    .unwrap_or_default()
+            })
+            .into_owned()
+    }
+
+    /// The file an `<Import Project="...">` names, if it exists
+    fn import_path(&self, project: &str) -> Option<PathBuf> {
+        let project = self.expand(project);
+        let path = match file_above().captures(&project) {
+            Some(captures) => {
+                let start = captures
+                    .get(2)
+                    .map(|d| self.directory.join(d.as_str().trim()))
+                    .unwrap_or_else(|| self.directory.clone());
+                find_above(&start, captures[1].trim())?
+            }
+            None if project.contains("$(") => return None,
+            None => self.directory.join(project.replace('\\', "/")),
+        };
+        path.is_file().then_some(path)
+    }
+
+    fn condition(&self, element: &Element) -> bool {
+        self.condition_in(element, &self.directory)
+    }
+
+    /// Whether an element's `Condition` holds; conditions this evaluator
+    /// cannot read are treated as false
+    fn condition_in(&self, element: &Element, directory: &Path) -> bool {
+        let Some(condition) = element.attribute("Condition") else {
+            return true;
+        };
+        let result = Condition::new(condition, self, directory).and_then(|mut c| c.evaluate());
+        if result.is_none() {
+            tracing::debug!("Unsupported MSBuild condition: {}", condition);
+        }
+        result.unwrap_or(false)
+    }
+}
+
+/// The closest `name` in `directory` or one of its parents
+fn find_above(directory: &Path, name: &str) -> Option<PathBuf> {
+    directory
+        .ancestors()
+        .map(|d| d.join(name))
+        .find(|candidate| candidate.is_file())
+}
+
+/// `<Project Sdk="Microsoft.NET.Sdk.Web/8.0.0">` or `<Sdk Name="..." />`;
+/// the first one when a project lists several
+fn sdk(root: &Element) -> Option<String> {
+    let value = root.attribute("Sdk").or_else(|| {
+        root.children
+            .iter()
+            .find(|e| e.name == "Sdk")
+            .and_then(|e| e.attribute("Name"))
+    })?;
+    let name = value.split(';').next()?.split('/').next()?.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// `A;B; C` → `["A", "B", "C"]`
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(';')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// A quoted string or property reference, already expanded
+    Value(String),
+    /// An unquoted word: `and`, `or`, `true`, a number or a function name
+    Word(String),
+    Operator(&'static str),
+}
+
+/// A `Condition` attribute: comparisons, `and`/`or`/`!`, parentheses,
+/// `Exists(...)` and `HasTrailingSlash(...)`
+struct Condition<'a> {
+    tokens: Vec<Token>,
+    position: usize,
+    directory: &'a Path,
+}
+
+impl<'a> Condition<'a> {
+    fn new(text: &str, evaluator: &Evaluator, directory: &'a Path) -> Option<Self> {
+        let mut tokens = Vec::new();
+        let chars: Vec<char> = text.chars().collect();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+            if c.is_whitespace() {
+                i += 1;
+            } else if c == '\'' {
+                let end = i + 1 + chars[i + 1..].iter().position(|&c| c == '\'')?;
+                let quoted: String = chars[i + 1..end].iter().collect();
+                tokens.push(Token::Value(evaluator.expand(&quoted)));
+                i = end + 1;
+            } else if c == '$' && chars.get(i + 1) == Some(&'(') {
+                let end = i + chars[i..].iter().position(|&c| c == ')')?;
+                let reference: String = chars[i..=end].iter().collect();
+                tokens.push(Token::Value(evaluator.expand(&reference)));
+                i = end + 1;
+            } else if let Some(operator) = ["==", "!=", "<=", ">=", "<", ">", "!", "(", ")", ","]
+                .into_iter()
+                .find(|op| chars[i..].starts_with(&op.chars().collect::<Vec<_>>()))
+            {
+                tokens.push(Token::Operator(operator));
+                i += operator.len();
+            } else if c.is_alphanumeric() || c == '_' || c == '.' {
+                let end = chars[i..]
+                    .iter()
+                    .position(|&c| !(c.is_alphanumeric() || c == '_' || c == '.'))
+                    .map_or(chars.len(), |n| i + n);
+                tokens.push(Token::Word(chars[i..end].iter().collect()));
+                i = end;
+            } else {
+                return None;
+            }
+        }
+
+        Some(Self {
+            tokens,
+            position: 0,
+            directory,
+        })
+    }
+
+    fn evaluate(&mut self) -> Option<bool> {
+        let result = self.or()?;
+        (self.position == self.tokens.len()).then_some(result)
+    }
+
+    fn or(&mut self) -> Option<bool> {
+        let mut result = self.and()?;
+        while self.keyword("or") {
+            // Both sides are evaluated so a malformed right side is an error
+            result = self.and()? || result;
+        }
+        Some(result)
+    }
+
+    fn and(&mut self) -> Option<bool> {
+        let mut result = self.unary()?;
+        while self.keyword("and") {
+            result = self.unary()? && result;
+        }
+        Some(result)
+    }
+
+    fn unary(&mut self) -> Option<bool> {
+        if self.operator("!") {
+            return Some(!self.unary()?);
+        }
+        if self.operator("(") {
+            let result = self.or()?;
+            return self.operator(")").then_some(result);
+        }
+
+        let left = self.operand()?;
+        let Some(Token::Operator(op)) = self.tokens.get(self.position).cloned() else {
+            return as_bool(&left);
+        };
+        if !["==", "!=", "<", ">", "<=", ">="].contains(&op) {
+            return as_bool(&left);
+        }
+        self.position += 1;
+        let right = self.operand()?;
+
+        Some(match op {
+            "==" => left.eq_ignore_ascii_case(&right),
+            "!=" => !left.eq_ignore_ascii_case(&right),
+            _ => {
+                let (left, right) = (left.parse::<f64>().ok()?, right.parse::<f64>().ok()?);
+                match op {
+                    "<" => left < right,
+                    ">" => left > right,
+                    "<=" => left <= right,
+                    _ => left >= right,
+                }
+            }
+        })
+    }
+
+    /// A value, a word or a function call
+    fn operand(&mut self) -> Option<String> {
+        match self.tokens.get(self.position).cloned()? {
+            Token::Value(value) => {
+                self.position += 1;
+                Some(value)
+            }
+            Token::Word(word) => {
+                self.position += 1;
+                if !self.operator("(") {
+                    return Some(word);
+                }
+                let argument = self.operand()?;
+                if !self.operator(")") {
+                    return None;
+                }
+                let result = match word.to_lowercase().as_str() {
+                    "exists" => {
+                        let path = argument.trim().replace('\\', "/");
+                        !path.is_empty() && self.directory.join(path).exists()
+                    }
+                    "hastrailingslash" => argument.ends_with('/') || argument.ends_with('\\'),
+                    _ => return None,
+                };
+                Some(result.to_string())
+            }
+            Token::Operator(_) => None,
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let found = matches!(
+            self.tokens.get(self.position),
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword)
+        );
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn operator(&mut self, operator: &'static str) -> bool {
+        let found = self.tokens.get(self.position) == Some(&Token::Operator(operator));
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+}
+
+fn as_bool(value: &str) -> Option<bool> {
+    if value.eq_ignore_ascii_case("true") {
+        Some(true)
+    } else if value.eq_ignore_ascii_case("false") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, path: &str, content: &str) -> PathBuf {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn versions(evaluation: &Evaluation) -> Vec<(&str, &str, bool)> {
+        evaluation
+            .packages
+            .iter()
+            .map(|p| (p.name.as_str(), p.version.as_str(), p.dev_only))
+            .collect()
+    }
+
+    #[test]
+    fn test_directory_props_and_central_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            "Directory.Build.props",
+            r#"<Project>
+  <PropertyGroup>
+    <LangVersion>latest</LangVersion>
+    <SerilogVersion>4.0.0</SerilogVersion>
+    <Company>Contoso</Company>
+  </PropertyGroup>
+  <ItemGroup>
+    <PackageReference Include="StyleCop.Analyzers" PrivateAssets="all" />
+  </ItemGroup>
+</Project>"#,
+        );
+        write(
+            root,
+            "Directory.Packages.props",
+            r#"<Project>
+  <PropertyGroup>
+    <ManagePackageVersionsCentrally>true</ManagePackageVersionsCentrally>
+  </PropertyGroup>
+  <ItemGroup>
+    <PackageVersion Include="Serilog" Version="$(SerilogVersion)" />
+    <PackageVersion Include="Dapper" Version="2.1.35" />
+    <PackageVersion Include="StyleCop.Analyzers" Version="1.1.118" />
+    <GlobalPackageReference Include="Nerdbank.GitVersioning" Version="3.6.143" />
+  </ItemGroup>
+</Project>"#,
+        );
+        let project = write(
+            root,
+            "src/Api/Api.csproj",
+            r#"<Project Sdk="Microsoft.NET.Sdk.Web">
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+    <Product>$(Company) $(MSBuildProjectName)</Product>
+    <SerilogVersion>3.1.1</SerilogVersion>
+  </PropertyGroup>
+  <ItemGroup>
+    <PackageReference Include="Serilog" />
+    <PackageReference Include="Dapper" VersionOverride="2.0.151" />
+    <PackageReference
+        Include="Polly">
+      <Version>
+        8.4.0
+      </Version>
+    </PackageReference>
+  </ItemGroup>
+</Project>"#,
+        );
+
+        let evaluation = evaluate(&project).unwrap();
+        assert_eq!(evaluation.property("LangVersion"), Some("latest"));
+        assert_eq!(evaluation.property("product"), Some("Contoso Api"));
+        assert_eq!(evaluation.property("Missing"), None);
+
+        // Items are evaluated after every property, so the project's
+        // `SerilogVersion` wins over the one the central version was written with
+        assert_eq!(
+            versions(&evaluation),
+            vec![
+                ("StyleCop.Analyzers", "1.1.118", true),
+                ("Nerdbank.GitVersioning", "3.6.143", true),
+                ("Serilog", "3.1.1", false),
+                ("Dapper", "2.0.151", false),
+                ("Polly", "8.4.0", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_conditions_imports_and_item_updates() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            "Directory.Build.props",
+            r#"<Project><PropertyGroup><RepoRoot>$(MSBuildThisFileDirectory)</RepoRoot></PropertyGroup></Project>"#,
+        );
+        write(
+            root,
+            "src/Directory.Build.props",
+            r#"<Project>
+  <Import Project="$([MSBuild]::GetPathOfFileAbove('Directory.Build.props', '$(MSBuildThisFileDirectory)../'))" />
+  <Import Project="build\common.props" Condition="Exists('build\common.props')" />
+  <Import Project="missing.props" />
+</Project>"#,
+        );
+        write(
+            root,
+            "src/build/common.props",
+            r#"<Project><PropertyGroup><UseSentry>true</UseSentry></PropertyGroup></Project>"#,
+        );
+        let project = write(
+            root,
+            "src/Worker/Worker.csproj",
+            r#"<Project Sdk="Microsoft.NET.Sdk.Worker">
+  <PropertyGroup Condition=" '$(Configuration)|$(Platform)' == 'Debug|AnyCPU' ">
+    <DefineConstants>$(DefineConstants);TRACE</DefineConstants>
+  </PropertyGroup>
+  <PropertyGroup Condition="'$(Configuration)' == 'Release'">
+    <Optimize>true</Optimize>
+  </PropertyGroup>
+  <ItemGroup Condition="'$(UseSentry)' == 'true' and !Exists('nothing.txt')">
+    <PackageReference Include="Sentry;Sentry.Serilog" Version="4.9.0" />
+  </ItemGroup>
+  <ItemGroup>
+    <PackageReference Include="Humanizer" Version="2.14.1" />
+    <PackageReference Update="Sentry.Serilog" Version="4.10.0" />
+    <PackageReference Remove="Humanizer" />
+    <PackageReference Include="Legacy" Version="1.0.0" Condition="$(UseLegacy)" />
+    <ProjectReference Include="..\Shared\Shared.csproj" />
+  </ItemGroup>
+</Project>"#,
+        );
+
+        let evaluation = evaluate(&project).unwrap();
+        assert_eq!(evaluation.sdk.as_deref(), Some("Microsoft.NET.Sdk.Worker"));
+        assert_eq!(evaluation.property("DefineConstants"), Some(";TRACE"));
+        assert_eq!(evaluation.property("Optimize"), None);
+        let repo_root = format!("{}/", root.join("src/..").display());
+        assert_eq!(evaluation.property("RepoRoot"), Some(repo_root.as_str()));
+        assert_eq!(
+            versions(&evaluation),
+            vec![
+                ("Sentry", "4.9.0", false),
+                ("Sentry.Serilog", "4.10.0", false)
+            ]
+        );
+        assert_eq!(
+            evaluation.project_references,
+            vec!["../Shared/Shared.csproj"]
+        );
+    }
+
+    #[test]
+    fn test_multi_targeting_collects_framework_specific_references() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = write(
+            dir.path(),
+            "Lib.csproj",
+            r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFrameworks>netstandard2.0;net8.0</TargetFrameworks>
+  </PropertyGroup>
+  <ItemGroup Condition="'$(TargetFramework)' == 'netstandard2.0'">
+    <PackageReference Include="System.Text.Json" Version="8.0.4" />
+  </ItemGroup>
+  <Choose>
+    <When Condition="'$(TargetFramework)' == 'net8.0'">
+      <ItemGroup><PackageReference Include="Microsoft.Extensions.Http" Version="8.0.0" /></ItemGroup>
+    </When>
+  </Choose>
+</Project>"#,
+        );
+
+        let evaluation = evaluate(&project).unwrap();
+        assert_eq!(evaluation.property("TargetFramework"), None);
+        assert_eq!(
+            evaluation.target_frameworks(),
+            vec!["netstandard2.0", "net8.0"]
+        );
+        assert_eq!(evaluation.sdk.as_deref(), Some("Microsoft.NET.Sdk"));
+        assert_eq!(
+            versions(&evaluation),
+            vec![
+                ("System.Text.Json", "8.0.4", false),
+                ("Microsoft.Extensions.Http", "8.0.0", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_condition_expressions() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("App.csproj");
+        let evaluator = Evaluator::new(&project, HashMap::new());
+        let holds = |text: &str| {
+            Condition::new(text, &evaluator, dir.path()).and_then(|mut c| c.evaluate())
+        };
+
+        assert_eq!(holds("'$(Configuration)' == 'debug'"), Some(true));
+        assert_eq!(
+            holds("'$(Missing)' != '' or ('a' == 'a' and !false)"),
+            Some(true)
+        );
+        assert_eq!(holds("'1.5' < '2'"), Some(true));
+        assert_eq!(
+            holds("HasTrailingSlash('$(MSBuildProjectDirectory)')"),
+            Some(false)
+        );
+        assert_eq!(holds("$(Missing)"), None);
+        assert_eq!(holds("$(TargetFramework.StartsWith('net4'))"), None);
+    }
+}
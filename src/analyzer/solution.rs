@@ -0,0 +1,142 @@
+//! .NET solution files: the classic `.sln` text format and the XML `.slnx`
+//! format introduced with Visual Studio 17.13.
+
+use anyhow::Result;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Project file extensions a solution can reference; anything else in a
+/// solution (solution folders, website projects, ...) is skipped
+const PROJECT_EXTENSIONS: &[&str] = &["csproj", "fsproj", "vbproj"];
+
+/// The solution file in `path`, preferring `.slnx` when both exist
+pub fn find_solution(path: &Path) -> Option<PathBuf> {
+    let mut solutions: Vec<PathBuf> = fs::read_dir(path)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|p| matches!(p.extension().and_then(|e| e.to_str()), Some("sln" | "slnx")))
+        .collect();
+    solutions.sort_by_key(|p| (p.extension() != Some("slnx".as_ref()), p.clone()));
+    solutions.into_iter().next()
+}
+
+/// Paths of the projects listed in a solution, resolved against its directory
+pub fn project_paths(solution: &Path) -> Result<Vec<PathBuf>> {
+    let content = fs::read_to_string(solution)?;
+    let relative = match solution.extension().and_then(|e| e.to_str()) {
+        Some("slnx") => parse_slnx(&content)?,
+        _ => parse_sln(&content),
+    };
+
+    let base = solution.parent().unwrap_or(Path::new("."));
+    Ok(relative
+        .into_iter()
+        .map(|p| p.replace('\\', "/"))
+        .filter(|p| {
+            Path::new(p)
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| PROJECT_EXTENSIONS.contains(&e))
+        })
+        .map(|p| base.join(p))
+        .collect())
+}
+
+/// `Project("{type}") = "Name", "src\Name\Name.csproj", "{guid}"` lines
+fn parse_sln(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (_, declaration) = line.trim_start().split_once("Project(")?;
+            let (_, values) = declaration.split_once('=')?;
+            let path = values.split(',').nth(1)?;
+            Some(path.trim().trim_matches('"').to_string())
+        })
+        .collect()
+}
+
+/// `<Project Path="src/Name/Name.csproj" />` elements, in or out of folders
+fn parse_slnx(content: &str) -> Result<Vec<String>> {
+    let mut reader = Reader::from_str(content);
+    let mut paths = Vec::new();
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(element) | Event::Empty(element)
+                if element.name().as_ref() == b"Project" =>
+            {
+                for attribute in element.attributes().flatten() {
+                    if attribute.key.as_ref() == b"Path" {
+                        paths.push(attribute.unescape_value()?.into_owned());
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_sln_projects() {
+        let content = r#"
+Microsoft Visual Studio Solution File, Format Version 12.00
+Project("{FAE04EC0-301F-11D3-BF4B-00C04F79EFBC}") = "Web", "src\Web\Web.csproj", "{1B2C}"
+EndProject
+Project("{2150E333-8FDC-42A3-9474-1A3956D46DE8}") = "tests", "tests", "{3D4E}"
+EndProject
+Project("{F2A71F9B-5D33-465A-A702-920D77279786}") = "Core", "src\Core\Core.fsproj", "{5F6A}"
+EndProject
+Global
+EndGlobal
+"#;
+        let dir = tempfile::tempdir().unwrap();
+        let sln = dir.path().join("App.sln");
+        fs::write(&sln, content).unwrap();
+
+        let projects = project_paths(&sln).unwrap();
+        assert_eq!(
+            projects,
+            vec![
+                dir.path().join("src/Web/Web.csproj"),
+                dir.path().join("src/Core/Core.fsproj"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parses_slnx_projects() {
+        let content = r#"<Solution>
+  <Folder Name="/src/">
+    <Project Path="src/Web/Web.csproj" />
+    <Project Path="src/Shared/Shared.csproj">
+      <BuildType Project="Release" />
+    </Project>
+  </Folder>
+  <Project Path="tests/Web.Tests/Web.Tests.csproj" />
+  <Folder Name="/docs/"><File Path="README.md" /></Folder>
+</Solution>"#;
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("App.sln"), "").unwrap();
+        fs::write(dir.path().join("App.slnx"), content).unwrap();
+
+        let slnx = find_solution(dir.path()).unwrap();
+        assert_eq!(slnx, dir.path().join("App.slnx"));
+
+        let names: Vec<_> = project_paths(&slnx)
+            .unwrap()
+            .iter()
+            .map(|p| p.file_stem().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, vec!["Web", "Shared", "Web.Tests"]);
+    }
+}
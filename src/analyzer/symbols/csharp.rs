@@ -0,0 +1,335 @@
+use anyhow::Result;
+use std::path::Path;
+
+use super::symbol;
+use crate::analyzer::csharp::CSharpAnalyzer;
+use crate::types::{
+    CSharpFile, ClassInfo, EnumInfo, FieldInfo, InterfaceInfo, MethodInfo, PropertyInfo, Symbol,
+    SymbolKind, TypeKind,
+};
+
+/// Parses a C# file with [`CSharpAnalyzer`] and maps its declarations into
+/// symbols, nested under one module per namespace they are declared in
+pub(super) fn extract(path: &Path, source: &str) -> Result<Vec<Symbol>> {
+    let file = CSharpAnalyzer::new()?.analyze_source(path, source)?;
+    Ok(file_symbols(&file))
+}
+
+fn file_symbols(file: &CSharpFile) -> Vec<Symbol> {
+    let declarations = file
+        .classes
+        .iter()
+        .map(|c| (&c.namespace, class_symbol(c)))
+        .chain(
+            file.interfaces
+                .iter()
+                .map(|i| (&i.namespace, interface_symbol(i))),
+        )
+        .chain(file.enums.iter().map(|e| (&e.namespace, enum_symbol(e))));
+
+    let mut symbols: Vec<Symbol> = Vec::new();
+    for (namespace, declaration) in declarations {
+        let namespace = match namespace {
+            Some(namespace) => namespace,
+            None => {
+                symbols.push(declaration);
+                continue;
+            }
+        };
+        let existing = symbols
+            .iter()
+            .position(|s| s.kind == SymbolKind::Module && &s.name == namespace);
+        let module = match existing {
+            Some(index) => &mut symbols[index],
+            None => {
+                symbols.push(symbol(namespace, SymbolKind::Module));
+                symbols.last_mut().unwrap()
+            }
+        };
+        module.children.push(declaration);
+    }
+    symbols
+}
+
+/// Classes, structs and records. Records keep their class or struct kind
+/// with a `record` modifier, like Java records.
+pub(super) fn class_symbol(class: &ClassInfo) -> Symbol {
+    let kind = match class.kind {
+        TypeKind::Class | TypeKind::Record => SymbolKind::Class,
+        TypeKind::Struct | TypeKind::RecordStruct => SymbolKind::Struct,
+    };
+    let mut item = symbol(&generic_name(&class.name, &class.type_parameters), kind);
+    item.modifiers = modifiers(&class.attributes, &class.modifiers);
+    if matches!(class.kind, TypeKind::Record | TypeKind::RecordStruct) {
+        item.modifiers.push("record".to_string());
+    }
+    item.bases = class
+        .base_class
+        .iter()
+        .chain(&class.interfaces)
+        .cloned()
+        .collect();
+    item.children = class
+        .fields
+        .iter()
+        .map(|field| field_symbol(field, SymbolKind::Field))
+        .chain(class.properties.iter().map(property_symbol))
+        .chain(
+            class
+                .events
+                .iter()
+                .map(|event| field_symbol(event, SymbolKind::Other("event".to_string()))),
+        )
+        .chain(class.constructors.iter().map(method_symbol))
+        .chain(class.methods.iter().map(method_symbol))
+        .collect();
+    item.location = Some(class.location);
+    item
+}
+
+fn interface_symbol(interface: &InterfaceInfo) -> Symbol {
+    let mut item = symbol(
+        &generic_name(&interface.name, &interface.type_parameters),
+        SymbolKind::Interface,
+    );
+    item.modifiers = modifiers(&interface.attributes, &interface.modifiers);
+    item.bases = interface.interfaces.clone();
+    item.children = interface
+        .properties
+        .iter()
+        .map(property_symbol)
+        .chain(interface.methods.iter().map(method_symbol))
+        .collect();
+    item.location = Some(interface.location);
+    item
+}
+
+/// Enums list their members as fields; the underlying type is the base
+fn enum_symbol(declaration: &EnumInfo) -> Symbol {
+    let mut item = symbol(&declaration.name, SymbolKind::Enum);
+    item.modifiers = modifiers(&declaration.attributes, &declaration.modifiers);
+    item.bases = declaration.underlying_type.iter().cloned().collect();
+    item.children = declaration
+        .members
+        .iter()
+        .map(|member| {
+            let mut item = symbol(&member.name, SymbolKind::Field);
+            item.location = Some(member.location);
+            item
+        })
+        .collect();
+    item.location = Some(declaration.location);
+    item
+}
+
+/// Constructors have no return type, so no `type_name`
+fn method_symbol(method: &MethodInfo) -> Symbol {
+    let mut item = symbol(
+        &generic_name(&method.name, &method.type_parameters),
+        SymbolKind::Method,
+    );
+    item.modifiers = modifiers(&method.attributes, &method.modifiers);
+    if !method.return_type.is_empty() {
+        item.type_name = Some(method.return_type.clone());
+    }
+    item.parameters = method.parameters.clone();
+    item.location = Some(method.location);
+    item
+}
+
+fn property_symbol(property: &PropertyInfo) -> Symbol {
+    let mut item = symbol(&property.name, SymbolKind::Property);
+    item.modifiers = modifiers(&property.attributes, &property.modifiers);
+    item.type_name = Some(property.prop_type.clone());
+    item.location = Some(property.location);
+    item
+}
+
+fn field_symbol(field: &FieldInfo, kind: SymbolKind) -> Symbol {
+    let mut item = symbol(&field.name, kind);
+    item.modifiers = modifiers(&field.attributes, &field.modifiers);
+    item.type_name = Some(field.field_type.clone());
+    item.location = Some(field.location);
+    item
+}
+
+/// Attributes as written (`[Parameter]`), then keywords
+fn modifiers(attributes: &[String], keywords: &[String]) -> Vec<String> {
+    attributes
+        .iter()
+        .map(|attribute| format!("[{}]", attribute))
+        .chain(keywords.iter().cloned())
+        .collect()
+}
+
+/// `Repository<T>` for a declaration with type parameters
+fn generic_name(name: &str, type_parameters: &[String]) -> String {
+    if type_parameters.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}<{}>", name, type_parameters.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_maps_csharp_file_into_symbols() {
+        let source = r#"
+namespace MyApp.Services
+{
+    public class WeatherService
+    {
+        public string City { get; set; }
+        public async void Refresh() { }
+    }
+
+    public interface IWeatherService
+    {
+        Task<int> GetTemperature();
+    }
+}
+"#;
+        let symbols = extract(Path::new("WeatherService.cs"), source).unwrap();
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0].name, "MyApp.Services");
+        assert!(matches!(symbols[0].kind, SymbolKind::Module));
+
+        let service = &symbols[0].children[0];
+        assert!(matches!(service.kind, SymbolKind::Class));
+        assert_eq!(service.modifiers, vec!["public"]);
+
+        let members: Vec<(&str, Option<&str>)> = service
+            .children
+            .iter()
+            .map(|s| (s.name.as_str(), s.type_name.as_deref()))
+            .collect();
+        assert_eq!(
+            members,
+            vec![("City", Some("string")), ("Refresh", Some("void"))]
+        );
+        assert!(matches!(service.children[0].kind, SymbolKind::Property));
+        assert_eq!(service.children[1].modifiers, vec!["public", "async"]);
+
+        let interface = &symbols[0].children[1];
+        assert!(matches!(interface.kind, SymbolKind::Interface));
+        assert_eq!(interface.children[0].name, "GetTemperature");
+    }
+
+    #[test]
+    fn test_groups_types_by_their_namespace() {
+        let source = r#"
+namespace Shop.Orders
+{
+    public class Order { }
+}
+
+namespace Shop.Billing
+{
+    public interface IInvoice { }
+
+    namespace Tax
+    {
+        public enum Rate { Zero, Standard }
+    }
+}
+
+namespace Shop.Orders
+{
+    public record OrderLine(int Quantity);
+}
+"#;
+        let symbols = extract(Path::new("Shop.cs"), source).unwrap();
+        let modules: Vec<(&str, Vec<&str>)> = symbols
+            .iter()
+            .map(|module| {
+                assert!(matches!(module.kind, SymbolKind::Module));
+                let children = module.children.iter().map(|s| s.name.as_str()).collect();
+                (module.name.as_str(), children)
+            })
+            .collect();
+        assert_eq!(
+            modules,
+            vec![
+                ("Shop.Orders", vec!["Order", "OrderLine"]),
+                ("Shop.Billing", vec!["IInvoice"]),
+                ("Shop.Billing.Tax", vec!["Rate"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_maps_records_structs_enums_and_members() {
+        let source = r#"
+[ApiController]
+public class OrdersController<T> : ControllerBase
+{
+    private readonly IOrderService service;
+    public event Action? Placed;
+
+    public OrdersController(IOrderService service) { }
+
+    [HttpGet("{id}")]
+    public Task<Order?> Get(int id) => service.Find(id);
+}
+
+public record Order(int Id, string Customer);
+public record struct Money(decimal Amount);
+public enum Status : byte { Open, Closed }
+"#;
+        let symbols = extract(Path::new("Orders.cs"), source).unwrap();
+        let names: Vec<(&str, &SymbolKind)> =
+            symbols.iter().map(|s| (s.name.as_str(), &s.kind)).collect();
+        assert_eq!(
+            names,
+            vec![
+                ("OrdersController<T>", &SymbolKind::Class),
+                ("Order", &SymbolKind::Class),
+                ("Money", &SymbolKind::Struct),
+                ("Status", &SymbolKind::Enum),
+            ]
+        );
+
+        let controller = &symbols[0];
+        assert_eq!(controller.modifiers, vec!["[ApiController]", "public"]);
+        assert_eq!(controller.bases, vec!["ControllerBase"]);
+        assert!(controller.has_annotation("ApiController"));
+
+        let members: Vec<(&str, &SymbolKind, Option<&str>)> = controller
+            .children
+            .iter()
+            .map(|s| (s.name.as_str(), &s.kind, s.type_name.as_deref()))
+            .collect();
+        assert_eq!(
+            members,
+            vec![
+                ("service", &SymbolKind::Field, Some("IOrderService")),
+                (
+                    "Placed",
+                    &SymbolKind::Other("event".to_string()),
+                    Some("Action?")
+                ),
+                ("OrdersController", &SymbolKind::Method, None),
+                ("Get", &SymbolKind::Method, Some("Task<Order?>")),
+            ]
+        );
+        let get = &controller.children[3];
+        assert_eq!(get.modifiers, vec!["[HttpGet(\"{id}\")]", "public"]);
+        assert_eq!(get.parameters[0].name, "id");
+        assert_eq!(get.parameters[0].param_type, "int");
+
+        assert_eq!(symbols[1].modifiers, vec!["public", "record"]);
+        assert_eq!(symbols[1].children.len(), 2);
+        assert_eq!(symbols[2].modifiers, vec!["public", "record"]);
+
+        assert_eq!(symbols[3].bases, vec!["byte"]);
+        let members: Vec<&str> = symbols[3]
+            .children
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(members, vec!["Open", "Closed"]);
+    }
+}
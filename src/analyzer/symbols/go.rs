@@ -0,0 +1,265 @@
+use anyhow::Result;
+use tree_sitter::Node;
+
+use super::{field_text, parse, symbol, symbol_at, text};
+use crate::types::{Symbol, SymbolKind};
+
+/// Extracts the outline of a Go file as its package module.
+///
+/// Methods are grouped under their receiver type. When the type is declared
+/// in another file of the package, they are collected in an `Impl` symbol
+/// named after it, so each file still shows the method set it contributes.
+pub(super) fn extract(source: &str) -> Result<Vec<Symbol>> {
+    let tree = parse(tree_sitter_go::language(), source)?;
+    let root = tree.root_node();
+
+    let mut declarations: Vec<Symbol> = Vec::new();
+    let mut package = None;
+    let mut cursor = root.walk();
+
+    for child in root.named_children(&mut cursor) {
+        match child.kind() {
+            "package_clause" => {
+                let mut name_cursor = child.walk();
+                package = child
+                    .named_children(&mut name_cursor)
+                    .find(|n| n.kind() == "package_identifier")
+                    .map(|n| symbol_at(text(&n, source), SymbolKind::Module, &child));
+            }
+            "type_declaration" => {
+                let mut spec_cursor = child.walk();
+                for spec in child.named_children(&mut spec_cursor) {
+                    declarations.extend(type_spec(&spec, source));
+                }
+            }
+            "function_declaration" => {
+                declarations.extend(
+                    field_text(&child, "name", source)
+                        .map(|name| symbol_at(name, SymbolKind::Function, &child)),
+                );
+            }
+            "method_declaration" => {
+                if let Some((receiver, method)) = method(&child, source) {
+                    attach_method(&mut declarations, receiver, method);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(match package {
+        Some(mut module) => {
+            module.children = declarations;
+            vec![module]
+        }
+        None => declarations,
+    })
+}
+
+/// `type X struct {...}`, `type Y interface {...}`, `type Z = W`, ...
+fn type_spec(node: &Node, source: &str) -> Option<Symbol> {
+    if !matches!(node.kind(), "type_spec" | "type_alias") {
+        return None;
+    }
+    let name = field_text(node, "name", source)?;
+    let Some(definition) = node.child_by_field_name("type") else {
+        return Some(symbol_at(name, SymbolKind::Other("type".to_string()), node));
+    };
+
+    let item = match definition.kind() {
+        "struct_type" => {
+            let mut item = symbol_at(name, SymbolKind::Struct, node);
+            item.children = struct_fields(&definition, source);
+            item
+        }
+        "interface_type" => {
+            let mut item = symbol_at(name, SymbolKind::Interface, node);
+            let mut cursor = definition.walk();
+            for spec in definition.named_children(&mut cursor) {
+                if spec.kind() == "method_spec" {
+                    item.children.extend(
+                        field_text(&spec, "name", source)
+                            .map(|name| symbol_at(name, SymbolKind::Method, &spec)),
+                    );
+                }
+            }
+            item
+        }
+        _ => symbol_at(name, SymbolKind::Other("type".to_string()), node),
+    };
+    Some(item)
+}
+
+/// Named fields, plus embedded fields under their type name
+fn struct_fields(node: &Node, source: &str) -> Vec<Symbol> {
+    let mut fields = Vec::new();
+    let mut cursor = node.walk();
+
+    for list in node.named_children(&mut cursor) {
+        if list.kind() != "field_declaration_list" {
+            continue;
+        }
+        let mut list_cursor = list.walk();
+        for declaration in list.named_children(&mut list_cursor) {
+            if declaration.kind() != "field_declaration" {
+                continue;
+            }
+            // `A, B int` declares several names in one field declaration
+            let mut name_cursor = declaration.walk();
+            let names: Vec<Node> = declaration
+                .children_by_field_name("name", &mut name_cursor)
+                .collect();
+
+            if names.is_empty() {
+                if let Some(embedded) = declaration.child_by_field_name("type") {
+                    let mut field = symbol_at(
+                        type_name(&embedded, source),
+                        SymbolKind::Field,
+                        &declaration,
+                    );
+                    field.modifiers.push("embedded".to_string());
+                    fields.push(field);
+                }
+            }
+            for name in names {
+                fields.push(symbol_at(
+                    text(&name, source),
+                    SymbolKind::Field,
+                    &declaration,
+                ));
+            }
+        }
+    }
+
+    fields
+}
+
+/// A method and the name of its receiver type
+fn method<'a>(node: &Node, source: &'a str) -> Option<(&'a str, Symbol)> {
+    let name = field_text(node, "name", source)?;
+    let receiver = node.child_by_field_name("receiver")?;
+
+    let mut cursor = receiver.walk();
+    let parameter = receiver
+        .named_children(&mut cursor)
+        .find(|n| n.kind() == "parameter_declaration")?;
+    let receiver_type = parameter.child_by_field_name("type")?;
+
+    let mut item = symbol_at(name, SymbolKind::Method, node);
+    if receiver_type.kind() == "pointer_type" {
+        item.modifiers.push("pointer receiver".to_string());
+    }
+    Some((type_name(&receiver_type, source), item))
+}
+
+/// `T` for `T`, `*T`, `T[K]` and `pkg.T`
+fn type_name<'a>(node: &Node, source: &'a str) -> &'a str {
+    match node.kind() {
+        "pointer_type" => match node.named_child(0) {
+            Some(inner) => type_name(&inner, source),
+            None => text(node, source),
+        },
+        "generic_type" => match node.child_by_field_name("type") {
+            Some(inner) => type_name(&inner, source),
+            None => text(node, source),
+        },
+        "qualified_type" => field_text(node, "name", source).unwrap_or_else(|| text(node, source)),
+        _ => text(node, source),
+    }
+}
+
+fn attach_method(declarations: &mut Vec<Symbol>, receiver: &str, method: Symbol) {
+    let owner = declarations
+        .iter()
+        .position(|d| d.name == receiver && !matches!(d.kind, SymbolKind::Function));
+
+    match owner {
+        Some(index) => declarations[index].children.push(method),
+        None => {
+            let mut methods = symbol(receiver, SymbolKind::Impl);
+            methods.children.push(method);
+            declarations.push(methods);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+package handlers
+
+import "github.com/gin-gonic/gin"
+
+type UserHandler struct {
+    *Service
+    repo, cache Store
+}
+
+type Store interface {
+    Find(id int) (*User, error)
+    Save(u *User) error
+}
+
+type HandlerFunc func(c *gin.Context)
+
+func NewUserHandler(s Store) *UserHandler { return nil }
+
+func (h *UserHandler) List(c *gin.Context) {}
+
+func (h UserHandler) Name() string { return "" }
+
+func (r *Router) Mount(g *gin.RouterGroup) {}
+
+func (s *Stack[T]) Push(v T) {}
+"#;
+
+    #[test]
+    fn test_extracts_go_outline() {
+        let symbols = extract(SOURCE).unwrap();
+        assert_eq!(symbols.len(), 1);
+        let package = &symbols[0];
+        assert_eq!(package.name, "handlers");
+        assert!(matches!(package.kind, SymbolKind::Module));
+        let clause = package.location.unwrap();
+        assert_eq!((clause.start_line, clause.end_line), (2, 2));
+
+        let names: Vec<&str> = package.children.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "UserHandler",
+                "Store",
+                "HandlerFunc",
+                "NewUserHandler",
+                "Router",
+                "Stack"
+            ]
+        );
+
+        let handler = &package.children[0];
+        assert!(matches!(handler.kind, SymbolKind::Struct));
+        let members: Vec<(&str, &Vec<String>)> = handler
+            .children
+            .iter()
+            .map(|s| (s.name.as_str(), &s.modifiers))
+            .collect();
+        assert_eq!(
+            members,
+            vec![
+                ("Service", &vec!["embedded".to_string()]),
+                ("repo", &vec![]),
+                ("cache", &vec![]),
+                ("List", &vec!["pointer receiver".to_string()]),
+                ("Name", &vec![]),
+            ]
+        );
+
+        assert!(matches!(package.children[1].kind, SymbolKind::Interface));
+        assert_eq!(package.children[1].children.len(), 2);
+        assert!(matches!(package.children[3].kind, SymbolKind::Function));
+        assert!(matches!(package.children[4].kind, SymbolKind::Impl));
+        assert_eq!(package.children[5].children[0].name, "Push");
+    }
+}
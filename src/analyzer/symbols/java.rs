@@ -0,0 +1,211 @@
+use anyhow::Result;
+use tree_sitter::Node;
+
+use super::{field_text, parse, symbol_at, text};
+use crate::types::{Symbol, SymbolKind};
+
+/// Extracts the outline of a Java file, nested under its package when it
+/// declares one.
+pub(super) fn extract(source: &str) -> Result<Vec<Symbol>> {
+    let tree = parse(tree_sitter_java::language(), source)?;
+    let root = tree.root_node();
+
+    let mut package = None;
+    let mut declarations = Vec::new();
+    let mut cursor = root.walk();
+
+    for child in root.named_children(&mut cursor) {
+        if child.kind() == "package_declaration" {
+            let mut name_cursor = child.walk();
+            package = child
+                .named_children(&mut name_cursor)
+                .find(|n| matches!(n.kind(), "identifier" | "scoped_identifier"))
+                .map(|n| symbol_at(text(&n, source), SymbolKind::Module, &child));
+        } else {
+            declarations.extend(type_declaration(&child, source));
+        }
+    }
+
+    Ok(match package {
+        Some(mut module) => {
+            module.children = declarations;
+            vec![module]
+        }
+        None => declarations,
+    })
+}
+
+/// Classes, interfaces, enums and records with their members.
+///
+/// Records are classes with a `record` modifier so they count as types.
+fn type_declaration(node: &Node, source: &str) -> Option<Symbol> {
+    let kind = match node.kind() {
+        "class_declaration" | "record_declaration" => SymbolKind::Class,
+        "interface_declaration" => SymbolKind::Interface,
+        "enum_declaration" => SymbolKind::Enum,
+        _ => return None,
+    };
+    let name = field_text(node, "name", source)?;
+
+    let mut item = symbol_at(name, kind, node);
+    item.modifiers = modifiers(node, source);
+    if node.kind() == "record_declaration" {
+        item.modifiers.push("record".to_string());
+    }
+    if let Some(body) = node.child_by_field_name("body") {
+        item.children = members(&body, source);
+    }
+    Some(item)
+}
+
+/// Methods, constructors, fields, enum constants and nested types of a
+/// class, interface or enum body
+fn members(body: &Node, source: &str) -> Vec<Symbol> {
+    let mut members = Vec::new();
+    let mut cursor = body.walk();
+
+    for member in body.named_children(&mut cursor) {
+        match member.kind() {
+            "method_declaration" | "constructor_declaration" => {
+                if let Some(name) = field_text(&member, "name", source) {
+                    let mut method = symbol_at(name, SymbolKind::Method, &member);
+                    method.modifiers = modifiers(&member, source);
+                    members.push(method);
+                }
+            }
+            "field_declaration" | "constant_declaration" => {
+                let modifiers = modifiers(&member, source);
+                let mut declarator_cursor = member.walk();
+                for declarator in
+                    member.children_by_field_name("declarator", &mut declarator_cursor)
+                {
+                    if let Some(name) = field_text(&declarator, "name", source) {
+                        let mut field = symbol_at(name, SymbolKind::Field, &member);
+                        field.modifiers = modifiers.clone();
+                        members.push(field);
+                    }
+                }
+            }
+            "enum_constant" => {
+                members.extend(
+                    field_text(&member, "name", source)
+                        .map(|name| symbol_at(name, SymbolKind::Field, &member)),
+                );
+            }
+            // Members following the constants of an enum
+            "enum_body_declarations" => members.extend(self::members(&member, source)),
+            _ => members.extend(type_declaration(&member, source)),
+        }
+    }
+
+    members
+}
+
+/// Annotations (`@RestController`, `@GetMapping("/users")`, ...) and
+/// keywords (`public`, `static`, ...) in source order
+fn modifiers(node: &Node, source: &str) -> Vec<String> {
+    let mut cursor = node.walk();
+    let Some(list) = node
+        .children(&mut cursor)
+        .find(|child| child.kind() == "modifiers")
+    else {
+        return Vec::new();
+    };
+
+    let mut modifiers = Vec::new();
+    let mut list_cursor = list.walk();
+    for modifier in list.children(&mut list_cursor) {
+        let modifier = text(&modifier, source);
+        modifiers.push(modifier.split_whitespace().collect::<Vec<_>>().join(" "));
+    }
+    modifiers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+package com.example.users;
+
+import org.springframework.web.bind.annotation.*;
+
+@RestController
+@RequestMapping("/users")
+public class UserController {
+    @Autowired
+    private UserService service;
+
+    public UserController() {}
+
+    @GetMapping("/{id}")
+    public User get(@PathVariable Long id) { return null; }
+}
+
+public interface UserRepository extends JpaRepository<User, Long> {
+    List<User> findByName(String name);
+}
+
+public enum Role {
+    ADMIN, USER;
+
+    public boolean isAdmin() { return this == ADMIN; }
+}
+
+public record UserDto(Long id, String name) {}
+"#;
+
+    #[test]
+    fn test_extracts_java_outline() {
+        let symbols = extract(SOURCE).unwrap();
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0].name, "com.example.users");
+        let package = symbols[0].location.unwrap();
+        assert_eq!((package.start_line, package.start_column), (2, 1));
+        assert_eq!((package.end_line, package.end_column), (2, 27));
+
+        let types = &symbols[0].children;
+        let names: Vec<&str> = types.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["UserController", "UserRepository", "Role", "UserDto"]
+        );
+
+        let controller = &types[0];
+        assert_eq!(
+            controller.modifiers,
+            vec!["@RestController", "@RequestMapping(\"/users\")", "public"]
+        );
+        let members: Vec<(&str, &Vec<String>)> = controller
+            .children
+            .iter()
+            .map(|s| (s.name.as_str(), &s.modifiers))
+            .collect();
+        assert_eq!(
+            members,
+            vec![
+                (
+                    "service",
+                    &vec!["@Autowired".to_string(), "private".to_string()]
+                ),
+                ("UserController", &vec!["public".to_string()]),
+                (
+                    "get",
+                    &vec!["@GetMapping(\"/{id}\")".to_string(), "public".to_string()]
+                ),
+            ]
+        );
+        assert!(matches!(controller.children[0].kind, SymbolKind::Field));
+        assert!(matches!(controller.children[2].kind, SymbolKind::Method));
+
+        assert!(matches!(types[1].kind, SymbolKind::Interface));
+        assert_eq!(types[1].children[0].name, "findByName");
+
+        assert!(matches!(types[2].kind, SymbolKind::Enum));
+        let constants: Vec<&str> = types[2].children.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(constants, vec!["ADMIN", "USER", "isAdmin"]);
+
+        assert!(matches!(types[3].kind, SymbolKind::Class));
+        assert_eq!(types[3].modifiers, vec!["public", "record"]);
+    }
+}
@@ -0,0 +1,120 @@
+//! Tree-sitter based symbol extraction for the generic analyzer.
+//!
+//! Each language module turns a syntax tree into the language-neutral
+//! [`Symbol`] outline stored in `SourceFile.symbols`.
+
+use anyhow::Result;
+use std::path::Path;
+use tree_sitter::{Language, Node, Parser, Tree};
+
+use crate::types::{Location, Symbol, SymbolKind};
+
+mod csharp;
+mod go;
+mod java;
+mod php;
+mod python;
+mod razor;
+mod rust;
+mod typescript;
+mod vue;
+
+/// Extracts the symbol outline of a source file.
+///
+/// `language` is the file extension as recorded in `SourceFile.language`;
+/// `path` names file-level symbols such as Vue and Razor components.
+/// Languages without an extractor yield an empty outline.
+pub fn extract_symbols(path: &Path, language: &str, source: &str) -> Result<Vec<Symbol>> {
+    match language {
+        "rs" => rust::extract(source),
+        "cs" => csharp::extract(path, source),
+        "razor" => razor::extract(path, source),
+        "py" | "pyi" => python::extract(source),
+        "go" => go::extract(source),
+        "java" => java::extract(source),
+        "php" => php::extract(source),
+        "ts" | "mts" | "cts" => {
+            typescript::extract(tree_sitter_typescript::language_typescript(), source)
+        }
+        "tsx" => typescript::extract(tree_sitter_typescript::language_tsx(), source),
+        "js" | "jsx" | "mjs" | "cjs" => {
+            typescript::extract(tree_sitter_javascript::language(), source)
+        }
+        "vue" => {
+            let name = path.file_stem().and_then(|s| s.to_str()).unwrap_or("App");
+            vue::extract(name, source)
+        }
+        _ => Ok(Vec::new()),
+    }
+}
+
+fn parse(language: Language, source: &str) -> Result<Tree> {
+    let mut parser = Parser::new();
+    parser.set_language(language)?;
+    parser
+        .parse(source, None)
+        .ok_or_else(|| anyhow::anyhow!("Failed to parse file"))
+}
+
+/// Source text of a node (empty if it is not valid UTF-8)
+fn text<'a>(node: &Node, source: &'a str) -> &'a str {
+    node.utf8_text(source.as_bytes()).unwrap_or("")
+}
+
+/// Source text of a node's named field, if present
+fn field_text<'a>(node: &Node, field: &str, source: &'a str) -> Option<&'a str> {
+    node.child_by_field_name(field).map(|n| text(&n, source))
+}
+
+/// Whether a node has an unnamed keyword child such as `default` or `async`
+fn has_token(node: &Node, token: &str) -> bool {
+    let mut cursor = node.walk();
+    let found = node
+        .children(&mut cursor)
+        .any(|child| !child.is_named() && child.kind() == token);
+    found
+}
+
+fn symbol(name: &str, kind: SymbolKind) -> Symbol {
+    Symbol {
+        name: name.to_string(),
+        kind,
+        modifiers: Vec::new(),
+        children: Vec::new(),
+        type_name: None,
+        bases: Vec::new(),
+        parameters: Vec::new(),
+        location: None,
+    }
+}
+
+/// A symbol declared by `node`, located at its span
+fn symbol_at(name: &str, kind: SymbolKind, node: &Node) -> Symbol {
+    let mut item = symbol(name, kind);
+    item.location = Some(location(node));
+    item
+}
+
+/// 1-based span of a whole file, for symbols the file itself declares
+/// (a Vue or Razor component)
+fn file_location(source: &str) -> Location {
+    let last_line = source.rfind('\n').map_or(0, |i| i + 1);
+    Location {
+        start_line: 1,
+        start_column: 1,
+        end_line: source.matches('\n').count() + 1,
+        end_column: source.len() - last_line + 1,
+    }
+}
+
+/// 1-based span of a node
+pub(crate) fn location(node: &Node) -> Location {
+    let start = node.start_position();
+    let end = node.end_position();
+    Location {
+        start_line: start.row + 1,
+        start_column: start.column + 1,
+        end_line: end.row + 1,
+        end_column: end.column + 1,
+    }
+}
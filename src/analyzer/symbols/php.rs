@@ -0,0 +1,644 @@
+//! PHP declarations from a lexical scan.
+//!
+//! The PHP grammar is not available for tree-sitter 0.20, so comments,
+//! strings and inline HTML are blanked out and the remaining tokens are
+//! matched against the handful of declaration forms PHP has. That is enough
+//! for an outline: PHP doesn't nest named declarations beyond
+//! namespace → type → member.
+
+use anyhow::Result;
+
+use super::symbol;
+use crate::types::{Location, Symbol, SymbolKind};
+
+pub(super) fn extract(source: &str) -> Result<Vec<Symbol>> {
+    let code = strip_non_code(source);
+    let tokens = tokenize(&code);
+    let scanner = Scanner {
+        positions: positions(&code, &tokens),
+        ..Scanner::default()
+    };
+    Ok(scanner.run(&tokens))
+}
+
+#[derive(Default)]
+struct Scanner {
+    symbols: Vec<Symbol>,
+    /// 1-based line and column of each token
+    positions: Vec<(usize, usize)>,
+    depth: usize,
+    parens: usize,
+    /// Open namespace and, for `namespace X { ... }`, its body depth
+    namespace: Option<(Symbol, Option<usize>)>,
+    /// Type being declared and its body depth
+    current_type: Option<(Symbol, usize)>,
+    /// Depth at which the last declared function's body opens, until its
+    /// closing brace (or `;` for abstract methods) sets its end
+    open_function: Option<usize>,
+}
+
+impl Scanner {
+    fn run(mut self, tokens: &[&str]) -> Vec<Symbol> {
+        let mut i = 0;
+        while i < tokens.len() {
+            let token = tokens[i];
+            let previous = if i > 0 { tokens[i - 1] } else { "" };
+            // `Foo::class`, `$this->function` and the like are not declarations
+            let member_access = matches!(previous, "::" | "->" | "?->");
+
+            match token {
+                "{" => self.depth += 1,
+                "}" => self.close_brace(tokens, i),
+                ";" if self.parens == 0 && self.open_function == Some(self.depth + 1) => {
+                    self.end_function(tokens, i);
+                }
+                "(" => self.parens += 1,
+                ")" => self.parens = self.parens.saturating_sub(1),
+                "namespace" if self.current_type.is_none() && !member_access => {
+                    i = self.namespace(tokens, i);
+                    continue;
+                }
+                "class" | "interface" | "trait" | "enum"
+                    if self.current_type.is_none() && !member_access =>
+                {
+                    if let Some(next) = self.type_declaration(tokens, i) {
+                        i = next;
+                        continue;
+                    }
+                }
+                "function" if !member_access => self.function(tokens, i),
+                _ if token.starts_with('$') => self.property(tokens, i),
+                _ => {}
+            }
+            i += 1;
+        }
+
+        if let Some((item, _)) = self.current_type.take() {
+            self.push(item);
+        }
+        if let Some((namespace, _)) = self.namespace.take() {
+            self.symbols.push(namespace);
+        }
+        self.symbols
+    }
+
+    fn close_brace(&mut self, tokens: &[&str], i: usize) {
+        self.depth = self.depth.saturating_sub(1);
+
+        if self.open_function == Some(self.depth + 1) {
+            self.end_function(tokens, i);
+        }
+        if matches!(self.current_type, Some((_, body)) if self.depth < body) {
+            let (mut item, _) = self.current_type.take().unwrap();
+            extend_to(&mut item, self.span(tokens, i, i));
+            self.push(item);
+        }
+        if matches!(self.namespace, Some((_, Some(body))) if self.depth < body) {
+            let (namespace, _) = self.namespace.take().unwrap();
+            self.symbols.push(namespace);
+        }
+    }
+
+    /// Adds a top-level declaration to the open namespace, if any
+    fn push(&mut self, item: Symbol) {
+        match &mut self.namespace {
+            Some((namespace, _)) => namespace.children.push(item),
+            None => self.symbols.push(item),
+        }
+    }
+
+    /// `namespace App\Models;` or `namespace App\Models { ... }`
+    fn namespace(&mut self, tokens: &[&str], i: usize) -> usize {
+        let Some(&name) = tokens.get(i + 1).filter(|t| is_name(t)) else {
+            return i + 1;
+        };
+
+        if let Some((previous, _)) = self.namespace.take() {
+            self.symbols.push(previous);
+        }
+        let braced = tokens.get(i + 2) == Some(&"{");
+        let body = braced.then_some(self.depth + 1);
+        self.namespace = Some((symbol(name, SymbolKind::Module), body));
+        i + 2
+    }
+
+    /// A named class, interface, trait or enum; returns the index of its
+    /// opening brace
+    fn type_declaration(&mut self, tokens: &[&str], i: usize) -> Option<usize> {
+        let name = *tokens.get(i + 1).filter(|t| is_name(t))?;
+        if matches!(name, "extends" | "implements") {
+            return None; // anonymous class
+        }
+
+        let mut extends = None;
+        let mut implements = Vec::new();
+        let mut in_implements = false;
+        let mut open = i + 2;
+        while open < tokens.len() && tokens[open] != "{" {
+            match tokens[open] {
+                "extends" => extends = tokens.get(open + 1).copied(),
+                "implements" => in_implements = true,
+                t if in_implements && is_name(t) => implements.push(t),
+                _ => {}
+            }
+            open += 1;
+        }
+
+        let kind = match tokens[i] {
+            "class" => SymbolKind::Class,
+            "interface" => SymbolKind::Interface,
+            "trait" => SymbolKind::Trait,
+            _ => SymbolKind::Enum,
+        };
+        let mut item = symbol(name, kind);
+        item.modifiers = modifiers_before(tokens, i);
+        item.location = Some(self.span(tokens, i, i + 1));
+
+        if tokens[i] == "class" {
+            let namespace = self.namespace.as_ref().map_or("", |(n, _)| n.name.as_str());
+            if let Some(role) = laravel_role(name, namespace, extends, &implements) {
+                item.modifiers.push(role.to_string());
+            }
+        }
+
+        self.current_type = Some((item, self.depth + 1));
+        Some(open)
+    }
+
+    /// Methods at the top of a type body, functions outside of types.
+    /// Closures (`function (...)`) have no name and are skipped.
+    fn function(&mut self, tokens: &[&str], i: usize) {
+        let name_index = if tokens.get(i + 1) == Some(&"&") {
+            i + 2
+        } else {
+            i + 1
+        };
+        let Some(&name) = tokens.get(name_index).filter(|t| is_name(t)) else {
+            return;
+        };
+
+        let namespace_depth = self.namespace_depth();
+        let location = Some(self.span(tokens, i, name_index));
+        match &mut self.current_type {
+            Some((item, body)) if self.depth == *body => {
+                let mut method = symbol(name, SymbolKind::Method);
+                method.modifiers = modifiers_before(tokens, i);
+                method.location = location;
+                item.children.push(method);
+            }
+            None if self.depth == namespace_depth => {
+                let mut function = symbol(name, SymbolKind::Function);
+                function.location = location;
+                self.push(function);
+            }
+            _ => return,
+        }
+        self.open_function = Some(self.depth + 1);
+    }
+
+    /// Ends the last declared function at token `i`
+    fn end_function(&mut self, tokens: &[&str], i: usize) {
+        self.open_function = None;
+        let end = self.span(tokens, i, i);
+        let function = match &mut self.current_type {
+            Some((item, _)) => item.children.last_mut(),
+            None => match &mut self.namespace {
+                Some((namespace, _)) => namespace.children.last_mut(),
+                None => self.symbols.last_mut(),
+            },
+        };
+        if let Some(function) = function {
+            extend_to(function, end);
+        }
+    }
+
+    /// Variables at the top of a type body outside any parentheses can only
+    /// be property declarations
+    fn property(&mut self, tokens: &[&str], i: usize) {
+        let Some((item, body)) = &mut self.current_type else {
+            return;
+        };
+        if self.depth != *body || self.parens > 0 {
+            return;
+        }
+
+        let mut property = symbol(&tokens[i][1..], SymbolKind::Property);
+        property.modifiers = modifiers_before(tokens, i);
+        item.children.push(property);
+
+        // Up to the end of its default value
+        let end = tokens[i..]
+            .iter()
+            .position(|t| matches!(*t, ";" | ","))
+            .map_or(i, |n| i + n);
+        let location = self.span(tokens, i, end);
+        if let Some((item, _)) = &mut self.current_type {
+            item.children.last_mut().unwrap().location = Some(location);
+        }
+    }
+
+    /// From the start of token `first` to the end of token `last`
+    fn span(&self, tokens: &[&str], first: usize, last: usize) -> Location {
+        let (start_line, start_column) = self.positions[first];
+        let (end_line, end_column) = self.positions[last];
+        Location {
+            start_line,
+            start_column,
+            end_line,
+            end_column: end_column + tokens[last].len(),
+        }
+    }
+
+    fn namespace_depth(&self) -> usize {
+        match self.namespace {
+            Some((_, Some(body))) => body,
+            _ => 0,
+        }
+    }
+}
+
+/// Moves the end of a declaration to the end of `last`
+fn extend_to(item: &mut Symbol, last: Location) {
+    if let Some(location) = &mut item.location {
+        location.end_line = last.end_line;
+        location.end_column = last.end_column;
+    }
+}
+
+/// Keywords such as `abstract`, `public` and `static` directly preceding a
+/// declaration, in source order. Type hints between them and a property
+/// name are skipped.
+fn modifiers_before(tokens: &[&str], i: usize) -> Vec<String> {
+    const MODIFIERS: &[&str] = &[
+        "abstract",
+        "final",
+        "readonly",
+        "public",
+        "protected",
+        "private",
+        "static",
+        "var",
+    ];
+
+    let mut modifiers = Vec::new();
+    for &token in tokens[..i].iter().rev() {
+        if MODIFIERS.contains(&token) {
+            modifiers.push(token.to_string());
+        } else if !(is_name(token) || token == "?" || token == "|") || !modifiers.is_empty() {
+            break;
+        }
+    }
+    modifiers.reverse();
+    modifiers
+}
+
+/// Laravel (and Symfony) building blocks, recognised by base class,
+/// interface or conventional namespace
+fn laravel_role(
+    name: &str,
+    namespace: &str,
+    extends: Option<&str>,
+    implements: &[&str],
+) -> Option<&'static str> {
+    let base = extends.map(short_name).unwrap_or("");
+    let implements = |interface: &str| implements.iter().any(|i| short_name(i) == interface);
+
+    if matches!(base, "Model" | "Authenticatable" | "Pivot") {
+        Some("model")
+    } else if base.ends_with("Controller")
+        || (name.ends_with("Controller") && namespace.contains("Controllers"))
+    {
+        Some("controller")
+    } else if implements("ShouldQueue") || namespace.ends_with("\\Jobs") {
+        Some("job")
+    } else if namespace.contains("Http\\Middleware") {
+        Some("middleware")
+    } else {
+        None
+    }
+}
+
+/// `Model` for `Illuminate\Database\Eloquent\Model`
+fn short_name(name: &str) -> &str {
+    name.rsplit('\\').next().unwrap_or(name)
+}
+
+fn is_name(token: &str) -> bool {
+    token
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '\\')
+}
+
+/// Splits code into names (including `\`-qualified ones and `$variables`),
+/// `::`, `->`, `?->` and single punctuation characters
+fn tokenize(code: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut chars = code.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        let word_char = |c: char| c.is_alphanumeric() || c == '_' || c == '\\';
+        let end = if word_char(c) || c == '$' {
+            let mut end = start + c.len_utf8();
+            while let Some(&(i, next)) = chars.peek() {
+                if !word_char(next) {
+                    break;
+                }
+                end = i + next.len_utf8();
+                chars.next();
+            }
+            end
+        } else {
+            let rest = &code[start..];
+            let operator = ["?->", "::", "->"]
+                .into_iter()
+                .find(|op| rest.starts_with(op));
+            match operator {
+                Some(op) => {
+                    for _ in 1..op.len() {
+                        chars.next();
+                    }
+                    start + op.len()
+                }
+                None => start + c.len_utf8(),
+            }
+        };
+        tokens.push(&code[start..end]);
+    }
+
+    tokens
+}
+
+/// Line and column of each token, which are all slices of `code`
+fn positions(code: &str, tokens: &[&str]) -> Vec<(usize, usize)> {
+    let mut positions = Vec::with_capacity(tokens.len());
+    let (mut line, mut line_start, mut scanned) = (1, 0, 0);
+
+    for token in tokens {
+        let offset = token.as_ptr() as usize - code.as_ptr() as usize;
+        for (i, b) in code.as_bytes()[scanned..offset].iter().enumerate() {
+            if *b == b'\n' {
+                line += 1;
+                line_start = scanned + i + 1;
+            }
+        }
+        scanned = offset;
+        positions.push((line, offset - line_start + 1));
+    }
+
+    positions
+}
+
+/// Replaces inline HTML, comments, attributes and string literals with
+/// spaces, keeping line breaks and byte offsets
+fn strip_non_code(source: &str) -> String {
+    let mut code = String::with_capacity(source.len());
+    let mut rest = source;
+    let mut in_php = false;
+
+    let blank = |code: &mut String, text: &str| {
+        for c in text.chars() {
+            match c {
+                '\n' => code.push('\n'),
+                c => code.extend(std::iter::repeat_n(' ', c.len_utf8())),
+            }
+        }
+    };
+
+    while !rest.is_empty() {
+        if !in_php {
+            let open = rest.find("<?php").map(|i| (i, 5));
+            let open = open.or_else(|| rest.find("<?=").map(|i| (i, 3)));
+            let Some((start, len)) = open else {
+                blank(&mut code, rest);
+                break;
+            };
+            blank(&mut code, &rest[..start + len]);
+            rest = &rest[start + len..];
+            in_php = true;
+            continue;
+        }
+
+        let skipped = if rest.starts_with("?>") {
+            in_php = false;
+            2
+        } else if rest.starts_with("//") || (rest.starts_with('#') && !rest.starts_with("#[")) {
+            // Line comments also end at a closing tag
+            let end = rest.find('\n').unwrap_or(rest.len());
+            rest[..end].find("?>").unwrap_or(end)
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            comment.find("*/").map_or(rest.len(), |i| i + 4)
+        } else if rest.starts_with("#[") {
+            closing_bracket(rest)
+        } else if rest.starts_with("<<<") {
+            heredoc_len(rest)
+        } else if rest.starts_with('\'') || rest.starts_with('"') {
+            quoted_len(rest)
+        } else {
+            let c = rest.chars().next().unwrap();
+            code.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        };
+
+        blank(&mut code, &rest[..skipped]);
+        rest = &rest[skipped..];
+    }
+
+    code
+}
+
+/// Length of a quoted string including its quotes
+fn quoted_len(text: &str) -> usize {
+    let quote = text.as_bytes()[0];
+    let bytes = text.as_bytes();
+    let mut i = 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b if b == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+    text.len()
+}
+
+/// Length of a `#[...]` attribute, which may contain nested brackets
+fn closing_bracket(text: &str) -> usize {
+    let mut depth = 0;
+    for (i, b) in text.bytes().enumerate() {
+        match b {
+            b'[' => depth += 1,
+            b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+/// Length of a heredoc or nowdoc up to and including its closing label
+fn heredoc_len(text: &str) -> usize {
+    let header_end = text.find('\n').unwrap_or(text.len());
+    let label = text[3..header_end]
+        .trim()
+        .trim_matches(|c| c == '"' || c == '\'');
+    if label.is_empty() {
+        return 3;
+    }
+
+    let mut offset = header_end;
+    for line in text[header_end..].split_inclusive('\n').skip(1) {
+        let start = offset + 1;
+        offset += line.len();
+        let trimmed = line.trim_start();
+        if trimmed.starts_with(label)
+            && !trimmed[label.len()..].starts_with(|c: char| c.is_alphanumeric() || c == '_')
+        {
+            return start + (line.len() - trimmed.len()) + label.len();
+        }
+    }
+    text.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"<?php
+
+namespace App\Http\Controllers;
+
+use App\Models\User;
+
+// class NotAClass {}
+#[Route('/users', methods: ['GET'])]
+final class UserController extends Controller
+{
+    protected array $middleware = ['auth'];
+
+    public function index(Request $request)
+    {
+        $html = "<div class=\"x\">{$request->name}</div>";
+        $query = <<<SQL
+            SELECT * FROM users WHERE class = 'function'
+            SQL;
+        return User::class;
+    }
+
+    private static function &helper(?User $user = null): void
+    {
+        array_map(function ($u) { return $u; }, []);
+    }
+}
+"#;
+
+    #[test]
+    fn test_extracts_php_outline() {
+        let symbols = extract(SOURCE).unwrap();
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0].name, "App\\Http\\Controllers");
+        assert!(matches!(symbols[0].kind, SymbolKind::Module));
+
+        let controller = &symbols[0].children[0];
+        assert_eq!(symbols[0].children.len(), 1);
+        assert_eq!(controller.name, "UserController");
+        assert_eq!(controller.modifiers, vec!["final", "controller"]);
+
+        let members: Vec<(&str, &Vec<String>)> = controller
+            .children
+            .iter()
+            .map(|s| (s.name.as_str(), &s.modifiers))
+            .collect();
+        assert_eq!(
+            members,
+            vec![
+                ("middleware", &vec!["protected".to_string()]),
+                ("index", &vec!["public".to_string()]),
+                ("helper", &vec!["private".to_string(), "static".to_string()]),
+            ]
+        );
+        assert!(matches!(controller.children[0].kind, SymbolKind::Property));
+        assert!(matches!(controller.children[1].kind, SymbolKind::Method));
+
+        // Spans start at the declaring keyword and end at the closing brace
+        let location = |s: &Symbol| {
+            let l = s.location.unwrap();
+            (l.start_line, l.start_column, l.end_line, l.end_column)
+        };
+        assert_eq!(location(controller), (9, 7, 26, 2));
+        assert_eq!(location(&controller.children[0]), (11, 21, 11, 44));
+        assert_eq!(location(&controller.children[1]), (13, 12, 20, 6));
+        assert_eq!(location(&controller.children[2]), (22, 20, 25, 6));
+    }
+
+    #[test]
+    fn test_tags_laravel_roles() {
+        let source = r#"<?php
+namespace App\Models {
+    use Illuminate\Database\Eloquent\Model;
+
+    class Post extends Model {
+        protected $fillable = ['title'];
+        public function author() { return $this->belongsTo(User::class); }
+    }
+
+    trait HasSlug { public function slug() {} }
+}
+
+namespace App\Jobs {
+    class SendDigest implements \Illuminate\Contracts\Queue\ShouldQueue {
+        public function handle() {}
+    }
+}
+
+namespace App\Http\Middleware {
+    interface Gate {}
+    class EnsureAdmin { public function handle($request, $next) {} }
+    function helper() {}
+}
+"#;
+        let symbols = extract(source).unwrap();
+        let namespaces: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            namespaces,
+            vec!["App\\Models", "App\\Jobs", "App\\Http\\Middleware"]
+        );
+
+        let post = &symbols[0].children[0];
+        assert_eq!(post.modifiers, vec!["model"]);
+        assert_eq!(post.children.len(), 2);
+        assert!(matches!(symbols[0].children[1].kind, SymbolKind::Trait));
+        assert!(symbols[0].children[1].modifiers.is_empty());
+
+        assert_eq!(symbols[1].children[0].modifiers, vec!["job"]);
+
+        let middleware = &symbols[2].children;
+        assert!(matches!(middleware[0].kind, SymbolKind::Interface));
+        assert!(middleware[0].modifiers.is_empty());
+        assert_eq!(middleware[1].modifiers, vec!["middleware"]);
+        assert!(matches!(middleware[2].kind, SymbolKind::Function));
+    }
+
+    #[test]
+    fn test_strips_heredocs_and_comments() {
+        let source =
+            "<?php\n$sql = <<<SQL\n  class A {}\n  SQL;\n/* class B {} */ $x = 'class C {}';";
+        let code = strip_non_code(source);
+        assert_eq!(code.len(), source.len());
+        assert_eq!(tokenize(&code), vec!["$sql", "=", ";", "$x", "=", ";"]);
+    }
+
+    #[test]
+    fn test_ignores_inline_html() {
+        let source = "<h1>class Title {}</h1>\n<?= $title ?>\n<p>function x() {}</p>";
+        assert!(extract(source).unwrap().is_empty());
+    }
+}
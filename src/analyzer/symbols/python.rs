@@ -0,0 +1,166 @@
+use anyhow::Result;
+use tree_sitter::Node;
+
+use super::{field_text, has_token, location, parse, symbol_at, text};
+use crate::types::{Symbol, SymbolKind};
+
+pub(super) fn extract(source: &str) -> Result<Vec<Symbol>> {
+    let tree = parse(tree_sitter_python::language(), source)?;
+    Ok(statements(&tree.root_node(), source, false))
+}
+
+/// Symbols for the statements directly inside a module or class body.
+///
+/// Functions in a class body are methods and assignments are fields;
+/// at module level they are functions and variables.
+fn statements(node: &Node, source: &str, in_class: bool) -> Vec<Symbol> {
+    let mut symbols = Vec::new();
+    let mut cursor = node.walk();
+
+    for child in node.named_children(&mut cursor) {
+        match child.kind() {
+            "decorated_definition" => {
+                let Some(definition) = child.child_by_field_name("definition") else {
+                    continue;
+                };
+                if let Some(mut item) = definition_symbol(&definition, source, in_class) {
+                    item.modifiers.splice(0..0, decorators(&child, source));
+                    // Span the decorators too, as annotations do in Java and C#
+                    item.location = Some(location(&child));
+                    symbols.push(item);
+                }
+            }
+            "function_definition" | "class_definition" => {
+                symbols.extend(definition_symbol(&child, source, in_class));
+            }
+            "expression_statement" => symbols.extend(assignments(&child, source, in_class)),
+            _ => {}
+        }
+    }
+
+    symbols
+}
+
+fn definition_symbol(node: &Node, source: &str, in_class: bool) -> Option<Symbol> {
+    let name = field_text(node, "name", source)?;
+
+    if node.kind() == "class_definition" {
+        let mut item = symbol_at(name, SymbolKind::Class, node);
+        if let Some(body) = node.child_by_field_name("body") {
+            item.children = statements(&body, source, true);
+        }
+        return Some(item);
+    }
+
+    let kind = if in_class {
+        SymbolKind::Method
+    } else {
+        SymbolKind::Function
+    };
+    let mut item = symbol_at(name, kind, node);
+    if has_token(node, "async") {
+        item.modifiers.push("async".to_string());
+    }
+    Some(item)
+}
+
+/// `@app.get("/items")`, `@pytest.fixture`, ... in source order, with
+/// their arguments collapsed onto one line
+fn decorators(node: &Node, source: &str) -> Vec<String> {
+    let mut decorators = Vec::new();
+    let mut cursor = node.walk();
+
+    for child in node.named_children(&mut cursor) {
+        if child.kind() == "decorator" {
+            let decorator = text(&child, source);
+            decorators.push(decorator.split_whitespace().collect::<Vec<_>>().join(" "));
+        }
+    }
+
+    decorators
+}
+
+/// `NAME = ...` and annotated `name: int` statements binding a plain name;
+/// tuple unpacking and attribute assignments are skipped
+fn assignments(node: &Node, source: &str, in_class: bool) -> Vec<Symbol> {
+    let kind = if in_class {
+        SymbolKind::Field
+    } else {
+        SymbolKind::Other("variable".to_string())
+    };
+
+    let mut symbols = Vec::new();
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        if child.kind() != "assignment" {
+            continue;
+        }
+        if let Some(left) = child
+            .child_by_field_name("left")
+            .filter(|left| left.kind() == "identifier")
+        {
+            symbols.push(symbol_at(text(&left, source), kind.clone(), &child));
+        }
+    }
+    symbols
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+from dataclasses import dataclass
+import pytest
+
+MAX_ITEMS = 100
+a, b = 1, 2
+
+@dataclass
+class Item:
+    name: str
+    price: float = 0.0
+
+    def total(self, count):
+        self.count = count
+        return self.price * count
+
+@app.get("/items/{item_id}",
+         response_model=Item)
+async def read_item(item_id: int):
+    def helper():
+        pass
+    return Item(name="x")
+
+@pytest.fixture
+def client():
+    return None
+"#;
+
+    #[test]
+    fn test_extracts_python_outline() {
+        let symbols = extract(SOURCE).unwrap();
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["MAX_ITEMS", "Item", "read_item", "client"]);
+
+        assert!(matches!(symbols[0].kind, SymbolKind::Other(ref k) if k == "variable"));
+
+        let item = &symbols[1];
+        assert!(matches!(item.kind, SymbolKind::Class));
+        assert_eq!(item.modifiers, vec!["@dataclass"]);
+        let members: Vec<&str> = item.children.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(members, vec!["name", "price", "total"]);
+        assert!(matches!(item.children[0].kind, SymbolKind::Field));
+        assert!(matches!(item.children[2].kind, SymbolKind::Method));
+
+        assert!(matches!(symbols[2].kind, SymbolKind::Function));
+        assert_eq!(
+            symbols[2].modifiers,
+            vec![
+                "@app.get(\"/items/{item_id}\", response_model=Item)",
+                "async"
+            ]
+        );
+        assert_eq!(symbols[3].modifiers, vec!["@pytest.fixture"]);
+    }
+}
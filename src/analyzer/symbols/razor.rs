@@ -0,0 +1,340 @@
+//! Razor components (`.razor`).
+//!
+//! Directives are read line by line, `@code` blocks are parsed as the
+//! members of a C# class, and the remaining markup is scanned for child
+//! component tags.
+
+use anyhow::Result;
+use regex::Regex;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use super::{csharp, file_location, symbol};
+use crate::analyzer::csharp::CSharpAnalyzer;
+use crate::types::{Location, Symbol, SymbolKind};
+
+fn directive() -> &'static Regex {
+    static DIRECTIVE: OnceLock<Regex> = OnceLock::new();
+    DIRECTIVE.get_or_init(|| {
+        Regex::new(r"^\s*@(page|inject|inherits|implements|attribute)\s+(.+?)\s*$").unwrap()
+    })
+}
+
+fn code_block() -> &'static Regex {
+    static CODE: OnceLock<Regex> = OnceLock::new();
+    CODE.get_or_init(|| Regex::new(r"@(?:code|functions)\s*\{").unwrap())
+}
+
+fn component_tag() -> &'static Regex {
+    static TAG: OnceLock<Regex> = OnceLock::new();
+    TAG.get_or_init(|| Regex::new(r"<([A-Z][A-Za-z0-9_]*(?:\.[A-Z][A-Za-z0-9_]*)*)[\s/>]").unwrap())
+}
+
+/// A Razor file is one component named after the file, spanning all of it.
+///
+/// - `@page "/route"` and `@attribute [X]` become modifiers as written
+/// - `@inherits` and `@implements` become its bases
+/// - `@inject T Name` becomes a `T Name` property with an `@inject` modifier
+/// - `@code` members follow, as the C# extractor maps them
+/// - components used in the markup are listed last, as `uses` symbols
+pub(super) fn extract(path: &Path, source: &str) -> Result<Vec<Symbol>> {
+    let name = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("Component");
+    let source = blank_comments(source);
+    let blocks = code_blocks(&source);
+
+    let mut component = symbol(name, SymbolKind::Component);
+    component.location = Some(file_location(&source));
+    let mut directives = Vec::new();
+    let mut offset = 0;
+    for (index, line) in source.split_inclusive('\n').enumerate() {
+        let line_start = offset;
+        offset += line.len();
+        if blocks.iter().any(|b| b.contains(&line_start)) {
+            continue;
+        }
+        let Some(captures) = directive().captures(line) else {
+            continue;
+        };
+        directives.push(line_start..offset);
+        let value = &captures[2];
+        let whole = captures.get(0).unwrap();
+        let start = whole.start() + line[whole.start()..].find('@').unwrap_or(0);
+        let location = Location {
+            start_line: index + 1,
+            start_column: start + 1,
+            end_line: index + 1,
+            end_column: captures.get(2).unwrap().end() + 1,
+        };
+
+        match &captures[1] {
+            "page" => component.modifiers.push(format!("@page {}", value)),
+            "attribute" => component.modifiers.push(value.to_string()),
+            "inherits" | "implements" => component.bases.push(value.to_string()),
+            _ => {
+                // `@inject IWeatherService Weather`
+                if let Some((service, property)) = value.rsplit_once(char::is_whitespace) {
+                    let mut injected = symbol(property, SymbolKind::Property);
+                    injected.modifiers.push("@inject".to_string());
+                    injected.type_name = Some(service.trim().to_string());
+                    injected.location = Some(location);
+                    component.children.push(injected);
+                }
+            }
+        }
+    }
+
+    if !blocks.is_empty() {
+        let file = CSharpAnalyzer::new()?.analyze_source(path, &code_as_class(&source, &blocks))?;
+        if let Some(class) = file.classes.first() {
+            component
+                .children
+                .extend(csharp::class_symbol(class).children);
+        }
+    }
+
+    // Directive lines are not markup: `@inject ILogger<Index> Logger` uses no `Index`
+    let skipped: Vec<_> = blocks.iter().cloned().chain(directives).collect();
+    component
+        .children
+        .extend(used_components(&source, &skipped));
+    Ok(vec![component])
+}
+
+/// Byte ranges of the contents of `@code { ... }` blocks
+fn code_blocks(source: &str) -> Vec<std::ops::Range<usize>> {
+    let mut blocks = Vec::new();
+    let mut from = 0;
+
+    while let Some(found) = code_block().find_at(source, from) {
+        let start = found.end();
+        let end = start + closing_brace(&source[start..]);
+        blocks.push(start..end);
+        from = (end + 1).min(source.len());
+    }
+
+    blocks
+}
+
+/// Offset of the brace closing a block whose opening brace precedes `code`,
+/// skipping braces in strings, characters and comments
+fn closing_brace(code: &str) -> usize {
+    let bytes = code.as_bytes();
+    let mut depth = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' => depth += 1,
+            b'}' if depth == 0 => return i,
+            b'}' => depth -= 1,
+            b'"' | b'\'' => {
+                let quote = bytes[i];
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote && bytes[i] != b'\n' {
+                    if bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i = code[i..].find('\n').map_or(bytes.len(), |n| i + n);
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = code[i + 2..].find("*/").map_or(bytes.len(), |n| i + n + 3);
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    bytes.len()
+}
+
+/// The `@code` blocks as the body of a C# class, at their original
+/// lines and columns: everything else is blanked, and the class header
+/// takes the place of the file's first line
+fn code_as_class(source: &str, blocks: &[std::ops::Range<usize>]) -> String {
+    let mut code = blank(source);
+    for block in blocks {
+        code.replace_range(block.clone(), &source[block.clone()]);
+    }
+
+    const HEADER: &str = "class C{";
+    let first_line = source.find('\n').unwrap_or(source.len());
+    let skip = HEADER.len().min(first_line).min(blocks[0].start);
+    format!("{}{}}}", HEADER, &code[skip..])
+}
+
+/// Capitalized tags outside the `skipped` ranges, once each, in order of
+/// first use
+fn used_components(source: &str, skipped: &[std::ops::Range<usize>]) -> Vec<Symbol> {
+    let mut used: Vec<Symbol> = Vec::new();
+
+    for captures in component_tag().captures_iter(source) {
+        let tag = captures.get(1).unwrap();
+        if skipped.iter().any(|b| b.contains(&tag.start()))
+            || used.iter().any(|u| u.name == tag.as_str())
+        {
+            continue;
+        }
+
+        let line = source[..tag.start()].matches('\n').count() + 1;
+        let column = tag.start() - source[..tag.start()].rfind('\n').map_or(0, |i| i + 1);
+        let mut item = symbol(tag.as_str(), SymbolKind::Other("uses".to_string()));
+        item.location = Some(Location {
+            start_line: line,
+            start_column: column,
+            end_line: line,
+            end_column: column + 1 + tag.len(),
+        });
+        used.push(item);
+    }
+
+    used
+}
+
+/// Replaces Razor comments (`@* ... *@`) with spaces, keeping line breaks
+fn blank_comments(source: &str) -> String {
+    let mut text = source.to_string();
+    let mut from = 0;
+
+    while let Some(start) = text[from..].find("@*").map(|i| from + i) {
+        let end = text[start..]
+            .find("*@")
+            .map_or(text.len(), |i| start + i + 2);
+        let blanked = blank(&text[start..end]);
+        text.replace_range(start..end, &blanked);
+        from = end;
+    }
+
+    text
+}
+
+/// Spaces in place of every byte but line breaks
+fn blank(text: &str) -> String {
+    text.chars()
+        .flat_map(|c| {
+            let fill = if c == '\n' { '\n' } else { ' ' };
+            std::iter::repeat_n(fill, c.len_utf8())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"@page "/counter"
+@page "/counter/{Start:int}"
+@inherits LayoutComponentBase
+@implements IDisposable
+@attribute [Authorize(Roles = "Admin")]
+@inject IWeatherService Weather
+@inject NavigationManager Navigation
+@inject ILogger<Counter> Logger
+
+@* <NotUsed /> *@
+<PageTitle>Counter</PageTitle>
+<MudButton OnClick="Increment">Clicks: @count</MudButton>
+<Shared.Alert Message="@message"/>
+<MudButton>Again</MudButton>
+<div class="x"></div>
+
+@code {
+    private int count;
+    private string message = "}";
+
+    [Parameter] public int Start { get; set; }
+    [CascadingParameter] public Task<AuthenticationState> Auth { get; set; } = default!;
+
+    protected override void OnInitialized() { count = Start; }
+
+    private void Increment() => count++;
+
+    public void Dispose() { }
+}
+"#;
+
+    #[test]
+    fn test_extracts_razor_component() {
+        let symbols = extract(Path::new("Pages/Counter.razor"), SOURCE).unwrap();
+        assert_eq!(symbols.len(), 1);
+
+        let counter = &symbols[0];
+        assert_eq!(counter.name, "Counter");
+        let file = counter.location.unwrap();
+        assert_eq!((file.start_line, file.start_column), (1, 1));
+        assert_eq!((file.end_line, file.end_column), (30, 1));
+        assert!(matches!(counter.kind, SymbolKind::Component));
+        assert_eq!(
+            counter.modifiers,
+            vec![
+                "@page \"/counter\"",
+                "@page \"/counter/{Start:int}\"",
+                "[Authorize(Roles = \"Admin\")]",
+            ]
+        );
+        assert!(counter.has_annotation("Authorize"));
+        assert_eq!(counter.bases, vec!["LayoutComponentBase", "IDisposable"]);
+
+        let children: Vec<(&str, &SymbolKind)> = counter
+            .children
+            .iter()
+            .map(|s| (s.name.as_str(), &s.kind))
+            .collect();
+        let uses = SymbolKind::Other("uses".to_string());
+        assert_eq!(
+            children,
+            vec![
+                ("Weather", &SymbolKind::Property),
+                ("Navigation", &SymbolKind::Property),
+                ("Logger", &SymbolKind::Property),
+                ("count", &SymbolKind::Field),
+                ("message", &SymbolKind::Field),
+                ("Start", &SymbolKind::Property),
+                ("Auth", &SymbolKind::Property),
+                ("OnInitialized", &SymbolKind::Method),
+                ("Increment", &SymbolKind::Method),
+                ("Dispose", &SymbolKind::Method),
+                ("PageTitle", &uses),
+                ("MudButton", &uses),
+                ("Shared.Alert", &uses),
+            ]
+        );
+
+        let weather = &counter.children[0];
+        assert_eq!(weather.modifiers, vec!["@inject"]);
+        assert_eq!(weather.type_name.as_deref(), Some("IWeatherService"));
+        assert_eq!(weather.location.unwrap().start_line, 6);
+
+        // Generic arguments on directive lines are not component tags
+        let logger = &counter.children[2];
+        assert_eq!(logger.type_name.as_deref(), Some("ILogger<Counter>"));
+
+        assert!(counter.children[5].has_annotation("Parameter"));
+        assert!(counter.children[6].has_annotation("CascadingParameter"));
+
+        // `@code` members keep their lines in the .razor file
+        let on_initialized = counter.children[7].location.unwrap();
+        assert_eq!(
+            (on_initialized.start_line, on_initialized.start_column),
+            (24, 5)
+        );
+        let button = counter.children[11].location.unwrap();
+        assert_eq!((button.start_line, button.start_column), (12, 1));
+    }
+
+    #[test]
+    fn test_markup_only_component() {
+        let symbols = extract(Path::new("Badge.razor"), "<span>@ChildContent</span>").unwrap();
+        assert_eq!(symbols[0].name, "Badge");
+        let file = symbols[0].location.unwrap();
+        assert_eq!((file.end_line, file.end_column), (1, 27));
+        assert!(symbols[0].children.is_empty());
+        assert!(symbols[0].modifiers.is_empty());
+    }
+}
@@ -0,0 +1,186 @@
+use anyhow::Result;
+use tree_sitter::Node;
+
+use super::{field_text, parse, symbol_at, text};
+use crate::types::{Symbol, SymbolKind};
+
+pub(super) fn extract(source: &str) -> Result<Vec<Symbol>> {
+    let tree = parse(tree_sitter_rust::language(), source)?;
+    Ok(items(&tree.root_node(), source))
+}
+
+/// Symbols for the items directly inside a source file, module or block body
+fn items(node: &Node, source: &str) -> Vec<Symbol> {
+    let mut symbols = Vec::new();
+    let mut cursor = node.walk();
+
+    for child in node.named_children(&mut cursor) {
+        let item = match child.kind() {
+            "struct_item" | "union_item" => named(&child, source, SymbolKind::Struct),
+            "enum_item" => named(&child, source, SymbolKind::Enum),
+            "function_item" => named(&child, source, SymbolKind::Function),
+            "trait_item" => named(&child, source, SymbolKind::Trait).map(|mut item| {
+                item.children = methods(&child, source);
+                item
+            }),
+            "impl_item" => impl_block(&child, source),
+            "mod_item" => named(&child, source, SymbolKind::Module).map(|mut item| {
+                // `mod foo;` has no body; its items live in another file
+                if let Some(body) = child.child_by_field_name("body") {
+                    item.children = items(&body, source);
+                }
+                item
+            }),
+            _ => None,
+        };
+        symbols.extend(item);
+    }
+
+    symbols
+}
+
+/// A named item with its visibility and function qualifiers as modifiers
+fn named(node: &Node, source: &str, kind: SymbolKind) -> Option<Symbol> {
+    let name = field_text(node, "name", source)?;
+    let mut item = symbol_at(name, kind, node);
+    item.modifiers = modifiers(node, source);
+    Some(item)
+}
+
+/// `impl Type` or `impl Trait for Type`; the name reads like the header
+fn impl_block(node: &Node, source: &str) -> Option<Symbol> {
+    let self_type = field_text(node, "type", source)?;
+    let name = match field_text(node, "trait", source) {
+        Some(trait_name) => format!("{} for {}", trait_name, self_type),
+        None => self_type.to_string(),
+    };
+
+    let mut item = symbol_at(&name, SymbolKind::Impl, node);
+    item.modifiers = modifiers(node, source);
+    item.children = methods(node, source);
+    Some(item)
+}
+
+/// Functions declared or defined in a trait or impl body
+fn methods(node: &Node, source: &str) -> Vec<Symbol> {
+    let Some(body) = node.child_by_field_name("body") else {
+        return Vec::new();
+    };
+
+    let mut methods = Vec::new();
+    let mut cursor = body.walk();
+    for child in body.named_children(&mut cursor) {
+        if matches!(child.kind(), "function_item" | "function_signature_item") {
+            methods.extend(named(&child, source, SymbolKind::Method));
+        }
+    }
+    methods
+}
+
+/// `pub`, `pub(crate)`, `async`, `unsafe`, `const`, ... in source order
+fn modifiers(node: &Node, source: &str) -> Vec<String> {
+    let mut modifiers = Vec::new();
+    let mut cursor = node.walk();
+
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            "visibility_modifier" => modifiers.push(text(&child, source).to_string()),
+            "function_modifiers" => {
+                let mut modifier_cursor = child.walk();
+                for modifier in child.children(&mut modifier_cursor) {
+                    modifiers.push(text(&modifier, source).to_string());
+                }
+            }
+            "unsafe" => modifiers.push("unsafe".to_string()),
+            _ => {}
+        }
+    }
+
+    modifiers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Location;
+
+    const SOURCE: &str = r#"
+pub struct Config {
+    pub name: String,
+}
+
+pub(crate) enum Mode { Fast, Safe }
+
+pub trait Analyzer {
+    fn analyze(&self) -> bool;
+    fn name(&self) -> &str { "x" }
+}
+
+impl Config {
+    pub fn new() -> Self { todo!() }
+    async fn load(&self) {}
+}
+
+impl std::fmt::Display for Config {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result { Ok(()) }
+}
+
+pub async fn run() {}
+
+mod tests {
+    fn helper() {}
+}
+
+mod external;
+"#;
+
+    #[test]
+    fn test_extracts_rust_outline() {
+        let symbols = extract(SOURCE).unwrap();
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "Config",
+                "Mode",
+                "Analyzer",
+                "Config",
+                "std::fmt::Display for Config",
+                "run",
+                "tests",
+                "external"
+            ]
+        );
+
+        assert!(matches!(symbols[0].kind, SymbolKind::Struct));
+        assert_eq!(symbols[0].modifiers, vec!["pub"]);
+        assert_eq!(
+            symbols[0].location,
+            Some(Location {
+                start_line: 2,
+                start_column: 1,
+                end_line: 4,
+                end_column: 2,
+            })
+        );
+        assert!(matches!(symbols[1].kind, SymbolKind::Enum));
+        assert_eq!(symbols[1].modifiers, vec!["pub(crate)"]);
+
+        assert!(matches!(symbols[2].kind, SymbolKind::Trait));
+        assert_eq!(symbols[2].children.len(), 2);
+
+        let inherent = &symbols[3];
+        assert!(matches!(inherent.kind, SymbolKind::Impl));
+        assert_eq!(inherent.children[0].modifiers, vec!["pub"]);
+        assert_eq!(inherent.children[1].modifiers, vec!["async"]);
+        assert_eq!(inherent.children[1].location.unwrap().start_line, 15);
+        assert!(matches!(inherent.children[1].kind, SymbolKind::Method));
+
+        assert!(matches!(symbols[5].kind, SymbolKind::Function));
+        assert_eq!(symbols[5].modifiers, vec!["pub", "async"]);
+
+        assert!(matches!(symbols[6].kind, SymbolKind::Module));
+        assert_eq!(symbols[6].children[0].name, "helper");
+        assert!(symbols[7].children.is_empty());
+    }
+}
@@ -0,0 +1,371 @@
+use anyhow::Result;
+use tree_sitter::{Language, Node};
+
+use super::{field_text, has_token, location, parse, symbol_at, text};
+use crate::types::{Symbol, SymbolKind};
+
+/// Extracts the outline of a TypeScript or JavaScript module.
+///
+/// The TypeScript, TSX and JavaScript grammars share node names for
+/// everything extracted here, so one walker serves all of them.
+pub(super) fn extract(language: Language, source: &str) -> Result<Vec<Symbol>> {
+    let tree = parse(language, source)?;
+    Ok(items(&tree.root_node(), source))
+}
+
+/// Symbols for the statements directly inside a module or namespace body
+fn items(node: &Node, source: &str) -> Vec<Symbol> {
+    let mut symbols = Vec::new();
+    let mut cursor = node.walk();
+
+    for child in node.named_children(&mut cursor) {
+        if child.kind() == "export_statement" {
+            // `export { a, b }` and anonymous default exports have no declaration
+            let Some(declaration) = child.child_by_field_name("declaration") else {
+                continue;
+            };
+            let mut export = vec!["export".to_string()];
+            if has_token(&child, "default") {
+                export.push("default".to_string());
+            }
+
+            for mut item in declarations(&declaration, source, true) {
+                item.modifiers.splice(0..0, export.iter().cloned());
+                symbols.push(item);
+            }
+        } else {
+            symbols.extend(declarations(&child, source, false));
+        }
+    }
+
+    symbols
+}
+
+/// Symbols introduced by one declaration statement.
+///
+/// Variables are only listed when they hold a function or component, or
+/// when they are exported; other locals are implementation details.
+fn declarations(node: &Node, source: &str, exported: bool) -> Vec<Symbol> {
+    let item = match node.kind() {
+        "class_declaration" | "abstract_class_declaration" => class(node, source),
+        "function_declaration" | "generator_function_declaration" => {
+            let name = field_text(node, "name", source);
+            name.map(|name| function(name, node))
+        }
+        "lexical_declaration" | "variable_declaration" => {
+            return variables(node, source, exported);
+        }
+        "interface_declaration" => interface(node, source),
+        "type_alias_declaration" => named(node, source, SymbolKind::Other("type".to_string())),
+        "enum_declaration" => named(node, source, SymbolKind::Enum),
+        "internal_module" => named(node, source, SymbolKind::Module).map(|mut item| {
+            if let Some(body) = node.child_by_field_name("body") {
+                item.children = items(&body, source);
+            }
+            item
+        }),
+        _ => None,
+    };
+
+    item.into_iter().collect()
+}
+
+fn named(node: &Node, source: &str, kind: SymbolKind) -> Option<Symbol> {
+    field_text(node, "name", source).map(|name| symbol_at(name, kind, node))
+}
+
+/// A function, or a component if it is capitalized and renders JSX
+fn function(name: &str, node: &Node) -> Symbol {
+    let kind = if is_component_name(name) && contains_jsx(node) {
+        SymbolKind::Component
+    } else {
+        SymbolKind::Function
+    };
+
+    let mut item = symbol_at(name, kind, node);
+    if has_token(node, "async") {
+        item.modifiers.push("async".to_string());
+    }
+    item
+}
+
+/// `const`, `let` and `var` declarations, one symbol per declarator
+fn variables(node: &Node, source: &str, exported: bool) -> Vec<Symbol> {
+    let keyword = match node.child_by_field_name("kind") {
+        Some(kind) => text(&kind, source),
+        None => "var",
+    };
+
+    let mut symbols = Vec::new();
+    let mut cursor = node.walk();
+    for declarator in node.named_children(&mut cursor) {
+        if declarator.kind() != "variable_declarator" {
+            continue;
+        }
+        // Destructuring patterns don't name a single symbol
+        let Some(name) = declarator
+            .child_by_field_name("name")
+            .filter(|n| n.kind() == "identifier")
+            .map(|n| text(&n, source))
+        else {
+            continue;
+        };
+        let value = declarator.child_by_field_name("value");
+
+        match value {
+            Some(value)
+                if matches!(
+                    value.kind(),
+                    "arrow_function" | "function" | "function_expression" | "generator_function"
+                ) =>
+            {
+                let mut item = function(name, &value);
+                item.location = Some(location(&declarator));
+                symbols.push(item);
+            }
+            // `memo(() => <div />)`, `forwardRef(...)` and similar wrappers
+            Some(value) if is_component_name(name) && contains_jsx(&value) => {
+                symbols.push(symbol_at(name, SymbolKind::Component, &declarator));
+            }
+            _ if exported => {
+                symbols.push(symbol_at(
+                    name,
+                    SymbolKind::Other(keyword.to_string()),
+                    &declarator,
+                ));
+            }
+            _ => {}
+        }
+    }
+    symbols
+}
+
+/// A class with its methods and fields; React class components become
+/// components
+fn class(node: &Node, source: &str) -> Option<Symbol> {
+    let name = field_text(node, "name", source)?;
+    let kind = if extends_react_component(node, source) {
+        SymbolKind::Component
+    } else {
+        SymbolKind::Class
+    };
+
+    let mut item = symbol_at(name, kind, node);
+    if node.kind() == "abstract_class_declaration" {
+        item.modifiers.push("abstract".to_string());
+    }
+
+    let Some(body) = node.child_by_field_name("body") else {
+        return Some(item);
+    };
+    let mut cursor = body.walk();
+    for member in body.named_children(&mut cursor) {
+        let (kind, name_field) = match member.kind() {
+            "method_definition" | "abstract_method_signature" | "method_signature" => {
+                (SymbolKind::Method, "name")
+            }
+            "public_field_definition" => (SymbolKind::Property, "name"),
+            "field_definition" => (SymbolKind::Property, "property"),
+            _ => continue,
+        };
+        if let Some(name) = field_text(&member, name_field, source) {
+            let mut child = symbol_at(name, kind, &member);
+            child.modifiers = member_modifiers(&member, source);
+            item.children.push(child);
+        }
+    }
+
+    Some(item)
+}
+
+/// An interface with its property and method signatures
+fn interface(node: &Node, source: &str) -> Option<Symbol> {
+    let mut item = named(node, source, SymbolKind::Interface)?;
+
+    if let Some(body) = node.child_by_field_name("body") {
+        let mut cursor = body.walk();
+        for member in body.named_children(&mut cursor) {
+            let kind = match member.kind() {
+                "property_signature" => SymbolKind::Property,
+                "method_signature" => SymbolKind::Method,
+                _ => continue,
+            };
+            if let Some(name) = field_text(&member, "name", source) {
+                item.children.push(symbol_at(name, kind, &member));
+            }
+        }
+    }
+
+    Some(item)
+}
+
+/// `private`, `static`, `async`, `get`, `readonly`, ... in source order
+fn member_modifiers(node: &Node, source: &str) -> Vec<String> {
+    let mut modifiers = Vec::new();
+    let mut cursor = node.walk();
+
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            "accessibility_modifier" | "override_modifier" => {
+                modifiers.push(text(&child, source).to_string())
+            }
+            "static" | "async" | "get" | "set" | "readonly" | "abstract" => {
+                modifiers.push(child.kind().to_string())
+            }
+            _ => {}
+        }
+    }
+
+    modifiers
+}
+
+/// React components are capitalized so JSX can tell them from HTML tags
+fn is_component_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+fn contains_jsx(node: &Node) -> bool {
+    if matches!(
+        node.kind(),
+        "jsx_element" | "jsx_self_closing_element" | "jsx_fragment"
+    ) {
+        return true;
+    }
+
+    let mut cursor = node.walk();
+    let found = node
+        .named_children(&mut cursor)
+        .any(|child| contains_jsx(&child));
+    found
+}
+
+/// `extends Component`, `extends React.PureComponent<Props>`, ...
+fn extends_react_component(node: &Node, source: &str) -> bool {
+    let mut cursor = node.walk();
+    let Some(heritage) = node
+        .named_children(&mut cursor)
+        .find(|child| child.kind() == "class_heritage")
+    else {
+        return false;
+    };
+
+    let heritage = text(&heritage, source);
+    let base = heritage
+        .trim_start()
+        .trim_start_matches("extends")
+        .trim_start()
+        .split(|c: char| !(c.is_alphanumeric() || c == '.' || c == '_'))
+        .next()
+        .unwrap_or("");
+
+    matches!(
+        base.trim_start_matches("React."),
+        "Component" | "PureComponent"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+import React, { memo } from 'react';
+
+export interface ButtonProps {
+    label: string;
+    onClick(): void;
+}
+
+export type Size = 'small' | 'large';
+
+export enum Theme { Light, Dark }
+
+export const API_URL = '/api';
+const cache = new Map();
+
+export abstract class Store {
+    private items: string[] = [];
+    static create() {}
+    abstract load(): Promise<void>;
+}
+
+export default function App() {
+    return <div><Button label="x" /></div>;
+}
+
+export const Button = ({ label }: ButtonProps) => <button>{label}</button>;
+
+const Icon = memo(() => <svg />);
+
+export async function fetchItems() {}
+
+const formatDate = (date: Date) => date.toISOString();
+
+class Legacy extends React.Component<ButtonProps> {
+    render() { return null; }
+}
+"#;
+
+    #[test]
+    fn test_extracts_typescript_outline() {
+        let symbols = extract(tree_sitter_typescript::language_tsx(), SOURCE).unwrap();
+        let outline: Vec<(&str, String)> = symbols
+            .iter()
+            .map(|s| (s.name.as_str(), format!("{:?}", s.kind)))
+            .collect();
+        assert_eq!(
+            outline,
+            vec![
+                ("ButtonProps", "Interface".to_string()),
+                ("Size", "Other(\"type\")".to_string()),
+                ("Theme", "Enum".to_string()),
+                ("API_URL", "Other(\"const\")".to_string()),
+                ("Store", "Class".to_string()),
+                ("App", "Component".to_string()),
+                ("Button", "Component".to_string()),
+                ("Icon", "Component".to_string()),
+                ("fetchItems", "Function".to_string()),
+                ("formatDate", "Function".to_string()),
+                ("Legacy", "Component".to_string()),
+            ]
+        );
+
+        assert_eq!(symbols[0].children.len(), 2);
+        assert_eq!(symbols[4].modifiers, vec!["export", "abstract"]);
+        let members: Vec<(&str, &Vec<String>)> = symbols[4]
+            .children
+            .iter()
+            .map(|m| (m.name.as_str(), &m.modifiers))
+            .collect();
+        assert_eq!(
+            members,
+            vec![
+                ("items", &vec!["private".to_string()]),
+                ("create", &vec!["static".to_string()]),
+                ("load", &vec!["abstract".to_string()]),
+            ]
+        );
+        assert_eq!(symbols[5].modifiers, vec!["export", "default"]);
+        assert_eq!(symbols[8].modifiers, vec!["export", "async"]);
+    }
+
+    #[test]
+    fn test_extracts_javascript_outline() {
+        let source = r#"
+class Counter extends Component {
+    count = 0;
+    increment() {}
+}
+function helper() {}
+export function Header() { return <header />; }
+module.exports = { helper };
+"#;
+        let symbols = extract(tree_sitter_javascript::language(), source).unwrap();
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Counter", "helper", "Header"]);
+        assert!(matches!(symbols[0].kind, SymbolKind::Component));
+        assert_eq!(symbols[0].children.len(), 2);
+        assert!(matches!(symbols[1].kind, SymbolKind::Function));
+        assert!(matches!(symbols[2].kind, SymbolKind::Component));
+    }
+}
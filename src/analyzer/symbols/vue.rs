@@ -0,0 +1,114 @@
+use anyhow::Result;
+use regex::Regex;
+use std::sync::OnceLock;
+
+use super::{file_location, symbol, typescript};
+use crate::types::{Symbol, SymbolKind};
+
+fn script_block() -> &'static Regex {
+    static SCRIPT: OnceLock<Regex> = OnceLock::new();
+    SCRIPT.get_or_init(|| Regex::new(r"(?s)<script\b([^>]*)>(.*?)</script>").unwrap())
+}
+
+/// A Vue single-file component is one component named after its file.
+///
+/// Its `<script>` and `<script setup>` blocks are parsed as TypeScript or
+/// JavaScript (per their `lang` attribute) and become its children; a
+/// `<script setup>` block adds a `setup` modifier. The component spans the
+/// whole file.
+pub(super) fn extract(name: &str, source: &str) -> Result<Vec<Symbol>> {
+    let mut component = symbol(name, SymbolKind::Component);
+    component.location = Some(file_location(source));
+
+    for block in script_block().captures_iter(source) {
+        let attributes = &block[1];
+        let language = if attributes.contains("lang=\"tsx\"") || attributes.contains("lang='tsx'") {
+            tree_sitter_typescript::language_tsx()
+        } else if attributes.contains("lang=\"ts\"") || attributes.contains("lang='ts'") {
+            tree_sitter_typescript::language_typescript()
+        } else {
+            tree_sitter_javascript::language()
+        };
+
+        if attributes.split_whitespace().any(|a| a == "setup") {
+            component.modifiers.push("setup".to_string());
+        }
+        let script = block.get(2).unwrap();
+        let mut children = typescript::extract(language, script.as_str())?;
+        shift(&mut children, &source[..script.start()]);
+        component.children.extend(children);
+    }
+
+    Ok(vec![component])
+}
+
+/// Moves locations within a script block to locations within the file,
+/// given the text preceding the block
+fn shift(symbols: &mut [Symbol], preceding: &str) {
+    let lines = preceding.matches('\n').count();
+    let columns = preceding.len() - preceding.rfind('\n').map_or(0, |i| i + 1);
+
+    for symbol in symbols {
+        if let Some(location) = &mut symbol.location {
+            // Only the block's first line starts mid-line
+            if location.start_line == 1 {
+                location.start_column += columns;
+            }
+            if location.end_line == 1 {
+                location.end_column += columns;
+            }
+            location.start_line += lines;
+            location.end_line += lines;
+        }
+        shift(&mut symbol.children, preceding);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extracts_script_setup_component() {
+        let source = r#"
+<template>
+  <button @click="increment">{{ count }}</button>
+</template>
+
+<script setup lang="ts">
+import { ref } from 'vue';
+interface Props { start: number }
+const count = ref(0);
+function increment() { count.value++; }
+</script>
+"#;
+        let symbols = extract("Counter", source).unwrap();
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0].name, "Counter");
+        assert!(matches!(symbols[0].kind, SymbolKind::Component));
+        assert_eq!(symbols[0].modifiers, vec!["setup"]);
+        let file = symbols[0].location.unwrap();
+        assert_eq!((file.start_line, file.start_column), (1, 1));
+        assert_eq!((file.end_line, file.end_column), (12, 1));
+
+        let children: Vec<&str> = symbols[0]
+            .children
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(children, vec!["Props", "increment"]);
+
+        let increment = symbols[0].children[1].location.unwrap();
+        assert_eq!((increment.start_line, increment.start_column), (10, 1));
+    }
+
+    #[test]
+    fn test_template_only_component() {
+        let symbols = extract("Badge", "<template><span /></template>").unwrap();
+        assert_eq!(symbols[0].name, "Badge");
+        let file = symbols[0].location.unwrap();
+        assert_eq!((file.end_line, file.end_column), (1, 30));
+        assert!(symbols[0].modifiers.is_empty());
+        assert!(symbols[0].children.is_empty());
+    }
+}
@@ -14,7 +14,14 @@ pub struct Config {
 pub struct ServerConfig {
     pub name: String,
     pub version: String,
-    pub transport: String, // "stdio"
+    pub transport: String, // "stdio" or "http"
+    /// Listen address for the HTTP transport
+    #[serde(default = "default_bind_address")]
+    pub bind_address: String,
+}
+
+fn default_bind_address() -> String {
+    "127.0.0.1:8080".to_string()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,6 +68,7 @@ impl Default for Config {
                 name: "mcp-rust-context".to_string(),
                 version: "0.1.0".to_string(),
                 transport: "stdio".to_string(),
+                bind_address: default_bind_address(),
             },
             analyzer: AnalyzerConfig {
                 target_frameworks: vec![
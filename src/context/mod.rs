@@ -1,9 +1,32 @@
 use crate::training::{SearchCriteria, TrainingManager};
 use crate::types::{
-    AnalysisResult, CodePattern, DotNetProject, Project, ProjectType, SeverityLevel, Statistics,
-    Suggestion,
+    AnalysisResult, CodePattern, Dependency, Project, ProjectMetadata, ProjectType, SeverityLevel,
+    Statistics, Suggestion, Symbol, SymbolKind,
 };
 use anyhow::Result;
+use std::path::Path;
+
+/// Files listed in the code outline section before it is truncated
+const OUTLINE_MAX_FILES: usize = 40;
+
+/// Annotations marking Spring-managed classes
+const SPRING_STEREOTYPES: &[&str] = &[
+    "@SpringBootApplication",
+    "@RestController",
+    "@Controller",
+    "@Service",
+    "@Repository",
+    "@Component",
+    "@Configuration",
+];
+
+/// .NET frameworks from most to least specific, to pick one for a solution
+const DOTNET_FRAMEWORKS: &[&str] = &[
+    "blazor-server",
+    "blazor-wasm",
+    "aspnet-core",
+    "worker-service",
+];
 
 /// Builds intelligent context for AI assistants based on project analysis
 #[derive(Default)]
@@ -28,20 +51,42 @@ impl ContextBuilder {
         let framework_type = self.detect_framework_from_project(&project);
 
         // Get relevant patterns
-        let patterns = if let Some(ref manager) = self.training_manager {
+        let mut patterns = if let Some(ref manager) = self.training_manager {
             self.get_patterns_for_project(manager, &framework_type, &project)?
         } else {
             vec![]
         };
 
         // Generate suggestions
-        let suggestions = self.generate_project_suggestions(&project, &framework_type);
+        let mut suggestions = self.generate_project_suggestions(&project, &framework_type);
+
+        // Sub-projects in another language bring their own framework
+        let mut foreign = Vec::new();
+        Self::foreign_projects(&project, &mut foreign);
+        for subproject in foreign {
+            let framework = self.detect_framework_from_project(subproject);
+            if let Some(ref manager) = self.training_manager {
+                for pattern in self.get_patterns_for_project(manager, &framework, subproject)? {
+                    if !patterns.iter().any(|p| p.id == pattern.id) {
+                        patterns.push(pattern);
+                    }
+                }
+            }
+            suggestions.extend(self.generate_project_suggestions(subproject, &framework));
+        }
 
         // Collect statistics
+        let files = project.all_files();
         let statistics = Statistics {
-            total_files: project.files.len(),
-            total_classes: project.files.iter().map(|f| f.symbols.len()).sum(),
-            total_methods: 0, // Would need symbol parsing
+            total_files: files.len(),
+            total_classes: files
+                .iter()
+                .map(|f| Symbol::count(&f.symbols, SymbolKind::is_type))
+                .sum(),
+            total_methods: files
+                .iter()
+                .map(|f| Symbol::count(&f.symbols, SymbolKind::is_callable))
+                .sum(),
             total_lines: 0,
             framework_version: project
                 .metadata
@@ -50,7 +95,7 @@ impl ContextBuilder {
                 .or(project.metadata.rust_edition.clone())
                 .or(project.metadata.node_version.clone())
                 .unwrap_or_else(|| "unknown".to_string()),
-            package_count: project.dependencies.len(),
+            package_count: project.direct_dependencies().count(),
         };
 
         Ok(AnalysisResult {
@@ -64,30 +109,40 @@ impl ContextBuilder {
     /// Detect framework from generic project
     fn detect_framework_from_project(&self, project: &Project) -> String {
         match project.project_type {
-            ProjectType::DotNet => {
-                // Check for Blazor, ASP.NET, etc.
-                if project
-                    .dependencies
+            ProjectType::DotNet if !project.subprojects.is_empty() => {
+                // A solution is its most specific project
+                let frameworks: Vec<String> = project
+                    .subprojects
                     .iter()
-                    .any(|d| d.name.contains("AspNetCore.Components"))
-                {
-                    "blazor-server".to_string()
-                } else if project
-                    .dependencies
+                    .map(|p| self.detect_framework_from_project(p))
+                    .collect();
+                DOTNET_FRAMEWORKS
                     .iter()
-                    .any(|d| d.name.contains("AspNetCore"))
-                {
+                    .find(|f| frameworks.iter().any(|fw| fw == *f))
+                    .unwrap_or(&"dotnet")
+                    .to_string()
+            }
+            ProjectType::DotNet => {
+                // The SDK says what kind of app it is; Blazor needs components
+                let sdk = project.metadata.sdk.as_deref().unwrap_or_default();
+                if sdk.ends_with(".BlazorWebAssembly") {
+                    "blazor-wasm".to_string()
+                } else if Self::has_components(project) {
+                    "blazor-server".to_string()
+                } else if sdk.ends_with(".Web") {
                     "aspnet-core".to_string()
+                } else if sdk.ends_with(".Worker") {
+                    "worker-service".to_string()
                 } else {
                     "dotnet".to_string()
                 }
             }
             ProjectType::Rust => {
-                if project.dependencies.iter().any(|d| d.name == "actix-web") {
+                if project.direct_dependencies().any(|d| d.name == "actix-web") {
                     "actix-web".to_string()
-                } else if project.dependencies.iter().any(|d| d.name == "axum") {
+                } else if project.direct_dependencies().any(|d| d.name == "axum") {
                     "axum".to_string()
-                } else if project.dependencies.iter().any(|d| d.name == "tokio") {
+                } else if project.direct_dependencies().any(|d| d.name == "tokio") {
                     "tokio".to_string()
                 } else {
                     "rust".to_string()
@@ -96,24 +151,24 @@ impl ContextBuilder {
             ProjectType::Node => {
                 if let Some(fw) = project.metadata.extra.get("framework") {
                     fw.clone()
-                } else if project.dependencies.iter().any(|d| d.name == "express") {
+                } else if project.direct_dependencies().any(|d| d.name == "express") {
                     "express".to_string()
-                } else if project.dependencies.iter().any(|d| d.name == "react") {
+                } else if project.direct_dependencies().any(|d| d.name == "react") {
                     "react".to_string()
-                } else if project.dependencies.iter().any(|d| d.name == "vue") {
+                } else if project.direct_dependencies().any(|d| d.name == "vue") {
                     "vue".to_string()
-                } else if project.dependencies.iter().any(|d| d.name == "next") {
+                } else if project.direct_dependencies().any(|d| d.name == "next") {
                     "nextjs".to_string()
                 } else {
                     "node".to_string()
                 }
             }
             ProjectType::Python => {
-                if project.dependencies.iter().any(|d| d.name == "django") {
+                if project.direct_dependencies().any(|d| d.name == "django") {
                     "django".to_string()
-                } else if project.dependencies.iter().any(|d| d.name == "flask") {
+                } else if project.direct_dependencies().any(|d| d.name == "flask") {
                     "flask".to_string()
-                } else if project.dependencies.iter().any(|d| d.name == "fastapi") {
+                } else if project.direct_dependencies().any(|d| d.name == "fastapi") {
                     "fastapi".to_string()
                 } else {
                     "python".to_string()
@@ -121,14 +176,12 @@ impl ContextBuilder {
             }
             ProjectType::Go => {
                 if project
-                    .dependencies
-                    .iter()
+                    .direct_dependencies()
                     .any(|d| d.name.contains("gin-gonic"))
                 {
                     "gin".to_string()
                 } else if project
-                    .dependencies
-                    .iter()
+                    .direct_dependencies()
                     .any(|d| d.name.contains("fiber"))
                 {
                     "fiber".to_string()
@@ -138,9 +191,9 @@ impl ContextBuilder {
             }
             ProjectType::Java => {
                 if project
-                    .dependencies
-                    .iter()
+                    .direct_dependencies()
                     .any(|d| d.name.contains("spring"))
+                    || Self::has_annotation(project, SPRING_STEREOTYPES)
                 {
                     "spring".to_string()
                 } else {
@@ -163,12 +216,116 @@ impl ContextBuilder {
         }
     }
 
+    /// Blazor components overriding the synchronous `OnInitialized`:
+    /// `.razor` components and classes deriving from `ComponentBase`
+    fn check_blazor_patterns(project: &Project) -> Vec<Suggestion> {
+        let mut suggestions = Vec::new();
+
+        for file in project.all_files() {
+            Symbol::walk(&file.symbols, &mut |class| {
+                let is_component = match class.kind {
+                    SymbolKind::Component => file.language == "razor",
+                    SymbolKind::Class => class.bases.iter().any(|b| b.contains("ComponentBase")),
+                    _ => false,
+                };
+                if !is_component {
+                    return;
+                }
+
+                let sync_init = class.children.iter().find(|m| {
+                    matches!(m.kind, SymbolKind::Method)
+                        && m.name == "OnInitialized"
+                        && !m.modifiers.iter().any(|modifier| modifier == "async")
+                });
+                if let Some(sync_init) = sync_init {
+                    suggestions.push(Suggestion {
+                        severity: SeverityLevel::Warning,
+                        category: "blazor-lifecycle".to_string(),
+                        message: format!(
+                            "Component '{}' uses synchronous OnInitialized(). Consider using OnInitializedAsync() for better performance.",
+                            class.name
+                        ),
+                        file: Some(file.path.clone()),
+                        line: sync_init.location.map(|l| l.start_line),
+                    });
+                }
+            });
+        }
+
+        suggestions
+    }
+
+    /// `async void` methods, whose exceptions can't be observed
+    fn check_async_patterns(project: &Project) -> Vec<Suggestion> {
+        let mut suggestions = Vec::new();
+
+        for file in project.all_files() {
+            Symbol::walk(&file.symbols, &mut |class| {
+                for method in &class.children {
+                    let is_async_void = matches!(method.kind, SymbolKind::Method)
+                        && method.modifiers.iter().any(|m| m == "async")
+                        && method.type_name.as_deref() == Some("void");
+                    if is_async_void {
+                        suggestions.push(Suggestion {
+                            severity: SeverityLevel::Warning,
+                            category: "async-patterns".to_string(),
+                            message: format!(
+                                "Method '{}' in class '{}' is async void. Use async Task instead for proper exception handling.",
+                                method.name, class.name
+                            ),
+                            file: Some(file.path.clone()),
+                            line: method.location.map(|l| l.start_line),
+                        });
+                    }
+                }
+            });
+        }
+
+        suggestions
+    }
+
+    /// Whether any symbol in the project carries one of `annotations`
+    fn has_annotation(project: &Project, annotations: &[&str]) -> bool {
+        let mut found = false;
+        for file in project.all_files() {
+            Symbol::walk(&file.symbols, &mut |s| {
+                found |= annotations.iter().any(|a| s.has_annotation(a));
+            });
+        }
+        found
+    }
+
+    /// Whether the project has Blazor components: `.razor` files or classes
+    /// deriving from `ComponentBase`
+    fn has_components(project: &Project) -> bool {
+        let mut found = false;
+        for file in project.all_files() {
+            found |= file.language == "razor";
+            Symbol::walk(&file.symbols, &mut |s| {
+                found |= matches!(s.kind, SymbolKind::Class)
+                    && s.bases.iter().any(|b| b.contains("ComponentBase"));
+            });
+        }
+        found
+    }
+
+    /// Sub-projects at any depth whose type differs from their parent's,
+    /// such as a Node frontend in a .NET repository
+    fn foreign_projects<'a>(project: &'a Project, found: &mut Vec<&'a Project>) {
+        for subproject in &project.subprojects {
+            if subproject.project_type != project.project_type {
+                found.push(subproject);
+            }
+            Self::foreign_projects(subproject, found);
+        }
+    }
+
     /// Get patterns relevant to the project
     fn get_patterns_for_project(
         &self,
         manager: &TrainingManager,
         framework: &str,
-        _project: &Project,
+        project: &Project,
     ) -> Result<Vec<CodePattern>> {
         let criteria = SearchCriteria {
             query: None,
@@ -179,11 +336,45 @@ impl ContextBuilder {
         };
 
         let results = manager.search_patterns(&criteria);
-        Ok(results
+        tracing::debug!(
+            "Found {} patterns for framework '{}' (min_score {})",
+            results.len(),
+            framework,
+            criteria.min_score
+        );
+
+        // Categories the code itself calls for come first
+        let mut categories = Vec::new();
+        for file in project.all_files() {
+            Symbol::walk(&file.symbols, &mut |s| {
+                if !matches!(s.kind, SymbolKind::Method) {
+                    return;
+                }
+                if s.name.contains("OnInitialized") && !categories.contains(&"lifecycle") {
+                    categories.push("lifecycle");
+                }
+                if s.modifiers.iter().any(|m| m == "async")
+                    && !categories.contains(&"async-patterns")
+                {
+                    categories.push("async-patterns");
+                }
+            });
+        }
+
+        let mut patterns: Vec<CodePattern> = Vec::new();
+        let by_category = categories
             .into_iter()
-            .take(10)
-            .map(|(p, _)| p.clone())
-            .collect())
+            .flat_map(|category| manager.search_by_framework_and_category(framework, category));
+        let scored = results.into_iter().map(|(p, _)| p);
+        for pattern in by_category.chain(scored) {
+            if patterns.len() == 10 {
+                break;
+            }
+            if !patterns.iter().any(|p| p.id == pattern.id) {
+                patterns.push(pattern.clone());
+            }
+        }
+        Ok(patterns)
     }
 
     /// Generate suggestions for generic project
@@ -192,48 +383,42 @@ impl ContextBuilder {
 
         // Project type specific suggestions
         match project.project_type {
-            ProjectType::Node => {
-                // Check for security vulnerabilities indicators
+            // Check for security vulnerabilities indicators
+            ProjectType::Node
                 if project
-                    .dependencies
-                    .iter()
-                    .any(|d| d.name == "express" && d.version.starts_with("3."))
-                {
-                    suggestions.push(Suggestion {
-                        severity: SeverityLevel::Warning,
-                        category: "security".to_string(),
-                        message:
-                            "Express 3.x is outdated. Consider upgrading to Express 4.x or 5.x"
-                                .to_string(),
-                        file: None,
-                        line: None,
-                    });
-                }
+                    .direct_dependencies()
+                    .any(|d| d.name == "express" && d.version_in_use().starts_with("3.")) =>
+            {
+                suggestions.push(Suggestion {
+                    severity: SeverityLevel::Warning,
+                    category: "security".to_string(),
+                    message: "Express 3.x is outdated. Consider upgrading to Express 4.x or 5.x"
+                        .to_string(),
+                    file: None,
+                    line: None,
+                });
             }
-            ProjectType::Python => {
+            ProjectType::Python
                 if !project.path.join("requirements.txt").exists()
-                    && !project.path.join("pyproject.toml").exists()
-                {
-                    suggestions.push(Suggestion {
-                        severity: SeverityLevel::Info,
-                        category: "best-practices".to_string(),
-                        message: "Consider adding a requirements.txt or pyproject.toml for dependency management".to_string(),
-                        file: None,
-                        line: None,
-                    });
-                }
+                    && !project.path.join("pyproject.toml").exists() =>
+            {
+                suggestions.push(Suggestion {
+                    severity: SeverityLevel::Info,
+                    category: "best-practices".to_string(),
+                    message: "Consider adding a requirements.txt or pyproject.toml for dependency management".to_string(),
+                    file: None,
+                    line: None,
+                });
             }
-            ProjectType::Rust => {
-                // Check for common patterns
-                if project.dependencies.iter().any(|d| d.name == "unwrap") {
-                    suggestions.push(Suggestion {
-                        severity: SeverityLevel::Warning,
-                        category: "error-handling".to_string(),
-                        message: "Avoid using .unwrap() in production code. Use proper error handling with Result".to_string(),
-                        file: None,
-                        line: None,
-                    });
-                }
+            // Check for common patterns
+            ProjectType::Rust if project.direct_dependencies().any(|d| d.name == "unwrap") => {
+                suggestions.push(Suggestion {
+                    severity: SeverityLevel::Warning,
+                    category: "error-handling".to_string(),
+                    message: "Avoid using .unwrap() in production code. Use proper error handling with Result".to_string(),
+                    file: None,
+                    line: None,
+                });
             }
             ProjectType::Php => {
                 // Laravel specific suggestions
@@ -252,8 +437,7 @@ impl ContextBuilder {
 
                         // Check for outdated Laravel
                         if project
-                            .dependencies
-                            .iter()
+                            .direct_dependencies()
                             .any(|d| d.name == "laravel/framework" && d.version.starts_with("^8"))
                         {
                             suggestions.push(Suggestion {
@@ -272,8 +456,7 @@ impl ContextBuilder {
                     if frontend == "vue" {
                         // Check for Inertia.js (common in Laravel + Vue)
                         if project
-                            .dependencies
-                            .iter()
+                            .direct_dependencies()
                             .any(|d| d.name == "inertiajs/inertia-laravel")
                         {
                             suggestions.push(Suggestion {
@@ -288,10 +471,10 @@ impl ContextBuilder {
                 }
 
                 // Security: Check for common security packages
-                let has_security_package = project.dependencies.iter().any(|d| {
+                let has_security_package = project.direct_dependencies().any(|d| {
                     d.name == "paragonie/random_compat" || d.name == "defuse/php-encryption"
                 });
-                if !has_security_package && project.dependencies.len() > 5 {
+                if !has_security_package && project.direct_dependencies().count() > 5 {
                     suggestions.push(Suggestion {
                         severity: SeverityLevel::Info,
                         category: "security".to_string(),
@@ -301,17 +484,48 @@ impl ContextBuilder {
                     });
                 }
             }
+            ProjectType::DotNet => {
+                if framework.starts_with("blazor-") {
+                    suggestions.extend(Self::check_blazor_patterns(project));
+                }
+                suggestions.extend(Self::check_async_patterns(project));
+            }
+            ProjectType::Java if framework == "spring" => {
+                for file in project.all_files() {
+                    let mut injected = Vec::new();
+                    Symbol::walk(&file.symbols, &mut |s| {
+                        if matches!(s.kind, SymbolKind::Field) && s.has_annotation("@Autowired") {
+                            injected.push(s);
+                        }
+                    });
+
+                    if let Some(first) = injected.first() {
+                        let names: Vec<&str> = injected.iter().map(|s| s.name.as_str()).collect();
+                        suggestions.push(Suggestion {
+                            severity: SeverityLevel::Info,
+                            category: "best-practices".to_string(),
+                            message: format!(
+                                "Field injection with @Autowired ({}). Prefer constructor injection for immutability and testability",
+                                names.join(", ")
+                            ),
+                            file: Some(file.path.clone()),
+                            line: first.location.map(|l| l.start_line),
+                        });
+                    }
+                }
+            }
             _ => {}
         }
 
         // Generic suggestion based on file count
-        if project.files.len() > 100 {
+        let file_count = project.all_files().len();
+        if file_count > 100 {
             suggestions.push(Suggestion {
                 severity: SeverityLevel::Info,
                 category: "architecture".to_string(),
                 message: format!(
                     "Large project with {} files. Consider modular organization.",
-                    project.files.len()
+                    file_count
                 ),
                 file: None,
                 line: None,
@@ -354,9 +568,16 @@ impl ContextBuilder {
         context.push_str(&format!("**Type:** {}\n", project.project_type.as_str()));
 
         // Metadata
-        if let Some(ref tf) = project.metadata.target_framework {
+        if let Some(tf) = Self::target_frameworks(&project.metadata) {
             context.push_str(&format!("**Target Framework:** {}\n", tf));
         }
+        if let Some(ref sdk) = project.metadata.sdk {
+            context.push_str(&format!("**SDK:** {}\n", sdk));
+        }
+        let settings = Self::build_settings(&project.metadata);
+        if !settings.is_empty() {
+            context.push_str(&format!("**Build:** {}\n", settings.join(", ")));
+        }
         if let Some(ref edition) = project.metadata.rust_edition {
             context.push_str(&format!("**Rust Edition:** {}\n", edition));
         }
@@ -370,16 +591,18 @@ impl ContextBuilder {
             context.push_str("## Dependencies\n\n");
 
             let prod_deps: Vec<_> = project
-                .dependencies
-                .iter()
+                .direct_dependencies()
                 .filter(|d| !d.dev_only)
                 .collect();
-            let dev_deps: Vec<_> = project.dependencies.iter().filter(|d| d.dev_only).collect();
+            let dev_deps: Vec<_> = project
+                .direct_dependencies()
+                .filter(|d| d.dev_only)
+                .collect();
 
             if !prod_deps.is_empty() {
                 context.push_str("### Production\n");
                 for dep in prod_deps.iter().take(20) {
-                    context.push_str(&format!("- {} ({})\n", dep.name, dep.version));
+                    context.push_str(&format!("- {} ({})\n", dep.name, Self::version(dep)));
                 }
                 if prod_deps.len() > 20 {
                     context.push_str(&format!("- ... and {} more\n", prod_deps.len() - 20));
@@ -390,13 +613,35 @@ impl ContextBuilder {
             if !dev_deps.is_empty() {
                 context.push_str("### Development\n");
                 for dep in dev_deps.iter().take(10) {
-                    context.push_str(&format!("- {} ({})\n", dep.name, dep.version));
+                    context.push_str(&format!("- {} ({})\n", dep.name, Self::version(dep)));
                 }
                 if dev_deps.len() > 10 {
                     context.push_str(&format!("- ... and {} more\n", dev_deps.len() - 10));
                 }
                 context.push('\n');
             }
+
+            let transitive: Vec<_> = project
+                .dependencies
+                .iter()
+                .filter(|d| d.transitive)
+                .collect();
+            if !transitive.is_empty() {
+                context.push_str("### Transitive\n");
+                for dep in transitive.iter().take(10) {
+                    context.push_str(&format!("- {} ({})\n", dep.name, dep.version_in_use()));
+                }
+                if transitive.len() > 10 {
+                    context.push_str(&format!("- ... and {} more\n", transitive.len() - 10));
+                }
+                context.push('\n');
+            }
+        }
+
+        // Solution projects, workspace members and nested projects
+        if !project.subprojects.is_empty() {
+            context.push_str("## Projects\n\n");
+            Self::push_projects(&mut context, &project.path, &project.subprojects);
         }
 
         // Statistics
@@ -409,12 +654,18 @@ impl ContextBuilder {
             "- Dependencies: {}\n",
             analysis.statistics.package_count
         ));
+        if analysis.statistics.total_classes > 0 || analysis.statistics.total_methods > 0 {
+            context.push_str(&format!(
+                "- Types: {}\n- Functions/Methods: {}\n",
+                analysis.statistics.total_classes, analysis.statistics.total_methods
+            ));
+        }
         context.push('\n');
 
         // File breakdown by extension
         let mut ext_counts: std::collections::HashMap<&str, usize> =
             std::collections::HashMap::new();
-        for file in &project.files {
+        for file in project.all_files() {
             *ext_counts.entry(&file.language).or_insert(0) += 1;
         }
         if !ext_counts.is_empty() {
@@ -425,6 +676,28 @@ impl ContextBuilder {
             context.push('\n');
         }
 
+        // Code outline
+        let outlined: Vec<_> = project
+            .all_files()
+            .into_iter()
+            .filter(|f| !f.symbols.is_empty())
+            .collect();
+        if !outlined.is_empty() {
+            context.push_str("## Code Outline\n\n");
+            for file in outlined.iter().take(OUTLINE_MAX_FILES) {
+                let path = file.path.strip_prefix(&project.path).unwrap_or(&file.path);
+                context.push_str(&format!("### {}\n", path.display()));
+                Self::push_outline(&mut context, &file.symbols, 0);
+                context.push('\n');
+            }
+            if outlined.len() > OUTLINE_MAX_FILES {
+                context.push_str(&format!(
+                    "... and {} more files\n\n",
+                    outlined.len() - OUTLINE_MAX_FILES
+                ));
+            }
+        }
+
         // Relevant Patterns
         if !analysis.patterns.is_empty() {
             context.push_str("## Relevant Patterns\n\n");
@@ -451,327 +724,144 @@ impl ContextBuilder {
                     SeverityLevel::Info => "ℹ️",
                 };
                 context.push_str(&format!(
-                    "{} **{}**: {}\n",
+                    "{} **{}**: {}",
                     icon, suggestion.category, suggestion.message
                 ));
+                if let Some(file) = &suggestion.file {
+                    let file = file.strip_prefix(&project.path).unwrap_or(file);
+                    match suggestion.line {
+                        Some(line) => {
+                            context.push_str(&format!(" (`{}:{}`)", file.display(), line))
+                        }
+                        None => context.push_str(&format!(" (`{}`)", file.display())),
+                    }
+                }
+                context.push('\n');
             }
         }
 
         context
     }
 
-    // ========================================================================
-    // Legacy .NET-specific methods (kept for compatibility)
-    // ========================================================================
-
-    /// Build complete analysis with patterns and suggestions
-    #[allow(dead_code)]
-    pub async fn build_analysis(
-        &self,
-        project: DotNetProject,
-    ) -> Result<crate::types::DotNetAnalysisResult> {
-        // Detect framework type from packages
-        let framework_type = self.detect_framework(&project);
-
-        // Get relevant patterns
-        let patterns = if let Some(ref manager) = self.training_manager {
-            self.get_relevant_patterns(manager, &framework_type, &project)?
-        } else {
-            vec![]
-        };
-
-        // Generate suggestions
-        let suggestions = self.generate_suggestions(&project, &framework_type);
-
-        // Collect statistics
-        let statistics = Statistics {
-            total_files: project.files.len(),
-            total_classes: project.files.iter().map(|f| f.classes.len()).sum(),
-            total_methods: project
-                .files
-                .iter()
-                .flat_map(|f| &f.classes)
-                .map(|c| c.methods.len())
-                .sum(),
-            total_lines: 0,
-            framework_version: project.target_framework.clone(),
-            package_count: project.packages.len(),
-        };
-
-        Ok(crate::types::DotNetAnalysisResult {
-            project,
-            patterns,
-            suggestions,
-            statistics,
-        })
-    }
-
-    /// Detect framework type from project packages
-    #[allow(dead_code)]
-    fn detect_framework(&self, project: &DotNetProject) -> String {
-        // Check for Blazor Server
-        if project
-            .packages
-            .iter()
-            .any(|p| p.name.contains("AspNetCore.Components"))
-        {
-            return "blazor-server".to_string();
-        }
-
-        // Check for ASP.NET Core
-        if project
-            .packages
-            .iter()
-            .any(|p| p.name.contains("AspNetCore"))
-        {
-            return "aspnet-core".to_string();
-        }
-
-        // Check for Entity Framework
-        if project
-            .packages
-            .iter()
-            .any(|p| p.name.contains("EntityFrameworkCore"))
-        {
-            return "entity-framework".to_string();
-        }
-
-        // Default
-        "dotnet".to_string()
-    }
-
-    /// Get relevant patterns based on project analysis
-    #[allow(dead_code)]
-    fn get_relevant_patterns(
-        &self,
-        manager: &TrainingManager,
-        framework: &str,
-        project: &DotNetProject,
-    ) -> Result<Vec<CodePattern>> {
-        let mut all_patterns = Vec::new();
-
-        // Search for framework-specific patterns
-        let criteria = SearchCriteria {
-            query: None,
-            category: None,
-            framework: Some(framework.to_string()),
-            tags: vec![],
-            min_score: 0.7, // Only high-quality patterns
-        };
-
-        let scored_patterns = manager.search_patterns(&criteria);
-
-        // Detect specific needs based on project structure
-        let mut additional_categories = Vec::new();
-
-        // Check for lifecycle methods usage
-        for file in &project.files {
-            for class in &file.classes {
-                for method in &class.methods {
-                    if method.name.contains("OnInitialized") {
-                        additional_categories.push("lifecycle");
-                    }
-                    if method.is_async {
-                        additional_categories.push("async-patterns");
-                    }
-                }
-            }
-        }
-
-        // Get patterns for detected categories
-        for category in additional_categories {
-            let results = manager.search_by_framework_and_category(framework, category);
-            for pattern in results {
-                if !all_patterns
-                    .iter()
-                    .any(|p: &CodePattern| p.id == pattern.id)
-                {
-                    all_patterns.push(pattern.clone());
-                }
-            }
+    /// Renders symbols as a nested markdown list, e.g. `- pub struct Config`
+    fn push_outline(context: &mut String, symbols: &[Symbol], depth: usize) {
+        for symbol in symbols {
+            let kind = match &symbol.kind {
+                SymbolKind::Other(kind) => kind.clone(),
+                kind => format!("{:?}", kind).to_lowercase(),
+            };
+            let mut line = symbol.modifiers.clone();
+            line.push(kind);
+            line.push(Self::signature(symbol));
+
+            context.push_str(&format!("{}- {}\n", "  ".repeat(depth), line.join(" ")));
+            Self::push_outline(context, &symbol.children, depth + 1);
         }
+    }
 
-        // Add scored patterns
-        for (pattern, _score) in scored_patterns.into_iter().take(5) {
-            if !all_patterns.iter().any(|p| p.id == pattern.id) {
-                all_patterns.push(pattern.clone());
+    /// The declared version, with the locked one when it differs: `^1.2 → 1.4.1`
+    fn version(dependency: &Dependency) -> String {
+        match dependency.resolved {
+            Some(ref resolved) if *resolved != dependency.version => {
+                format!("{} → {}", dependency.version, resolved)
             }
+            _ => dependency.version.clone(),
         }
-
-        Ok(all_patterns)
     }
 
-    /// Generate suggestions based on project analysis
-    #[allow(dead_code)]
-    fn generate_suggestions(&self, project: &DotNetProject, framework: &str) -> Vec<Suggestion> {
-        let mut suggestions = Vec::new();
-
-        // Check for Blazor-specific issues
-        if framework == "blazor-server" {
-            suggestions.extend(self.check_blazor_patterns(project));
+    /// `net8.0, net9.0` for a multi-targeting project
+    fn target_frameworks(metadata: &ProjectMetadata) -> Option<String> {
+        if metadata.target_frameworks.is_empty() {
+            metadata.target_framework.clone()
+        } else {
+            Some(metadata.target_frameworks.join(", "))
         }
-
-        // Check for async/await patterns
-        suggestions.extend(self.check_async_patterns(project));
-
-        // Check for dependency injection usage
-        suggestions.extend(self.check_di_patterns(project));
-
-        suggestions
     }
 
-    #[allow(dead_code)]
-    fn check_blazor_patterns(&self, project: &DotNetProject) -> Vec<Suggestion> {
-        let mut suggestions = Vec::new();
-
-        for file in &project.files {
-            for class in &file.classes {
-                // Check if inherits from ComponentBase
-                let is_component = class
-                    .base_class
-                    .as_ref()
-                    .map(|b| b.contains("ComponentBase"))
-                    .unwrap_or(false);
-
-                if is_component {
-                    // Check for synchronous OnInitialized
-                    let has_sync_init = class
-                        .methods
-                        .iter()
-                        .any(|m| m.name == "OnInitialized" && !m.is_async);
-
-                    if has_sync_init {
-                        suggestions.push(Suggestion {
-                            severity: SeverityLevel::Warning,
-                            category: "blazor-lifecycle".to_string(),
-                            message: format!(
-                                "Component '{}' uses synchronous OnInitialized(). Consider using OnInitializedAsync() for better performance.",
-                                class.name
-                            ),
-                            file: Some(file.path.clone()),
-                            line: None,
-                        });
-                    }
-                }
-            }
-        }
-
-        suggestions
+    /// `OutputType Exe`, `Nullable enable`, ... for the .NET settings a project sets
+    fn build_settings(metadata: &ProjectMetadata) -> Vec<String> {
+        [
+            ("OutputType", &metadata.output_type),
+            ("Nullable", &metadata.nullable),
+            ("LangVersion", &metadata.lang_version),
+            ("ImplicitUsings", &metadata.implicit_usings),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| value.as_ref().map(|v| format!("{} {}", name, v)))
+        .collect()
     }
 
-    #[allow(dead_code)]
-    fn check_async_patterns(&self, project: &DotNetProject) -> Vec<Suggestion> {
-        let mut suggestions = Vec::new();
-
-        for file in &project.files {
-            for class in &file.classes {
-                for method in &class.methods {
-                    // Check for async void (should be async Task)
-                    if method.is_async && method.return_type == "void" {
-                        suggestions.push(Suggestion {
-                            severity: SeverityLevel::Warning,
-                            category: "async-patterns".to_string(),
-                            message: format!(
-                                "Method '{}' in class '{}' is async void. Use async Task instead for proper exception handling.",
-                                method.name, class.name
-                            ),
-                            file: Some(file.path.clone()),
-                            line: None,
-                        });
-                    }
+    /// One section per sub-project with its type, framework, packages and
+    /// the projects it references
+    fn push_projects(context: &mut String, root: &Path, projects: &[Project]) {
+        for project in projects {
+            let path = project.path.strip_prefix(root).unwrap_or(&project.path);
+            context.push_str(&format!("### {} (`{}`)\n", project.name, path.display()));
+            context.push_str(&format!("- Type: {}\n", project.project_type.as_str()));
+            if let Some(ref version) = project.version {
+                context.push_str(&format!("- Version: {}\n", version));
+            }
+            if let Some(tf) = Self::target_frameworks(&project.metadata) {
+                context.push_str(&format!("- Target Framework: {}\n", tf));
+            }
+            if let Some(ref sdk) = project.metadata.sdk {
+                context.push_str(&format!("- SDK: {}\n", sdk));
+            }
+            let settings = Self::build_settings(&project.metadata);
+            if !settings.is_empty() {
+                context.push_str(&format!("- Build: {}\n", settings.join(", ")));
+            }
+            let direct = project.direct_dependencies().count();
+            if direct > 0 {
+                let packages: Vec<String> = project
+                    .direct_dependencies()
+                    .take(10)
+                    .map(|d| format!("{} ({})", d.name, Self::version(d)))
+                    .collect();
+                let more = direct - packages.len();
+                context.push_str(&format!("- Dependencies: {}", packages.join(", ")));
+                if more > 0 {
+                    context.push_str(&format!(" and {} more", more));
                 }
+                context.push('\n');
+            }
+            if !project.references.is_empty() {
+                context.push_str(&format!(
+                    "- References: {}\n",
+                    project.references.join(", ")
+                ));
             }
+            context.push_str(&format!("- Files: {}\n\n", project.all_files().len()));
+            Self::push_projects(context, root, &project.subprojects);
         }
-
-        suggestions
     }
 
-    #[allow(dead_code)]
-    fn check_di_patterns(&self, _project: &DotNetProject) -> Vec<Suggestion> {
-        // Could check for:
-        // - Services that should be injected
-        // - Missing DI registration
-        // - Incorrect service lifetime
-
-        // For now, just a placeholder
-        vec![Suggestion {
-            severity: SeverityLevel::Info,
-            category: "dependency-injection".to_string(),
-            message: "Consider using dependency injection for data access and external services."
-                .to_string(),
-            file: None,
-            line: None,
-        }]
-    }
+    /// Name plus whatever the extractor recorded about its shape:
+    /// `Get(int id): Task<Order?>`, `Title: string?`, `Cart : ComponentBase`
+    fn signature(symbol: &Symbol) -> String {
+        let mut signature = symbol.name.clone();
+        let callable = matches!(symbol.kind, SymbolKind::Method | SymbolKind::Function);
 
-    /// Build a formatted context string for AI consumption (legacy .NET version)
-    #[allow(dead_code)]
-    pub fn build_context_string(&self, analysis: &crate::types::DotNetAnalysisResult) -> String {
-        let mut context = String::new();
-
-        context.push_str("# .NET Project Analysis\n\n");
-        context.push_str(&format!("**Project:** {}\n", analysis.project.name));
-        context.push_str(&format!(
-            "**Framework:** {}\n",
-            analysis.project.target_framework
-        ));
-        context.push_str(&format!(
-            "**Language:** C# {}\n\n",
-            analysis.project.language_version
-        ));
-
-        // Dependencies
-        context.push_str("## Dependencies\n\n");
-        for package in &analysis.project.packages {
-            context.push_str(&format!("- {} ({})\n", package.name, package.version));
+        if callable && (symbol.type_name.is_some() || !symbol.parameters.is_empty()) {
+            let parameters: Vec<String> = symbol
+                .parameters
+                .iter()
+                .map(|p| {
+                    let mut parameter = p.modifiers.clone();
+                    parameter.extend([p.param_type.clone(), p.name.clone()]);
+                    parameter.retain(|part| !part.is_empty());
+                    parameter.join(" ")
+                })
+                .collect();
+            signature.push_str(&format!("({})", parameters.join(", ")));
         }
-        context.push('\n');
-
-        // Statistics
-        context.push_str("## Project Statistics\n\n");
-        context.push_str(&format!(
-            "- Total Files: {}\n",
-            analysis.statistics.total_files
-        ));
-        context.push_str(&format!(
-            "- Total Classes: {}\n",
-            analysis.statistics.total_classes
-        ));
-        context.push_str(&format!(
-            "- Total Methods: {}\n",
-            analysis.statistics.total_methods
-        ));
-        context.push('\n');
-
-        // Relevant Patterns
-        if !analysis.patterns.is_empty() {
-            context.push_str("## Relevant Patterns\n\n");
-            for pattern in &analysis.patterns {
-                context.push_str(&format!("### {}\n", pattern.title));
-                context.push_str(&format!("**Category:** {}\n", pattern.category));
-                context.push_str(&format!("{}\n\n", pattern.description));
-                context.push_str("```csharp\n");
-                context.push_str(&pattern.code);
-                context.push_str("\n```\n\n");
-            }
+        if let Some(type_name) = &symbol.type_name {
+            signature.push_str(&format!(": {}", type_name));
         }
-
-        // Suggestions
-        if !analysis.suggestions.is_empty() {
-            context.push_str("## Suggestions\n\n");
-            for suggestion in &analysis.suggestions {
-                let icon = match suggestion.severity {
-                    SeverityLevel::Error => "❌",
-                    SeverityLevel::Warning => "⚠️",
-                    SeverityLevel::Info => "ℹ️",
-                };
-                context.push_str(&format!(
-                    "{} **{}**: {}\n",
-                    icon, suggestion.category, suggestion.message
-                ));
-            }
+        if !symbol.bases.is_empty() {
+            signature.push_str(&format!(" : {}", symbol.bases.join(", ")));
         }
 
-        context
+        signature
     }
 }
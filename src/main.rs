@@ -1,5 +1,5 @@
 use anyhow::Result;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, Layer};
 
 mod analyzer;
 mod config;
@@ -11,17 +11,21 @@ mod utils;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize tracing - ONLY to stderr, no ANSI colors for MCP compatibility
+    // Initialize tracing - ONLY to stderr, no ANSI colors for MCP compatibility.
+    // The env filter applies to stderr only; events forwarded to MCP clients
+    // are filtered by the level each client sets with `logging/setLevel`.
+    let log_forwarder = mcp::LogForwarder::default();
     tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "mcp_dotnet_context=error".into()),
-        )
         .with(
             tracing_subscriber::fmt::layer()
                 .with_ansi(false) // Disable ANSI color codes
-                .with_writer(std::io::stderr), // Force stderr
+                .with_writer(std::io::stderr) // Force stderr
+                .with_filter(
+                    tracing_subscriber::EnvFilter::try_from_default_env()
+                        .unwrap_or_else(|_| "mcp_dotnet_context=error".into()),
+                ),
         )
+        .with(log_forwarder.layer())
         .init();
 
     tracing::info!("🦀 MCP .NET Context Server v0.1.0 starting...");
@@ -31,7 +35,9 @@ async fn main() -> Result<()> {
     tracing::info!("✅ Configuration loaded");
 
     // Initialize MCP server
-    let server = mcp::Server::new(config).await?;
+    let server = mcp::Server::new(config)
+        .await?
+        .with_log_forwarder(log_forwarder);
     tracing::info!("🚀 Server initialized");
 
     // Start server (stdio transport for Claude Desktop)
@@ -0,0 +1,548 @@
+//! Streamable HTTP transport.
+//!
+//! Clients POST JSON-RPC messages to `/mcp` and receive the response as JSON,
+//! or as an SSE stream (progress notifications, then the response) when the
+//! request carries a progress token. A GET on the same endpoint opens an SSE
+//! stream for other server-initiated messages, and DELETE ends the session.
+//! Sessions are created on `initialize` and identified by the `Mcp-Session-Id`
+//! header, so several editors can share one long-lived server. A session with
+//! no open stream that sees no requests for a while expires, so clients that
+//! disconnect without a DELETE don't leave it behind.
+
+use anyhow::{Context, Result};
+use axum::extract::State;
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc};
+use tokio_stream::wrappers::{BroadcastStream, UnboundedReceiverStream};
+use tokio_stream::StreamExt;
+
+use super::Server;
+
+const SESSION_HEADER: &str = "mcp-session-id";
+const PROTOCOL_VERSION_HEADER: &str = "mcp-protocol-version";
+
+/// Early HTTP-level rejection (status plus a short plain-text reason)
+type Rejection = (StatusCode, &'static str);
+
+/// Buffered server messages per session before slow SSE readers start lagging
+const SESSION_CHANNEL_CAPACITY: usize = 64;
+
+/// How long a session without an open stream lives after its last request
+const SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// How often idle sessions are looked for
+const SESSION_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+type Sessions = Arc<Mutex<HashMap<String, Session>>>;
+
+#[derive(Clone)]
+struct HttpState {
+    server: Arc<Server>,
+    /// Active sessions and the channel feeding their SSE stream
+    sessions: Sessions,
+}
+
+struct Session {
+    sender: broadcast::Sender<String>,
+    /// Last request, or the moment its last SSE stream closed
+    last_seen: Instant,
+}
+
+impl Session {
+    fn new() -> Self {
+        Session {
+            sender: broadcast::channel(SESSION_CHANNEL_CAPACITY).0,
+            last_seen: Instant::now(),
+        }
+    }
+}
+
+/// Marks the session as seen when the SSE stream holding it is dropped,
+/// so the idle timeout counts from the client's disconnect
+struct StreamGuard {
+    sessions: Sessions,
+    session_id: String,
+}
+
+impl Drop for StreamGuard {
+    fn drop(&mut self) {
+        if let Some(session) = self.sessions.lock().unwrap().get_mut(&self.session_id) {
+            session.last_seen = Instant::now();
+        }
+    }
+}
+
+/// Serves the MCP endpoint on the configured bind address until shutdown.
+pub(super) async fn serve(server: Server) -> Result<()> {
+    let bind_address = server.config.server.bind_address.clone();
+    let listener = tokio::net::TcpListener::bind(&bind_address)
+        .await
+        .with_context(|| format!("Failed to bind HTTP transport to {}", bind_address))?;
+
+    eprintln!(
+        "MCP server starting on http transport at http://{}/mcp",
+        listener.local_addr()?
+    );
+
+    let server = Arc::new(server);
+    super::watch::spawn(server.clone());
+
+    axum::serve(listener, router(server)).await?;
+
+    eprintln!("MCP server shutting down");
+    Ok(())
+}
+
+fn router(server: Arc<Server>) -> Router {
+    let state = HttpState {
+        server,
+        sessions: Arc::new(Mutex::new(HashMap::new())),
+    };
+
+    let sweeper = state.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SESSION_SWEEP_INTERVAL);
+        loop {
+            interval.tick().await;
+            expire_idle_sessions(&sweeper, Instant::now(), SESSION_IDLE_TIMEOUT);
+        }
+    });
+
+    Router::new()
+        .route(
+            "/mcp",
+            post(handle_post).get(handle_get).delete(handle_delete),
+        )
+        .with_state(state)
+}
+
+async fn handle_post(State(state): State<HttpState>, headers: HeaderMap, body: String) -> Response {
+    if let Err(rejection) = check_origin(&headers) {
+        return rejection.into_response();
+    }
+
+    let message = serde_json::from_str::<serde_json::Value>(&body).ok();
+    let is_initialize = message
+        .as_ref()
+        .map(|m| m["method"] == "initialize")
+        .unwrap_or(false);
+    let wants_progress = message
+        .as_ref()
+        .map(|m| !m["params"]["_meta"]["progressToken"].is_null())
+        .unwrap_or(false);
+
+    // Every message except `initialize` must belong to an existing session;
+    // `initialize` is handled under the id it will be given on success
+    let session_id = if is_initialize {
+        uuid::Uuid::new_v4().to_string()
+    } else {
+        match session_from_headers(&state, &headers) {
+            Ok(session_id) => session_id,
+            Err(rejection) => return rejection.into_response(),
+        }
+    };
+
+    if !is_initialize {
+        if let Err(rejection) = check_protocol_version(&state, &session_id, &headers) {
+            return rejection.into_response();
+        }
+    }
+
+    // The session's stream exists before `initialize` is handled, so messages
+    // the server later sends outside of any request reach the client
+    if is_initialize {
+        state
+            .sessions
+            .lock()
+            .unwrap()
+            .insert(session_id.clone(), Session::new());
+    }
+
+    let (outbound, notifications) = mpsc::unbounded_channel::<String>();
+
+    // Requests asking for progress get an SSE response carrying the
+    // notifications followed by the final JSON-RPC response
+    if wants_progress && !is_initialize && accepts_event_stream(&headers) {
+        let server = state.server.clone();
+        tokio::spawn(async move {
+            if let Some(response) = server.handle_message(&body, &session_id, &outbound).await {
+                if let Ok(json) = serde_json::to_string(&response) {
+                    let _ = outbound.send(json);
+                }
+            }
+        });
+
+        let stream = UnboundedReceiverStream::new(notifications)
+            .map(|data| Ok::<_, Infallible>(Event::default().event("message").data(data)));
+        return Sse::new(stream).into_response();
+    }
+
+    // Otherwise notifications go to the session's GET stream, if one is open
+    let sender = state
+        .sessions
+        .lock()
+        .unwrap()
+        .get(&session_id)
+        .map(|session| session.sender.clone());
+    if let Some(sender) = sender {
+        let mut notifications = notifications;
+        tokio::spawn(async move {
+            while let Some(notification) = notifications.recv().await {
+                let _ = sender.send(notification);
+            }
+        });
+    }
+
+    // Batches are answered with a JSON array (they never carry `initialize`)
+    if message.as_ref().map(|m| m.is_array()).unwrap_or(false) {
+        return match state
+            .server
+            .handle_payload(&body, &session_id, &outbound)
+            .await
+        {
+            Some(json) => ([(header::CONTENT_TYPE, "application/json")], json).into_response(),
+            None => StatusCode::ACCEPTED.into_response(),
+        };
+    }
+
+    // Requests from different sessions run concurrently on the shared server
+    let response = state
+        .server
+        .handle_message(&body, &session_id, &outbound)
+        .await;
+
+    let Some(response) = response else {
+        // Notifications and client responses are acknowledged without a body
+        return StatusCode::ACCEPTED.into_response();
+    };
+
+    let new_session = if !is_initialize {
+        None
+    } else if response.error.is_none() {
+        Some(session_id)
+    } else {
+        state.sessions.lock().unwrap().remove(&session_id);
+        state.server.end_session(&session_id);
+        None
+    };
+
+    let mut http_response = Json(response).into_response();
+    if let Some(session_id) = new_session {
+        if let Ok(value) = HeaderValue::from_str(&session_id) {
+            http_response.headers_mut().insert(SESSION_HEADER, value);
+        }
+    }
+    http_response
+}
+
+async fn handle_get(State(state): State<HttpState>, headers: HeaderMap) -> Response {
+    if let Err(rejection) = check_origin(&headers) {
+        return rejection.into_response();
+    }
+
+    if !accepts_event_stream(&headers) {
+        return (
+            StatusCode::NOT_ACCEPTABLE,
+            "GET requires Accept: text/event-stream",
+        )
+            .into_response();
+    }
+
+    let (session_id, receiver) = match session_from_headers(&state, &headers) {
+        Ok(session_id) => match state.sessions.lock().unwrap().get(&session_id) {
+            Some(session) => (session_id, session.sender.subscribe()),
+            None => return StatusCode::NOT_FOUND.into_response(),
+        },
+        Err(rejection) => return rejection.into_response(),
+    };
+
+    // Lagged messages are dropped rather than closing the stream
+    let guard = StreamGuard {
+        sessions: state.sessions.clone(),
+        session_id,
+    };
+    let stream = BroadcastStream::new(receiver).filter_map(move |message| {
+        let _open = &guard;
+        message
+            .ok()
+            .map(|data| Ok::<_, Infallible>(Event::default().event("message").data(data)))
+    });
+
+    Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+async fn handle_delete(State(state): State<HttpState>, headers: HeaderMap) -> Response {
+    if let Err(rejection) = check_origin(&headers) {
+        return rejection.into_response();
+    }
+
+    match session_from_headers(&state, &headers) {
+        Ok(session_id) => {
+            state.sessions.lock().unwrap().remove(&session_id);
+            state.server.end_session(&session_id);
+            StatusCode::OK.into_response()
+        }
+        Err(rejection) => rejection.into_response(),
+    }
+}
+
+fn accepts_event_stream(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.contains("text/event-stream"))
+        .unwrap_or(false)
+}
+
+/// Returns the session id from the request headers if it names a live
+/// session, and marks that session as seen.
+fn session_from_headers(state: &HttpState, headers: &HeaderMap) -> Result<String, Rejection> {
+    let session_id = headers
+        .get(SESSION_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .ok_or((StatusCode::BAD_REQUEST, "Missing Mcp-Session-Id header"))?;
+
+    match state.sessions.lock().unwrap().get_mut(session_id) {
+        Some(session) => {
+            session.last_seen = Instant::now();
+            Ok(session_id.to_string())
+        }
+        None => Err((StatusCode::NOT_FOUND, "Unknown or expired session")),
+    }
+}
+
+/// Ends the sessions that have no open SSE stream and were last seen more
+/// than `max_idle` before `now`
+fn expire_idle_sessions(state: &HttpState, now: Instant, max_idle: Duration) {
+    let expired: Vec<String> = {
+        let mut sessions = state.sessions.lock().unwrap();
+        let expired: Vec<String> = sessions
+            .iter()
+            .filter(|(_, s)| {
+                s.sender.receiver_count() == 0
+                    && now.saturating_duration_since(s.last_seen) >= max_idle
+            })
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in &expired {
+            sessions.remove(id);
+        }
+        expired
+    };
+
+    for id in expired {
+        tracing::debug!("Session {} expired", id);
+        state.server.end_session(&id);
+    }
+}
+
+/// Checks the `MCP-Protocol-Version` header against the version negotiated
+/// for the session. Clients on 2024-11-05 don't send it, so it is optional.
+fn check_protocol_version(
+    state: &HttpState,
+    session_id: &str,
+    headers: &HeaderMap,
+) -> Result<(), Rejection> {
+    let Some(version) = headers
+        .get(PROTOCOL_VERSION_HEADER)
+        .and_then(|v| v.to_str().ok())
+    else {
+        return Ok(());
+    };
+
+    match state.server.protocol_version(session_id) {
+        Some(negotiated) if negotiated != version => Err((
+            StatusCode::BAD_REQUEST,
+            "MCP-Protocol-Version does not match the negotiated version",
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Rejects browser requests from non-local origins (DNS rebinding protection).
+fn check_origin(headers: &HeaderMap) -> Result<(), Rejection> {
+    let Some(origin) = headers.get(header::ORIGIN).and_then(|v| v.to_str().ok()) else {
+        return Ok(());
+    };
+
+    let host = origin
+        .split_once("://")
+        .map(|(_, rest)| rest)
+        .unwrap_or(origin);
+    let host = if host.starts_with('[') {
+        host.split_inclusive(']').next().unwrap_or(host)
+    } else {
+        host.split([':', '/']).next().unwrap_or(host)
+    };
+
+    if matches!(host, "localhost" | "127.0.0.1" | "[::1]") {
+        Ok(())
+    } else {
+        Err((StatusCode::FORBIDDEN, "Origin not allowed"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+
+    async fn start() -> std::net::SocketAddr {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.storage.base_path = dir.path().to_path_buf();
+        let server = Server::new(config).await.unwrap();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router(Arc::new(server))).await });
+        addr
+    }
+
+    async fn post(addr: std::net::SocketAddr, extra_headers: &str, body: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let request = format!(
+            "POST /mcp HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\n\
+             Content-Length: {}\r\nConnection: close\r\n{}\r\n{}",
+            addr,
+            body.len(),
+            extra_headers,
+            body
+        );
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    fn session_id(response: &str) -> String {
+        response
+            .lines()
+            .find_map(|l| {
+                l.to_lowercase()
+                    .starts_with("mcp-session-id:")
+                    .then(|| l.split_once(':').unwrap().1.trim().to_string())
+            })
+            .expect("missing session header")
+    }
+
+    #[tokio::test]
+    async fn test_session_lifecycle() {
+        let addr = start().await;
+
+        let init = post(
+            addr,
+            "",
+            r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{}}"#,
+        )
+        .await;
+        assert!(init.starts_with("HTTP/1.1 200"));
+        let session = session_id(&init);
+
+        let notification = post(
+            addr,
+            &format!("Mcp-Session-Id: {}\r\n", session),
+            r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+        )
+        .await;
+        assert!(notification.starts_with("HTTP/1.1 202"));
+
+        let list = post(
+            addr,
+            &format!(
+                "Mcp-Session-Id: {}\r\nMCP-Protocol-Version: 2025-06-18\r\n",
+                session
+            ),
+            r#"{"jsonrpc":"2.0","id":2,"method":"tools/list"}"#,
+        )
+        .await;
+        assert!(list.starts_with("HTTP/1.1 200"));
+        assert!(list.contains("analyze-project"));
+
+        let wrong_version = post(
+            addr,
+            &format!(
+                "Mcp-Session-Id: {}\r\nMCP-Protocol-Version: 2024-11-05\r\n",
+                session
+            ),
+            r#"{"jsonrpc":"2.0","id":3,"method":"tools/list"}"#,
+        )
+        .await;
+        assert!(wrong_version.starts_with("HTTP/1.1 400"));
+    }
+
+    #[tokio::test]
+    async fn test_idle_sessions_expire() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.storage.base_path = dir.path().to_path_buf();
+        let state = HttpState {
+            server: Arc::new(Server::new(config).await.unwrap()),
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+        };
+        for id in ["streaming", "recent", "idle"] {
+            state
+                .sessions
+                .lock()
+                .unwrap()
+                .insert(id.to_string(), Session::new());
+        }
+        // Checked from a clock one timeout ahead, where only `recent` was just seen
+        let later = Instant::now() + SESSION_IDLE_TIMEOUT;
+        let mut sessions = state.sessions.lock().unwrap();
+        sessions.get_mut("recent").unwrap().last_seen = later;
+        let stream = sessions["streaming"].sender.subscribe();
+        drop(sessions);
+
+        // An open stream keeps a session alive however long it is quiet
+        expire_idle_sessions(&state, later, SESSION_IDLE_TIMEOUT);
+        let mut alive: Vec<String> = state.sessions.lock().unwrap().keys().cloned().collect();
+        alive.sort();
+        assert_eq!(alive, vec!["recent", "streaming"]);
+
+        drop(stream);
+        expire_idle_sessions(&state, later + SESSION_IDLE_TIMEOUT, SESSION_IDLE_TIMEOUT);
+        assert!(state.sessions.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_missing_session_and_foreign_origin() {
+        let addr = start().await;
+
+        let missing = post(
+            addr,
+            "",
+            r#"{"jsonrpc":"2.0","id":1,"method":"tools/list"}"#,
+        )
+        .await;
+        assert!(missing.starts_with("HTTP/1.1 400"));
+
+        let unknown = post(
+            addr,
+            "Mcp-Session-Id: nope\r\n",
+            r#"{"jsonrpc":"2.0","id":1,"method":"tools/list"}"#,
+        )
+        .await;
+        assert!(unknown.starts_with("HTTP/1.1 404"));
+
+        let foreign = post(
+            addr,
+            "Origin: https://evil.example\r\n",
+            r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{}}"#,
+        )
+        .await;
+        assert!(foreign.starts_with("HTTP/1.1 403"));
+    }
+}
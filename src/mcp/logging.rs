@@ -0,0 +1,219 @@
+//! MCP logging capability.
+//!
+//! Clients pick a minimum level with `logging/setLevel`; from then on
+//! `tracing` events emitted by the analyzer, training and context modules and
+//! the pattern watcher are forwarded to that client as `notifications/message`. The forwarding layer
+//! is installed next to the stderr logger in `main`, so stderr output is
+//! unaffected.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::filter::filter_fn;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+use super::Outbound;
+
+/// Modules (the `tracing` target below the crate) whose events, and those of
+/// their submodules, reach clients
+const FORWARDED_MODULES: &[&str] = &["analyzer", "training", "context", "mcp::watch"];
+
+/// Syslog-style severities defined by the MCP logging spec, lowest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Notice,
+    Warning,
+    Error,
+    Critical,
+    Alert,
+    Emergency,
+}
+
+impl LogLevel {
+    pub fn parse(level: &str) -> Option<Self> {
+        match level {
+            "debug" => Some(LogLevel::Debug),
+            "info" => Some(LogLevel::Info),
+            "notice" => Some(LogLevel::Notice),
+            "warning" => Some(LogLevel::Warning),
+            "error" => Some(LogLevel::Error),
+            "critical" => Some(LogLevel::Critical),
+            "alert" => Some(LogLevel::Alert),
+            "emergency" => Some(LogLevel::Emergency),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogLevel::Debug => "debug",
+            LogLevel::Info => "info",
+            LogLevel::Notice => "notice",
+            LogLevel::Warning => "warning",
+            LogLevel::Error => "error",
+            LogLevel::Critical => "critical",
+            LogLevel::Alert => "alert",
+            LogLevel::Emergency => "emergency",
+        }
+    }
+
+    fn from_tracing(level: &Level) -> Self {
+        match *level {
+            Level::TRACE | Level::DEBUG => LogLevel::Debug,
+            Level::INFO => LogLevel::Info,
+            Level::WARN => LogLevel::Warning,
+            Level::ERROR => LogLevel::Error,
+        }
+    }
+}
+
+/// Routes `tracing` events to the sessions that asked for them.
+///
+/// Cloning is cheap; the server and the `tracing` layer share one registry.
+#[derive(Clone, Default)]
+pub struct LogForwarder {
+    /// Minimum level and message channel per session
+    sessions: Arc<Mutex<HashMap<String, (LogLevel, Outbound)>>>,
+}
+
+impl LogForwarder {
+    /// Returns the `tracing` layer that feeds this forwarder
+    pub fn layer<S>(&self) -> impl Layer<S>
+    where
+        S: Subscriber + for<'span> LookupSpan<'span>,
+    {
+        ForwardingLayer {
+            forwarder: self.clone(),
+        }
+        .with_filter(filter_fn(|metadata| is_forwarded(metadata.target())))
+    }
+
+    pub(super) fn set_level(&self, session: &str, level: LogLevel, outbound: Outbound) {
+        self.sessions
+            .lock()
+            .unwrap()
+            .insert(session.to_string(), (level, outbound));
+    }
+
+    pub(super) fn remove(&self, session: &str) {
+        self.sessions.lock().unwrap().remove(session);
+    }
+
+    fn forward(&self, level: LogLevel, logger: &str, data: &str) {
+        let mut sessions = self.sessions.lock().unwrap();
+        if sessions.is_empty() {
+            return;
+        }
+
+        let notification = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/message",
+            "params": {
+                "level": level.as_str(),
+                "logger": logger,
+                "data": data
+            }
+        })
+        .to_string();
+
+        // Sessions whose transport has gone away are dropped
+        sessions.retain(|_, (min_level, outbound)| {
+            level < *min_level || outbound.send(notification.clone()).is_ok()
+        });
+    }
+}
+
+fn is_forwarded(target: &str) -> bool {
+    let Some((_, path)) = target.split_once("::") else {
+        return false;
+    };
+    FORWARDED_MODULES.iter().any(|module| {
+        path.strip_prefix(module)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+    })
+}
+
+struct ForwardingLayer {
+    forwarder: LogForwarder,
+}
+
+impl<S: Subscriber> Layer<S> for ForwardingLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        let metadata = event.metadata();
+        self.forwarder.forward(
+            LogLevel::from_tracing(metadata.level()),
+            metadata.target(),
+            &visitor.text,
+        );
+    }
+}
+
+/// Renders an event as its message followed by `key=value` fields
+#[derive(Default)]
+struct MessageVisitor {
+    text: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let fields = std::mem::take(&mut self.text);
+            let _ = write!(self.text, "{:?}{}", value, fields);
+        } else {
+            let _ = write!(self.text, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_forwards_events_at_requested_level() {
+        let forwarder = LogForwarder::default();
+        let subscriber = tracing_subscriber::registry().with(forwarder.layer());
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let (outbound, mut rx) = mpsc::unbounded_channel();
+        forwarder.set_level("s", LogLevel::Warning, outbound);
+
+        tracing::info!(target: "mcp_context_rust::training", "below the level");
+        tracing::warn!(target: "other_crate::engine", "not a forwarded module");
+        tracing::warn!(target: "mcp_context_rust::mcp", "protocol chatter");
+        tracing::warn!(target: "mcp_context_rust::context", pattern = "di-service", "no match");
+
+        let message: serde_json::Value = serde_json::from_str(&rx.try_recv().unwrap()).unwrap();
+        assert_eq!(message["method"], "notifications/message");
+        assert_eq!(message["params"]["level"], "warning");
+        assert_eq!(message["params"]["logger"], "mcp_context_rust::context");
+        assert_eq!(message["params"]["data"], "no match pattern=\"di-service\"");
+        assert!(rx.try_recv().is_err());
+
+        tracing::error!(target: "mcp_context_rust::mcp::watch", "bad pattern file");
+        let message: serde_json::Value = serde_json::from_str(&rx.try_recv().unwrap()).unwrap();
+        assert_eq!(message["params"]["data"], "bad pattern file");
+
+        forwarder.remove("s");
+        tracing::error!(target: "mcp_context_rust::analyzer", "after removal");
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_level_ordering() {
+        assert!(LogLevel::Debug < LogLevel::Warning);
+        assert!(LogLevel::Emergency > LogLevel::Error);
+        assert_eq!(LogLevel::parse("notice"), Some(LogLevel::Notice));
+        assert_eq!(LogLevel::parse("verbose"), None);
+    }
+}
@@ -1,18 +1,72 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::{mpsc, RwLock};
 
-use crate::analyzer::GenericAnalyzer;
+use crate::analyzer::{AnalysisControl, AnalysisPhase, AnalysisProgress, GenericAnalyzer};
 use crate::config::Config;
 use crate::context::ContextBuilder;
-use crate::training::{SearchCriteria, TrainingManager};
-use crate::types::CodePattern;
+use crate::training::{PatternChanges, SearchCriteria, TrainingManager};
+use crate::types::{AnalysisResult, CodePattern};
+
+mod http;
+mod logging;
+mod schema;
+mod watch;
+
+pub use logging::LogForwarder;
+use logging::LogLevel;
+
+/// URI scheme for pattern resources: `pattern://<framework>/<id>`
+const PATTERN_URI_PREFIX: &str = "pattern://";
+/// URI scheme for project resources: `project://<name>/summary`
+const PROJECT_URI_PREFIX: &str = "project://";
+const PROJECT_URI_SUFFIX: &str = "/summary";
+
+/// Protocol revisions this server speaks, newest first
+const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+/// Minimum delay between two progress notifications of the same phase
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Channel carrying serialized server-to-client messages (notifications)
+/// emitted while a request is being handled
+type Outbound = mpsc::UnboundedSender<String>;
 
 /// MCP Server implementation
+///
+/// All handlers take `&self`, so one server can be shared behind an `Arc` and
+/// serve several requests concurrently.
 pub struct Server {
     config: Config,
-    training_manager: TrainingManager,
+    training_manager: RwLock<TrainingManager>,
+    /// Results of previous `analyze-project` calls, keyed by project name
+    analyzed_projects: RwLock<BTreeMap<String, AnalysisResult>>,
+    /// Requests currently being handled, keyed by session and request id,
+    /// so `notifications/cancelled` can reach them
+    in_flight: Mutex<HashMap<String, AnalysisControl>>,
+    /// Lifecycle state of each client session (stdio uses the empty session id)
+    sessions: Mutex<HashMap<String, SessionState>>,
+    /// Delivers `tracing` events to sessions that called `logging/setLevel`
+    log_forwarder: LogForwarder,
+}
+
+/// What a client agreed to during `initialize`
+#[derive(Debug, Clone)]
+struct SessionState {
+    /// Negotiated protocol revision
+    protocol_version: String,
+    /// Set once the client sends `notifications/initialized`
+    initialized: bool,
+    /// Channel for messages sent outside of any request (list changes)
+    outbound: Outbound,
+    /// Resource URIs the client subscribed to with `resources/subscribe`
+    subscriptions: HashSet<String>,
 }
 
 /// JSON-RPC Request structure
@@ -46,6 +100,77 @@ struct JsonRpcError {
     data: Option<serde_json::Value>,
 }
 
+// JSON-RPC 2.0 error codes, plus the MCP-specific "resource not found"
+const PARSE_ERROR: i32 = -32700;
+const INVALID_REQUEST: i32 = -32600;
+const METHOD_NOT_FOUND: i32 = -32601;
+const INVALID_PARAMS: i32 = -32602;
+const INTERNAL_ERROR: i32 = -32603;
+const RESOURCE_NOT_FOUND: i32 = -32002;
+
+/// Protocol-level failure of a request handler.
+///
+/// Only malformed or unanswerable requests end up here; a tool that runs
+/// and fails reports it in its result with `isError: true` instead.
+#[derive(Debug)]
+struct RpcError {
+    code: i32,
+    message: String,
+}
+
+impl RpcError {
+    fn method_not_found(method: &str) -> Self {
+        Self {
+            code: METHOD_NOT_FOUND,
+            message: format!("Method not found: {}", method),
+        }
+    }
+
+    fn invalid_request(message: impl Into<String>) -> Self {
+        Self {
+            code: INVALID_REQUEST,
+            message: message.into(),
+        }
+    }
+
+    fn invalid_params(message: impl Into<String>) -> Self {
+        Self {
+            code: INVALID_PARAMS,
+            message: message.into(),
+        }
+    }
+
+    fn resource_not_found(uri: &str) -> Self {
+        Self {
+            code: RESOURCE_NOT_FOUND,
+            message: format!("Resource not found: {}", uri),
+        }
+    }
+
+    fn internal(message: impl Into<String>) -> Self {
+        Self {
+            code: INTERNAL_ERROR,
+            message: message.into(),
+        }
+    }
+}
+
+impl JsonRpcResponse {
+    fn error(id: Option<serde_json::Value>, code: i32, message: String) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            // Errors for requests whose id could not be determined carry `"id": null`
+            id: Some(id.unwrap_or(serde_json::Value::Null)),
+            result: None,
+            error: Some(JsonRpcError {
+                code,
+                message,
+                data: None,
+            }),
+        }
+    }
+}
+
 impl Server {
     pub async fn new(config: Config) -> Result<Self> {
         // Initialize training manager
@@ -73,22 +198,65 @@ impl Server {
 
         Ok(Self {
             config,
-            training_manager,
+            training_manager: RwLock::new(training_manager),
+            analyzed_projects: RwLock::new(BTreeMap::new()),
+            in_flight: Mutex::new(HashMap::new()),
+            sessions: Mutex::new(HashMap::new()),
+            log_forwarder: LogForwarder::default(),
         })
     }
 
-    pub async fn run(mut self) -> Result<()> {
+    /// Uses a forwarder whose `tracing` layer has been installed, so
+    /// `logging/setLevel` actually delivers log messages to the client
+    pub fn with_log_forwarder(mut self, log_forwarder: LogForwarder) -> Self {
+        self.log_forwarder = log_forwarder;
+        self
+    }
+
+    pub async fn run(self) -> Result<()> {
+        match self.config.server.transport.as_str() {
+            "stdio" => self.run_stdio().await,
+            "http" => http::serve(self).await,
+            other => anyhow::bail!(
+                "Unsupported transport '{}'. Use \"stdio\" or \"http\".",
+                other
+            ),
+        }
+    }
+
+    async fn run_stdio(self) -> Result<()> {
         eprintln!("MCP server starting on stdio transport");
 
+        let server = Arc::new(self);
+        watch::spawn(server.clone());
         let stdin = tokio::io::stdin();
         let mut reader = BufReader::new(stdin);
-        let mut stdout = tokio::io::stdout();
 
         eprintln!("Waiting for requests...");
 
-        // Track if client uses Content-Length framing
+        // Track if client uses Content-Length framing (shared with the writer task)
         let mut use_framing = false;
 
+        // Request handlers still running (finished ones are reaped as we go)
+        let mut handlers = tokio::task::JoinSet::new();
+        let framing = Arc::new(AtomicBool::new(false));
+
+        // A single writer task owns stdout so concurrent handlers never interleave output
+        let (outbound, mut outbound_rx) = mpsc::unbounded_channel::<String>();
+        let writer_framing = framing.clone();
+        let writer = tokio::spawn(async move {
+            let mut stdout = tokio::io::stdout();
+            while let Some(message) = outbound_rx.recv().await {
+                let use_framing = writer_framing.load(Ordering::Relaxed);
+                eprintln!("Sending response (framing={})", use_framing);
+                // Send response matching client's framing style
+                if let Err(e) = Self::write_mcp_message(&mut stdout, &message, use_framing).await {
+                    eprintln!("Error writing response: {}", e);
+                    break;
+                }
+            }
+        });
+
         // Process requests
         loop {
             // Read message (auto-detect framing on first message)
@@ -97,64 +265,31 @@ impl Server {
                     if json_body.is_empty() {
                         continue;
                     }
+                    framing.store(use_framing, Ordering::Relaxed);
                     eprintln!(
                         "Received request: {}",
                         &json_body[..json_body.len().min(100)]
                     );
 
-                    match serde_json::from_str::<JsonRpcRequest>(&json_body) {
-                        Ok(request) => {
-                            // Check if this is a notification (no id field)
-                            if request.id.is_none() && request.method.starts_with("notifications/")
-                            {
-                                eprintln!("Received notification: {}, ignoring", request.method);
-                                continue;
-                            }
+                    // Notifications are handled inline so they keep their order
+                    // relative to later requests (e.g. `notifications/initialized`)
+                    if Self::is_notification(&json_body) {
+                        server.handle_payload(&json_body, "", &outbound).await;
+                        continue;
+                    }
 
-                            let response = self.handle_request(request).await;
-                            match serde_json::to_string(&response) {
-                                Ok(response_str) => {
-                                    eprintln!("Sending response (framing={})", use_framing);
-                                    // Send response matching client's framing style
-                                    if let Err(e) = Self::write_mcp_message(
-                                        &mut stdout,
-                                        &response_str,
-                                        use_framing,
-                                    )
-                                    .await
-                                    {
-                                        eprintln!("Error writing response: {}", e);
-                                        break;
-                                    }
-                                    eprintln!(
-                                        "Response sent successfully, waiting for next request..."
-                                    );
-                                }
-                                Err(e) => {
-                                    eprintln!("Error serializing response: {}", e);
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            eprintln!("Failed to parse request: {}", e);
-                            let error_response = JsonRpcResponse {
-                                jsonrpc: "2.0".to_string(),
-                                id: None,
-                                result: None,
-                                error: Some(JsonRpcError {
-                                    code: -32700,
-                                    message: "Parse error".to_string(),
-                                    data: Some(serde_json::json!({ "error": e.to_string() })),
-                                }),
-                            };
-
-                            if let Ok(error_str) = serde_json::to_string(&error_response) {
-                                let _ =
-                                    Self::write_mcp_message(&mut stdout, &error_str, use_framing)
-                                        .await;
-                            }
+                    // Each request is handled on its own task so a slow analysis
+                    // doesn't block other calls or cancellation notifications
+                    let server = server.clone();
+                    let outbound = outbound.clone();
+                    handlers.spawn(async move {
+                        if let Some(response) =
+                            server.handle_payload(&json_body, "", &outbound).await
+                        {
+                            let _ = outbound.send(response);
                         }
-                    }
+                    });
+                    while handlers.try_join_next().is_some() {}
                 }
                 Ok(None) => {
                     eprintln!("stdin closed (EOF)");
@@ -167,10 +302,283 @@ impl Server {
             }
         }
 
+        // Let in-flight handlers finish and flush their responses; the session
+        // holds a sender for unsolicited messages, so it must go last
+        while handlers.join_next().await.is_some() {}
+        server.end_session("");
+        drop(outbound);
+        let _ = writer.await;
+
         eprintln!("MCP server shutting down");
         Ok(())
     }
 
+    /// Returns true if the raw message is a single notification (no `id`)
+    fn is_notification(json_body: &str) -> bool {
+        serde_json::from_str::<serde_json::Value>(json_body)
+            .map(|message| message.is_object() && message.get("id").is_none())
+            .unwrap_or(false)
+    }
+
+    /// Handles one raw JSON-RPC message, independent of the transport.
+    /// Returns `None` for notifications, which never get a response, and for
+    /// requests the client cancelled while they were running.
+    ///
+    /// `session` scopes request ids so that clients sharing one server
+    /// cannot cancel each other's requests. Notifications produced while the
+    /// request runs (e.g. progress) are sent through `outbound`.
+    async fn handle_message(
+        &self,
+        json_body: &str,
+        session: &str,
+        outbound: &Outbound,
+    ) -> Option<JsonRpcResponse> {
+        let message = match serde_json::from_str::<serde_json::Value>(json_body) {
+            Ok(message) => message,
+            Err(e) => {
+                eprintln!("Failed to parse request: {}", e);
+                return Some(JsonRpcResponse::error(
+                    None,
+                    PARSE_ERROR,
+                    format!("Parse error: {}", e),
+                ));
+            }
+        };
+        self.handle_value(message, session, outbound).await
+    }
+
+    /// Handles a raw payload that is either a single message or a JSON-RPC
+    /// batch array, and returns the serialized response (if any).
+    ///
+    /// Batch entries are handled in order; notifications produce no entry, and
+    /// a batch of only notifications produces no response at all.
+    async fn handle_payload(
+        &self,
+        json_body: &str,
+        session: &str,
+        outbound: &Outbound,
+    ) -> Option<String> {
+        let batch = match serde_json::from_str::<serde_json::Value>(json_body) {
+            Ok(serde_json::Value::Array(batch)) => batch,
+            _ => {
+                let response = self.handle_message(json_body, session, outbound).await?;
+                return serde_json::to_string(&response).ok();
+            }
+        };
+
+        if batch.is_empty() {
+            let response = JsonRpcResponse::error(None, INVALID_REQUEST, "Empty batch".to_string());
+            return serde_json::to_string(&response).ok();
+        }
+
+        let mut responses = Vec::new();
+        for message in batch {
+            if let Some(response) = self.handle_value(message, session, outbound).await {
+                responses.push(response);
+            }
+        }
+
+        if responses.is_empty() {
+            return None;
+        }
+        serde_json::to_string(&responses).ok()
+    }
+
+    /// Handles one parsed JSON-RPC message (see `handle_message`)
+    async fn handle_value(
+        &self,
+        message: serde_json::Value,
+        session: &str,
+        outbound: &Outbound,
+    ) -> Option<JsonRpcResponse> {
+        let id = message.get("id").cloned();
+        match serde_json::from_value::<JsonRpcRequest>(message) {
+            Ok(request) => {
+                // A message without an id is a notification and never gets a
+                // response, whatever its method
+                if request.id.is_none() {
+                    if request.method.starts_with("notifications/") {
+                        self.handle_notification(&request, session);
+                    } else if let Err(error) = self.check_lifecycle(&request.method, session) {
+                        eprintln!(
+                            "Ignoring {} notification: {}",
+                            request.method, error.message
+                        );
+                    } else {
+                        let control = AnalysisControl::new();
+                        self.handle_request(request, session, outbound, &control)
+                            .await;
+                    }
+                    return None;
+                }
+
+                if let Err(error) = self.check_lifecycle(&request.method, session) {
+                    return Some(JsonRpcResponse::error(
+                        request.id,
+                        error.code,
+                        error.message,
+                    ));
+                }
+
+                let mut control = AnalysisControl::new();
+                let progress_token = request
+                    .params
+                    .as_ref()
+                    .and_then(|p| p.get("_meta"))
+                    .and_then(|m| m.get("progressToken"))
+                    .cloned();
+                if let Some(token) = progress_token {
+                    control =
+                        control.with_progress(Self::progress_notifier(token, outbound.clone()));
+                }
+
+                let key = request.id.as_ref().map(|id| Self::request_key(session, id));
+                if let Some(ref key) = key {
+                    self.in_flight
+                        .lock()
+                        .unwrap()
+                        .insert(key.clone(), control.clone());
+                }
+
+                let response = self
+                    .handle_request(request, session, outbound, &control)
+                    .await;
+
+                if let Some(ref key) = key {
+                    self.in_flight.lock().unwrap().remove(key);
+                }
+
+                if control.is_cancelled() {
+                    eprintln!("Request cancelled by client, dropping response");
+                    return None;
+                }
+                Some(response)
+            }
+            Err(e) => {
+                eprintln!("Invalid request: {}", e);
+                Some(JsonRpcResponse::error(
+                    id,
+                    INVALID_REQUEST,
+                    format!("Invalid request: {}", e),
+                ))
+            }
+        }
+    }
+
+    fn handle_notification(&self, notification: &JsonRpcRequest, session: &str) {
+        match notification.method.as_str() {
+            "notifications/initialized" => match self.sessions.lock().unwrap().get_mut(session) {
+                Some(state) => state.initialized = true,
+                None => eprintln!("Received notifications/initialized before initialize"),
+            },
+            "notifications/cancelled" => {
+                let Some(request_id) = notification
+                    .params
+                    .as_ref()
+                    .and_then(|p| p.get("requestId"))
+                else {
+                    return;
+                };
+
+                let key = Self::request_key(session, request_id);
+                if let Some(control) = self.in_flight.lock().unwrap().get(&key) {
+                    eprintln!("Cancelling request {}", request_id);
+                    control.cancel();
+                }
+            }
+            _ => {
+                eprintln!("Received notification: {}, ignoring", notification.method);
+            }
+        }
+    }
+
+    /// Rejects requests other than `initialize` and `ping` until the session
+    /// has completed the initialization handshake
+    fn check_lifecycle(&self, method: &str, session: &str) -> Result<(), RpcError> {
+        if matches!(method, "initialize" | "ping") {
+            return Ok(());
+        }
+
+        match self.sessions.lock().unwrap().get(session) {
+            Some(state) if state.initialized => Ok(()),
+            Some(_) => Err(RpcError::invalid_request(
+                "Server not initialized: waiting for notifications/initialized",
+            )),
+            None => Err(RpcError::invalid_request(
+                "Server not initialized: send initialize first",
+            )),
+        }
+    }
+
+    /// Protocol revision negotiated by a session, if it has initialized
+    fn protocol_version(&self, session: &str) -> Option<String> {
+        self.sessions
+            .lock()
+            .unwrap()
+            .get(session)
+            .map(|state| state.protocol_version.clone())
+    }
+
+    /// Forgets everything about a session once its client is gone
+    fn end_session(&self, session: &str) {
+        self.sessions.lock().unwrap().remove(session);
+        self.log_forwarder.remove(session);
+    }
+
+    fn request_key(session: &str, id: &serde_json::Value) -> String {
+        format!("{}:{}", session, id)
+    }
+
+    /// Builds a progress callback that turns analyzer updates into
+    /// `notifications/progress` for the given token.
+    ///
+    /// Updates are throttled per phase, and `progress` counts discovered plus
+    /// parsed files so it only ever increases across phases.
+    fn progress_notifier(
+        token: serde_json::Value,
+        outbound: Outbound,
+    ) -> impl Fn(&AnalysisProgress) + Send + Sync + 'static {
+        let last_sent: Mutex<Option<(Instant, AnalysisPhase, usize)>> = Mutex::new(None);
+
+        move |progress| {
+            let value = progress.files_seen + progress.files_parsed;
+
+            let mut last = last_sent.lock().unwrap();
+            if let Some((sent_at, phase, sent_value)) = *last {
+                if value <= sent_value {
+                    return;
+                }
+                let throttled = phase == progress.phase && sent_at.elapsed() < PROGRESS_INTERVAL;
+                if throttled && progress.phase != AnalysisPhase::Done {
+                    return;
+                }
+            }
+            *last = Some((Instant::now(), progress.phase, value));
+
+            let mut params = serde_json::json!({
+                "progressToken": token,
+                "progress": value,
+                "message": format!(
+                    "{}: {} files found, {} parsed",
+                    progress.phase.as_str(),
+                    progress.files_seen,
+                    progress.files_parsed
+                )
+            });
+            // The total is only known once discovery has finished
+            if matches!(progress.phase, AnalysisPhase::Parsing | AnalysisPhase::Done) {
+                params["total"] = serde_json::json!(progress.files_seen * 2);
+            }
+
+            let notification = serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "notifications/progress",
+                "params": params
+            });
+            let _ = outbound.send(notification.to_string());
+        }
+    }
+
     /// Reads a single MCP message from stdin.
     /// Auto-detects framing style (Content-Length headers vs newline-delimited JSON).
     /// Sets `use_framing` to true if Content-Length headers are detected.
@@ -222,9 +630,9 @@ impl Server {
                 .map_err(|e| anyhow::anyhow!("Invalid UTF-8 in message body: {}", e))?;
 
             Ok(Some(json_body))
-        } else if trimmed.starts_with('{') {
+        } else if trimmed.starts_with('{') || trimmed.starts_with('[') {
             // Legacy: newline-delimited JSON (no Content-Length header)
-            // Claude Desktop uses this mode
+            // Claude Desktop uses this mode. `[` starts a JSON-RPC batch.
             Ok(Some(trimmed.to_string()))
         } else if trimmed.is_empty() {
             // Empty line, continue reading
@@ -287,16 +695,32 @@ impl Server {
         Ok(())
     }
 
-    async fn handle_request(&mut self, request: JsonRpcRequest) -> JsonRpcResponse {
+    async fn handle_request(
+        &self,
+        request: JsonRpcRequest,
+        session: &str,
+        outbound: &Outbound,
+        control: &AnalysisControl,
+    ) -> JsonRpcResponse {
         tracing::info!("Handling method: {}", request.method);
 
         let result = match request.method.as_str() {
-            "initialize" => self.handle_initialize().await,
+            "initialize" => self.handle_initialize(request.params.as_ref(), session, outbound),
+            "ping" => Ok(serde_json::json!({})),
+            "logging/setLevel" => self.handle_set_level(request.params.as_ref(), session, outbound),
             "tools/list" => self.handle_tools_list().await,
-            "tools/call" => self.handle_tool_call(request.params).await,
+            "tools/call" => self.handle_tool_call(request.params, control).await,
             "prompts/list" => self.handle_prompts_list().await,
+            "prompts/get" => self.handle_prompts_get(request.params, control).await,
             "resources/list" => self.handle_resources_list().await,
-            _ => Err(format!("Unknown method: {}", request.method)),
+            "resources/read" => self.handle_resources_read(request.params).await,
+            "resources/subscribe" => {
+                self.handle_resources_subscribe(request.params.as_ref(), session, true)
+            }
+            "resources/unsubscribe" => {
+                self.handle_resources_subscribe(request.params.as_ref(), session, false)
+            }
+            method => Err(RpcError::method_not_found(method)),
         };
 
         match result {
@@ -306,33 +730,89 @@ impl Server {
                 result: Some(value),
                 error: None,
             },
-            Err(error_msg) => JsonRpcResponse {
-                jsonrpc: "2.0".to_string(),
-                id: request.id,
-                result: None,
-                error: Some(JsonRpcError {
-                    code: -32603,
-                    message: error_msg,
-                    data: None,
-                }),
-            },
+            Err(error) => JsonRpcResponse::error(request.id, error.code, error.message),
         }
     }
 
-    async fn handle_initialize(&self) -> Result<serde_json::Value, String> {
+    fn handle_initialize(
+        &self,
+        params: Option<&serde_json::Value>,
+        session: &str,
+        outbound: &Outbound,
+    ) -> Result<serde_json::Value, RpcError> {
+        let requested = params
+            .and_then(|p| p["protocolVersion"].as_str())
+            .unwrap_or(SUPPORTED_PROTOCOL_VERSIONS[0]);
+        let protocol_version = Self::negotiate_protocol_version(requested);
+
+        eprintln!(
+            "Client requested protocol {}, using {}",
+            requested, protocol_version
+        );
+
+        // (Re-)initializing restarts the handshake for this session
+        self.sessions.lock().unwrap().insert(
+            session.to_string(),
+            SessionState {
+                protocol_version: protocol_version.to_string(),
+                initialized: false,
+                outbound: outbound.clone(),
+                subscriptions: HashSet::new(),
+            },
+        );
+
         Ok(serde_json::json!({
-            "protocolVersion": "2024-11-05",
+            "protocolVersion": protocol_version,
             "serverInfo": {
                 "name": self.config.server.name,
                 "version": self.config.server.version,
             },
-            "capabilities": {
-                "tools": {}
-            }
+            "capabilities": Self::capabilities()
         }))
     }
 
-    async fn handle_tools_list(&self) -> Result<serde_json::Value, String> {
+    /// Picks the client's revision if supported, otherwise our newest one
+    /// (the client then decides whether it can proceed).
+    fn negotiate_protocol_version(requested: &str) -> &'static str {
+        SUPPORTED_PROTOCOL_VERSIONS
+            .iter()
+            .find(|v| **v == requested)
+            .copied()
+            .unwrap_or(SUPPORTED_PROTOCOL_VERSIONS[0])
+    }
+
+    /// Capabilities advertised in `initialize`. Only features the server
+    /// actually implements are listed; tools and prompts are fixed, so their
+    /// `listChanged` stays off.
+    fn capabilities() -> serde_json::Value {
+        serde_json::json!({
+            "tools": { "listChanged": false },
+            "resources": { "subscribe": true, "listChanged": true },
+            "prompts": { "listChanged": false },
+            "logging": {}
+        })
+    }
+
+    fn handle_set_level(
+        &self,
+        params: Option<&serde_json::Value>,
+        session: &str,
+        outbound: &Outbound,
+    ) -> Result<serde_json::Value, RpcError> {
+        let level = params
+            .and_then(|p| p["level"].as_str())
+            .ok_or_else(|| RpcError::invalid_params("Missing level"))?;
+        let level = LogLevel::parse(level)
+            .ok_or_else(|| RpcError::invalid_params(format!("Invalid log level: {}", level)))?;
+
+        eprintln!("Forwarding logs at level {} and above", level.as_str());
+        self.log_forwarder
+            .set_level(session, level, outbound.clone());
+
+        Ok(serde_json::json!({}))
+    }
+
+    async fn handle_tools_list(&self) -> Result<serde_json::Value, RpcError> {
         Ok(serde_json::json!({
             "tools": [
                 {
@@ -347,7 +827,8 @@ impl Server {
                             }
                         },
                         "required": ["project_path"]
-                    }
+                    },
+                    "outputSchema": schema::analysis_result()
                 },
                 {
                     "name": "get-patterns",
@@ -365,7 +846,8 @@ impl Server {
                             }
                         },
                         "required": ["framework"]
-                    }
+                    },
+                    "outputSchema": schema::pattern_list()
                 },
                 {
                     "name": "search-patterns",
@@ -395,7 +877,8 @@ impl Server {
                                 "description": "Minimum relevance score (0.0 - 1.0)"
                             }
                         }
-                    }
+                    },
+                    "outputSchema": schema::search_results()
                 },
                 {
                     "name": "train-pattern",
@@ -438,7 +921,8 @@ impl Server {
                             }
                         },
                         "required": ["id", "category", "framework", "title", "description", "code"]
-                    }
+                    },
+                    "outputSchema": schema::train_result()
                 },
                 {
                     "name": "get-statistics",
@@ -446,7 +930,8 @@ impl Server {
                     "inputSchema": {
                         "type": "object",
                         "properties": {}
-                    }
+                    },
+                    "outputSchema": schema::statistics()
                 },
                 {
                     "name": "get-help",
@@ -454,91 +939,136 @@ impl Server {
                     "inputSchema": {
                         "type": "object",
                         "properties": {}
-                    }
+                    },
+                    "outputSchema": schema::help()
                 }
             ]
         }))
     }
 
     async fn handle_tool_call(
-        &mut self,
+        &self,
         params: Option<serde_json::Value>,
-    ) -> Result<serde_json::Value, String> {
-        let params = params.ok_or("Missing params")?;
-        let tool_name = params["name"].as_str().ok_or("Missing tool name")?;
+        control: &AnalysisControl,
+    ) -> Result<serde_json::Value, RpcError> {
+        let params = params.ok_or_else(|| RpcError::invalid_params("Missing params"))?;
+        let tool_name = params["name"]
+            .as_str()
+            .ok_or_else(|| RpcError::invalid_params("Missing tool name"))?;
         let arguments = &params["arguments"];
 
         tracing::info!("Calling tool: {}", tool_name);
 
-        match tool_name {
-            "analyze-project" => self.tool_analyze_project(arguments).await,
+        let result = match tool_name {
+            "analyze-project" => self.tool_analyze_project(arguments, control).await,
             "get-patterns" => self.tool_get_patterns(arguments).await,
             "search-patterns" => self.tool_search_patterns(arguments).await,
             "train-pattern" => self.tool_train_pattern(arguments).await,
             "get-statistics" => self.tool_get_statistics().await,
             "get-help" => self.tool_get_help().await,
-            _ => Err(format!("Unknown tool: {}", tool_name)),
-        }
+            _ => {
+                return Err(RpcError::invalid_params(format!(
+                    "Unknown tool: {}",
+                    tool_name
+                )))
+            }
+        };
+
+        // The tool ran: bad arguments or a failed analysis are reported to
+        // the model as an error result rather than as a protocol error
+        Ok(result.unwrap_or_else(|message| {
+            eprintln!("Tool {} failed: {}", tool_name, message);
+            Self::tool_error(message)
+        }))
     }
 
     // Tool: analyze-project
     async fn tool_analyze_project(
         &self,
         args: &serde_json::Value,
+        control: &AnalysisControl,
     ) -> Result<serde_json::Value, String> {
         let project_path = args["project_path"]
             .as_str()
             .ok_or("Missing project_path")?;
 
+        let analysis = self
+            .analyze_project_path(project_path, control)
+            .await
+            .map_err(|e| e.message)?;
+
+        // Generate formatted context
+        let context_string = ContextBuilder::new().build_generic_context_string(&analysis);
+        let structured = serde_json::to_value(&analysis)
+            .map_err(|e| format!("Failed to serialize analysis: {}", e))?;
+
+        // Remember the analysis so it can be read back as a `project://` resource
+        let uri = Self::project_uri(&analysis.project.name);
+        let replaced = self
+            .analyzed_projects
+            .write()
+            .await
+            .insert(analysis.project.name.clone(), analysis)
+            .is_some();
+        if replaced {
+            self.notify_resources_changed(false, &[uri]);
+        } else {
+            self.notify_resources_changed(true, &[]);
+        }
+
+        Ok(Self::tool_result(context_string, structured))
+    }
+
+    /// Validates a project path and runs the generic analysis with pattern lookup.
+    /// Shared by the `analyze-project` tool and the `explain-project` prompt.
+    async fn analyze_project_path(
+        &self,
+        project_path: &str,
+        control: &AnalysisControl,
+    ) -> Result<AnalysisResult, RpcError> {
         eprintln!("DEBUG: Analyzing project path: {}", project_path);
 
         // Validate path exists
         let path = PathBuf::from(project_path);
         if !path.exists() {
-            return Err(format!(
+            return Err(RpcError::invalid_params(format!(
                 "Project path does not exist: '{}'. Please provide an absolute path to a project directory.",
                 project_path
-            ));
+            )));
         }
 
         if !path.is_dir() {
-            return Err(format!(
+            return Err(RpcError::invalid_params(format!(
                 "Path is not a directory: '{}'. Please provide a directory path, not a file path.",
                 project_path
-            ));
+            )));
         }
 
         eprintln!("DEBUG: Path exists and is directory, detecting project type...");
 
-        // Use the new generic analyzer
-        let project = GenericAnalyzer::analyze(path.as_path())
-            .await
-            .map_err(|e| {
-                eprintln!("DEBUG: Analysis failed with error: {}", e);
-                format!("Failed to analyze project: {}. Make sure the directory contains a valid project file (Cargo.toml, package.json, .csproj, pyproject.toml, go.mod, or pom.xml).", e)
-            })?;
+        // The walk and parsing block, so they run off the async workers and
+        // other requests keep being answered meanwhile
+        let analysis_control = control.clone();
+        let project = tokio::task::spawn_blocking(move || {
+            GenericAnalyzer::analyze_with(&path, &analysis_control)
+        })
+        .await
+        .map_err(|e| RpcError::internal(format!("Analysis task failed: {}", e)))?
+        .map_err(|e| {
+            eprintln!("DEBUG: Analysis failed with error: {}", e);
+            RpcError::invalid_params(format!("Failed to analyze project: {}. Make sure the directory contains a valid project file (Cargo.toml, package.json, .csproj, pyproject.toml, go.mod, or pom.xml).", e))
+        })?;
 
         eprintln!("DEBUG: Detected project type: {:?}", project.project_type);
 
         // Build context with patterns
         let context_builder =
-            ContextBuilder::new().with_training_manager(self.training_manager.clone());
+            ContextBuilder::new().with_training_manager(self.training_manager.read().await.clone());
 
-        let analysis = context_builder
+        context_builder
             .build_generic_analysis(project)
             .await
-            .map_err(|e| format!("Failed to build analysis: {}", e))?;
-
-        // Generate formatted context
-        let context_string = context_builder.build_generic_context_string(&analysis);
-
-        Ok(serde_json::json!({
-            "content": [{
-                "type": "text",
-                "text": context_string
-            }],
-            "isError": false
-        }))
+            .map_err(|e| RpcError::internal(format!("Failed to build analysis: {}", e)))
     }
 
     // Tool: get-patterns
@@ -549,9 +1079,9 @@ impl Server {
         let framework = args["framework"].as_str().ok_or("Missing framework")?;
         let category = args["category"].as_str();
 
+        let training_manager = self.training_manager.read().await;
         let patterns = if let Some(cat) = category {
-            self.training_manager
-                .search_by_framework_and_category(framework, cat)
+            training_manager.search_by_framework_and_category(framework, cat)
         } else {
             let criteria = SearchCriteria {
                 query: None,
@@ -560,13 +1090,19 @@ impl Server {
                 tags: vec![],
                 min_score: 0.0,
             };
-            self.training_manager
+            training_manager
                 .search_patterns(&criteria)
                 .into_iter()
                 .map(|(p, _)| p)
                 .collect()
         };
 
+        let structured = serde_json::json!({
+            "framework": framework,
+            "category": category,
+            "patterns": patterns
+        });
+
         let mut output = String::new();
         output.push_str(&format!("# Patterns for {}\n\n", framework));
 
@@ -591,13 +1127,7 @@ impl Server {
             }
         }
 
-        Ok(serde_json::json!({
-            "content": [{
-                "type": "text",
-                "text": output
-            }],
-            "isError": false
-        }))
+        Ok(Self::tool_result(output, structured))
     }
 
     // Tool: search-patterns
@@ -620,7 +1150,16 @@ impl Server {
             min_score: args["min_score"].as_f64().unwrap_or(0.0) as f32,
         };
 
-        let results = self.training_manager.search_patterns(&criteria);
+        let training_manager = self.training_manager.read().await;
+        let results = training_manager.search_patterns(&criteria);
+
+        let structured = serde_json::json!({
+            "total": results.len(),
+            "results": results
+                .iter()
+                .map(|(pattern, score)| serde_json::json!({ "pattern": pattern, "score": score }))
+                .collect::<Vec<_>>()
+        });
 
         let mut output = String::new();
         output.push_str("# Pattern Search Results\n\n");
@@ -639,18 +1178,12 @@ impl Server {
             output.push_str("---\n\n");
         }
 
-        Ok(serde_json::json!({
-            "content": [{
-                "type": "text",
-                "text": output
-            }],
-            "isError": false
-        }))
+        Ok(Self::tool_result(output, structured))
     }
 
     // Tool: train-pattern
     async fn tool_train_pattern(
-        &mut self,
+        &self,
         args: &serde_json::Value,
     ) -> Result<serde_json::Value, String> {
         let pattern = CodePattern {
@@ -684,34 +1217,39 @@ impl Server {
             updated_at: chrono::Utc::now(),
         };
 
+        let mut training_manager = self.training_manager.write().await;
+
         // Add pattern with validation (prevents path traversal)
-        self.training_manager
+        let added = training_manager
             .add_pattern(pattern.clone())
             .map_err(|e| format!("Invalid pattern: {}", e))?;
 
         // Save to disk
-        self.training_manager
+        training_manager
             .save_patterns()
             .await
             .map_err(|e| format!("Failed to save patterns: {}", e))?;
+        drop(training_manager);
+
+        // The file watcher will see the save too, but finds nothing new by then.
+        // Retraining an existing pattern leaves the list as it was.
+        self.notify_resources_changed(added, &[Self::pattern_uri(&pattern)]);
 
         let output = format!(
             "✅ Pattern '{}' added successfully!\n\n**ID:** {}\n**Category:** {}\n**Framework:** {}",
             pattern.title, pattern.id, pattern.category, pattern.framework
         );
+        let structured = serde_json::json!({
+            "uri": Self::pattern_uri(&pattern),
+            "pattern": pattern
+        });
 
-        Ok(serde_json::json!({
-            "content": [{
-                "type": "text",
-                "text": output
-            }],
-            "isError": false
-        }))
+        Ok(Self::tool_result(output, structured))
     }
 
     // Tool: get-statistics
     async fn tool_get_statistics(&self) -> Result<serde_json::Value, String> {
-        let stats = self.training_manager.get_statistics();
+        let stats = self.training_manager.read().await.get_statistics();
 
         let output = format!(
             "# Pattern Database Statistics\n\n\
@@ -741,13 +1279,7 @@ impl Server {
                 .unwrap_or_default()
         );
 
-        Ok(serde_json::json!({
-            "content": [{
-                "type": "text",
-                "text": output
-            }],
-            "isError": false
-        }))
+        Ok(Self::tool_result(output, stats))
     }
 
     // Tool: get-help
@@ -813,7 +1345,7 @@ get-statistics {}
 - **JavaScript:** react, vue, nextjs, express
 - **Python:** django, flask, fastapi
 - **Rust:** actix-web, axum, tokio
-- **.NET:** blazor-server, aspnet-core
+- **.NET:** blazor-server, blazor-wasm, aspnet-core, worker-service
 - **Go:** gin, fiber
 - **Java:** spring
 
@@ -823,26 +1355,1115 @@ get-statistics {}
 - El servidor detecta automáticamente el tipo de proyecto
 "#;
 
-        Ok(serde_json::json!({
+        let structured = serde_json::json!({
+            "tools": [
+                "analyze-project",
+                "search-patterns",
+                "get-patterns",
+                "train-pattern",
+                "get-statistics",
+                "get-help"
+            ],
+            "text": help_text
+        });
+
+        Ok(Self::tool_result(help_text.to_string(), structured))
+    }
+
+    /// Builds a `CallToolResult` reporting a failed tool call
+    fn tool_error(message: String) -> serde_json::Value {
+        serde_json::json!({
+            "content": [{
+                "type": "text",
+                "text": message
+            }],
+            "isError": true
+        })
+    }
+
+    /// Wraps a tool's markdown output and its `structuredContent` (which must
+    /// match the tool's `outputSchema`) into a `CallToolResult`
+    fn tool_result(text: String, structured: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({
             "content": [{
                 "type": "text",
-                "text": help_text
+                "text": text
             }],
+            "structuredContent": structured,
             "isError": false
+        })
+    }
+
+    async fn handle_prompts_list(&self) -> Result<serde_json::Value, RpcError> {
+        Ok(serde_json::json!({
+            "prompts": [
+                {
+                    "name": "review-blazor-component",
+                    "description": "Review a Blazor component against the Blazor Server patterns in the database",
+                    "arguments": [
+                        {
+                            "name": "code",
+                            "description": "Component source (.razor or .razor.cs)",
+                            "required": true
+                        },
+                        {
+                            "name": "focus",
+                            "description": "Optional topic to prioritize (e.g. 'lifecycle', 'state', 'security')",
+                            "required": false
+                        }
+                    ]
+                },
+                {
+                    "name": "explain-project",
+                    "description": "Analyze a project and ask for an explanation of its structure, dependencies and conventions",
+                    "arguments": [
+                        {
+                            "name": "project_path",
+                            "description": "Absolute path to the project directory",
+                            "required": true
+                        }
+                    ]
+                },
+                {
+                    "name": "apply-pattern",
+                    "description": "Apply a stored code pattern to the user's code",
+                    "arguments": [
+                        {
+                            "name": "framework",
+                            "description": "Framework the pattern belongs to (e.g., blazor-server)",
+                            "required": true
+                        },
+                        {
+                            "name": "pattern_id",
+                            "description": "ID of the pattern to apply",
+                            "required": true
+                        },
+                        {
+                            "name": "code",
+                            "description": "Code the pattern should be applied to",
+                            "required": false
+                        }
+                    ]
+                }
+            ]
+        }))
+    }
+
+    async fn handle_prompts_get(
+        &self,
+        params: Option<serde_json::Value>,
+        control: &AnalysisControl,
+    ) -> Result<serde_json::Value, RpcError> {
+        let params = params.ok_or_else(|| RpcError::invalid_params("Missing params"))?;
+        let name = params["name"]
+            .as_str()
+            .ok_or_else(|| RpcError::invalid_params("Missing prompt name"))?;
+        let arguments = &params["arguments"];
+
+        let (description, text) = match name {
+            "review-blazor-component" => {
+                let code = arguments["code"]
+                    .as_str()
+                    .ok_or_else(|| RpcError::invalid_params("Missing code"))?;
+                let focus = arguments["focus"].as_str();
+
+                let criteria = SearchCriteria {
+                    query: focus.map(|s| s.to_string()),
+                    category: None,
+                    framework: Some("blazor-server".to_string()),
+                    tags: vec![],
+                    min_score: 0.7,
+                };
+                let training_manager = self.training_manager.read().await;
+                let patterns: Vec<&CodePattern> = training_manager
+                    .search_patterns(&criteria)
+                    .into_iter()
+                    .take(5)
+                    .map(|(p, _)| p)
+                    .collect();
+
+                let mut text = String::from(
+                    "Review the following Blazor Server component. Point out lifecycle, \
+                    state management, performance and security issues, and suggest concrete fixes.\n\n",
+                );
+                if let Some(focus) = focus {
+                    text.push_str(&format!("Focus especially on: {}\n\n", focus));
+                }
+                text.push_str(&format!("```razor\n{}\n```\n\n", code));
+                text.push_str(&Self::format_prompt_patterns(&patterns));
+
+                ("Blazor component review".to_string(), text)
+            }
+            "explain-project" => {
+                let project_path = arguments["project_path"]
+                    .as_str()
+                    .ok_or_else(|| RpcError::invalid_params("Missing project_path"))?;
+                let analysis = self.analyze_project_path(project_path, control).await?;
+                let context = ContextBuilder::new().build_generic_context_string(&analysis);
+
+                let text = format!(
+                    "Explain this project: what it does, how it is structured, its key \
+                    dependencies and the conventions a new contributor should follow.\n\n{}",
+                    context
+                );
+
+                (format!("Explanation of {}", analysis.project.name), text)
+            }
+            "apply-pattern" => {
+                let framework = arguments["framework"]
+                    .as_str()
+                    .ok_or_else(|| RpcError::invalid_params("Missing framework"))?;
+                let pattern_id = arguments["pattern_id"]
+                    .as_str()
+                    .ok_or_else(|| RpcError::invalid_params("Missing pattern_id"))?;
+                // The same ID may exist under several frameworks
+                let training_manager = self.training_manager.read().await;
+                let pattern = training_manager
+                    .get_pattern(framework, pattern_id)
+                    .ok_or_else(|| {
+                        RpcError::invalid_params(format!(
+                            "Pattern not found: {}/{}",
+                            framework, pattern_id
+                        ))
+                    })?;
+
+                let mut text = format!(
+                    "Apply the pattern \"{}\" ({} / {}).\n\n{}\n\n```\n{}\n```\n\n",
+                    pattern.title,
+                    pattern.framework,
+                    pattern.category,
+                    pattern.description,
+                    pattern.code
+                );
+                match arguments["code"].as_str() {
+                    Some(code) => text.push_str(&format!(
+                        "Rewrite the following code so it follows this pattern:\n\n```\n{}\n```\n",
+                        code
+                    )),
+                    None => text.push_str(
+                        "Show how to apply this pattern to the code we are working on.\n",
+                    ),
+                }
+
+                (format!("Apply pattern {}", pattern.id), text)
+            }
+            _ => {
+                return Err(RpcError::invalid_params(format!(
+                    "Unknown prompt: {}",
+                    name
+                )))
+            }
+        };
+
+        Ok(serde_json::json!({
+            "description": description,
+            "messages": [{
+                "role": "user",
+                "content": {
+                    "type": "text",
+                    "text": text
+                }
+            }]
         }))
     }
 
-    async fn handle_prompts_list(&self) -> Result<serde_json::Value, String> {
-        // Return empty prompts list (not implemented yet)
+    /// Formats patterns as a markdown reference section for prompt messages
+    fn format_prompt_patterns(patterns: &[&CodePattern]) -> String {
+        if patterns.is_empty() {
+            return String::new();
+        }
+
+        let mut output = String::from("## Reference patterns\n\n");
+        for pattern in patterns {
+            output.push_str(&format!("### {} ({})\n", pattern.title, pattern.category));
+            output.push_str(&format!("{}\n\n", pattern.description));
+            output.push_str("```csharp\n");
+            output.push_str(&pattern.code);
+            output.push_str("\n```\n\n");
+        }
+        output
+    }
+
+    async fn handle_resources_list(&self) -> Result<serde_json::Value, RpcError> {
+        let mut resources = Vec::new();
+
+        for pattern in self.training_manager.read().await.get_all_patterns() {
+            resources.push(serde_json::json!({
+                "uri": Self::pattern_uri(pattern),
+                "name": pattern.title,
+                "description": pattern.description,
+                "mimeType": "text/plain"
+            }));
+        }
+
+        for (name, analysis) in self.analyzed_projects.read().await.iter() {
+            resources.push(serde_json::json!({
+                "uri": Self::project_uri(name),
+                "name": format!("{} summary", name),
+                "description": format!(
+                    "Analysis of {} project at {}",
+                    analysis.project.project_type.as_str(),
+                    analysis.project.path.display()
+                ),
+                "mimeType": "text/markdown"
+            }));
+        }
+
         Ok(serde_json::json!({
-            "prompts": []
+            "resources": resources
         }))
     }
 
-    async fn handle_resources_list(&self) -> Result<serde_json::Value, String> {
-        // Return empty resources list (not implemented yet)
+    async fn handle_resources_read(
+        &self,
+        params: Option<serde_json::Value>,
+    ) -> Result<serde_json::Value, RpcError> {
+        let params = params.ok_or_else(|| RpcError::invalid_params("Missing params"))?;
+        let uri = params["uri"]
+            .as_str()
+            .ok_or_else(|| RpcError::invalid_params("Missing uri"))?;
+
+        let (mime_type, text) = if let Some(rest) = uri.strip_prefix(PATTERN_URI_PREFIX) {
+            let (framework, id) = rest
+                .split_once('/')
+                .ok_or_else(|| RpcError::invalid_params(format!("Invalid pattern URI: {}", uri)))?;
+            let training_manager = self.training_manager.read().await;
+            let pattern = training_manager
+                .get_pattern(framework, id)
+                .ok_or_else(|| RpcError::resource_not_found(uri))?;
+            ("text/plain", pattern.code.clone())
+        } else if let Some(rest) = uri.strip_prefix(PROJECT_URI_PREFIX) {
+            let name = rest
+                .strip_suffix(PROJECT_URI_SUFFIX)
+                .ok_or_else(|| RpcError::invalid_params(format!("Invalid project URI: {}", uri)))?;
+            let analyzed_projects = self.analyzed_projects.read().await;
+            let analysis = analyzed_projects
+                .get(name)
+                .ok_or_else(|| RpcError::resource_not_found(uri))?;
+            (
+                "text/markdown",
+                ContextBuilder::new().build_generic_context_string(analysis),
+            )
+        } else {
+            return Err(RpcError::invalid_params(format!(
+                "Unsupported resource URI: {}",
+                uri
+            )));
+        };
+
         Ok(serde_json::json!({
-            "resources": []
+            "contents": [{
+                "uri": uri,
+                "mimeType": mime_type,
+                "text": text
+            }]
         }))
     }
+
+    fn pattern_uri(pattern: &CodePattern) -> String {
+        format!("{}{}/{}", PATTERN_URI_PREFIX, pattern.framework, pattern.id)
+    }
+
+    fn project_uri(name: &str) -> String {
+        format!("{}{}{}", PROJECT_URI_PREFIX, name, PROJECT_URI_SUFFIX)
+    }
+
+    fn handle_resources_subscribe(
+        &self,
+        params: Option<&serde_json::Value>,
+        session: &str,
+        subscribe: bool,
+    ) -> Result<serde_json::Value, RpcError> {
+        let uri = params
+            .and_then(|p| p["uri"].as_str())
+            .ok_or_else(|| RpcError::invalid_params("Missing uri"))?;
+        if !uri.starts_with(PATTERN_URI_PREFIX) && !uri.starts_with(PROJECT_URI_PREFIX) {
+            return Err(RpcError::invalid_params(format!(
+                "Unsupported resource URI: {}",
+                uri
+            )));
+        }
+
+        if let Some(state) = self.sessions.lock().unwrap().get_mut(session) {
+            if subscribe {
+                state.subscriptions.insert(uri.to_string());
+            } else {
+                state.subscriptions.remove(uri);
+            }
+        }
+
+        Ok(serde_json::json!({}))
+    }
+
+    fn notify_patterns_changed(&self, changes: &PatternChanges) {
+        if changes.is_empty() {
+            return;
+        }
+
+        let list_changed = !changes.added.is_empty() || !changes.removed.is_empty();
+        let updated: Vec<String> = changes
+            .added
+            .iter()
+            .chain(&changes.updated)
+            .chain(&changes.removed)
+            .map(Self::pattern_uri)
+            .collect();
+        self.notify_resources_changed(list_changed, &updated);
+    }
+
+    /// Sends `notifications/resources/list_changed` to every initialized
+    /// session (if `list_changed`), and `notifications/resources/updated` for
+    /// each URI a session subscribed to.
+    fn notify_resources_changed(&self, list_changed: bool, updated: &[String]) {
+        let sessions = self.sessions.lock().unwrap();
+        for state in sessions.values().filter(|state| state.initialized) {
+            if list_changed {
+                let notification = serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "method": "notifications/resources/list_changed"
+                });
+                let _ = state.outbound.send(notification.to_string());
+            }
+
+            for uri in updated
+                .iter()
+                .filter(|uri| state.subscriptions.contains(*uri))
+            {
+                let notification = serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "method": "notifications/resources/updated",
+                    "params": { "uri": uri }
+                });
+                let _ = state.outbound.send(notification.to_string());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    const PATTERN_JSON: &str = r#"{
+  "patterns": [
+    {
+      "id": "di-service",
+      "category": "dependency-injection",
+      "framework": "blazor-server",
+      "version": "10.0",
+      "title": "Service injection",
+      "description": "Inject a scoped service",
+      "code": "@inject IDataService Data",
+      "tags": ["di"],
+      "usage_count": 0,
+      "relevance_score": 0.9,
+      "created_at": "2025-10-25T00:00:00Z",
+      "updated_at": "2025-10-25T00:00:00Z"
+    }
+  ]
+}"#;
+
+    async fn test_server(base: &std::path::Path) -> Server {
+        let patterns = base.join("patterns");
+        fs::create_dir_all(&patterns).unwrap();
+        fs::write(patterns.join("blazor-server-patterns.json"), PATTERN_JSON).unwrap();
+
+        let mut config = Config::default();
+        config.storage.base_path = base.to_path_buf();
+        Server::new(config).await.unwrap()
+    }
+
+    async fn call(server: &Server, method: &str, params: serde_json::Value) -> serde_json::Value {
+        let (outbound, _rx) = mpsc::unbounded_channel();
+        let response = server
+            .handle_request(
+                JsonRpcRequest {
+                    jsonrpc: "2.0".to_string(),
+                    id: Some(serde_json::json!(1)),
+                    method: method.to_string(),
+                    params: Some(params),
+                },
+                "",
+                &outbound,
+                &AnalysisControl::new(),
+            )
+            .await;
+        serde_json::to_value(&response).unwrap()
+    }
+
+    /// Runs the initialization handshake for a session
+    async fn initialize(server: &Server, session: &str) {
+        let (outbound, _rx) = mpsc::unbounded_channel();
+        let init = r#"{"jsonrpc":"2.0","id":0,"method":"initialize","params":{}}"#;
+        let response = server.handle_message(init, session, &outbound).await;
+        assert!(response.unwrap().error.is_none());
+        let initialized = r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#;
+        assert!(server
+            .handle_message(initialized, session, &outbound)
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pattern_resources() {
+        let dir = tempdir().unwrap();
+        // The same ID under another framework is a resource of its own
+        fs::create_dir_all(dir.path().join("patterns")).unwrap();
+        fs::write(
+            dir.path().join("patterns/razor-patterns.json"),
+            PATTERN_JSON
+                .replace("blazor-server", "razor")
+                .replace("IDataService", "IRazorService"),
+        )
+        .unwrap();
+        let server = test_server(dir.path()).await;
+
+        let list = call(&server, "resources/list", serde_json::json!({})).await;
+        let uris: Vec<&str> = list["result"]["resources"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|r| r["uri"].as_str())
+            .collect();
+        assert_eq!(
+            uris,
+            vec![
+                "pattern://blazor-server/di-service",
+                "pattern://razor/di-service"
+            ]
+        );
+
+        let read = call(
+            &server,
+            "resources/read",
+            serde_json::json!({ "uri": "pattern://blazor-server/di-service" }),
+        )
+        .await;
+        assert_eq!(
+            read["result"]["contents"][0]["text"],
+            "@inject IDataService Data"
+        );
+
+        let missing = call(
+            &server,
+            "resources/read",
+            serde_json::json!({ "uri": "pattern://react/di-service" }),
+        )
+        .await;
+        assert!(missing["error"].is_object());
+
+        for (uri, code) in [
+            (
+                "pattern://blazor-server/di-service",
+                "@inject IDataService Data",
+            ),
+            ("pattern://razor/di-service", "@inject IRazorService Data"),
+        ] {
+            let read = call(&server, "resources/read", serde_json::json!({ "uri": uri })).await;
+            assert_eq!(read["result"]["contents"][0]["text"], code);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_project_resources() {
+        let dir = tempdir().unwrap();
+        let server = test_server(dir.path()).await;
+
+        let project = dir.path().join("demo");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(
+            project.join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+
+        call(
+            &server,
+            "tools/call",
+            serde_json::json!({
+                "name": "analyze-project",
+                "arguments": { "project_path": project.to_str().unwrap() }
+            }),
+        )
+        .await;
+
+        let list = call(&server, "resources/list", serde_json::json!({})).await;
+        assert!(list["result"]["resources"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|r| r["uri"] == "project://demo/summary"));
+
+        let read = call(
+            &server,
+            "resources/read",
+            serde_json::json!({ "uri": "project://demo/summary" }),
+        )
+        .await;
+        let text = read["result"]["contents"][0]["text"].as_str().unwrap();
+        assert!(text.contains("**Project:** demo"));
+    }
+
+    #[tokio::test]
+    async fn test_prompts() {
+        let dir = tempdir().unwrap();
+        let server = test_server(dir.path()).await;
+
+        let list = call(&server, "prompts/list", serde_json::json!({})).await;
+        let names: Vec<&str> = list["result"]["prompts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|p| p["name"].as_str())
+            .collect();
+        assert!(names.contains(&"review-blazor-component"));
+        assert!(names.contains(&"explain-project"));
+        assert!(names.contains(&"apply-pattern"));
+
+        let review = call(
+            &server,
+            "prompts/get",
+            serde_json::json!({
+                "name": "review-blazor-component",
+                "arguments": { "code": "<h1>Hello</h1>" }
+            }),
+        )
+        .await;
+        let text = review["result"]["messages"][0]["content"]["text"]
+            .as_str()
+            .unwrap();
+        assert!(text.contains("<h1>Hello</h1>"));
+        assert!(text.contains("@inject IDataService Data"));
+
+        let apply = call(
+            &server,
+            "prompts/get",
+            serde_json::json!({
+                "name": "apply-pattern",
+                "arguments": { "framework": "blazor-server", "pattern_id": "di-service" }
+            }),
+        )
+        .await;
+        assert_eq!(apply["result"]["messages"][0]["role"], "user");
+
+        // Patterns are looked up within their framework
+        let other_framework = call(
+            &server,
+            "prompts/get",
+            serde_json::json!({
+                "name": "apply-pattern",
+                "arguments": { "framework": "react", "pattern_id": "di-service" }
+            }),
+        )
+        .await;
+        assert!(other_framework["error"].is_object());
+
+        let missing = call(
+            &server,
+            "prompts/get",
+            serde_json::json!({ "name": "explain-project", "arguments": {} }),
+        )
+        .await;
+        assert!(missing["error"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_request_is_signalled_and_dropped() {
+        let dir = tempdir().unwrap();
+        let server = test_server(dir.path()).await;
+
+        let control = AnalysisControl::new();
+        server.in_flight.lock().unwrap().insert(
+            Server::request_key("s1", &serde_json::json!(7)),
+            control.clone(),
+        );
+
+        // A cancellation from another session must not reach the request
+        let foreign =
+            r#"{"jsonrpc":"2.0","method":"notifications/cancelled","params":{"requestId":7}}"#;
+        let (outbound, _rx) = mpsc::unbounded_channel();
+        assert!(server
+            .handle_message(foreign, "s2", &outbound)
+            .await
+            .is_none());
+        assert!(!control.is_cancelled());
+
+        assert!(server
+            .handle_message(foreign, "s1", &outbound)
+            .await
+            .is_none());
+        assert!(control.is_cancelled());
+
+        // An analysis started with a cancelled control stops instead of completing
+        let project = dir.path().join("demo");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(project.join("Cargo.toml"), "[package]\nname = \"demo\"\n").unwrap();
+        let result = server
+            .analyze_project_path(project.to_str().unwrap(), &control)
+            .await;
+        assert!(result.unwrap_err().message.contains("cancelled"));
+    }
+
+    #[tokio::test]
+    async fn test_progress_notifications() {
+        let dir = tempdir().unwrap();
+        let server = test_server(dir.path()).await;
+
+        let project = dir.path().join("demo");
+        fs::create_dir_all(project.join("src")).unwrap();
+        fs::write(project.join("Cargo.toml"), "[package]\nname = \"demo\"\n").unwrap();
+        fs::write(project.join("src/main.rs"), "fn main() {}").unwrap();
+        fs::write(project.join("src/lib.rs"), "").unwrap();
+
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": {
+                "name": "analyze-project",
+                "arguments": { "project_path": project.to_str().unwrap() },
+                "_meta": { "progressToken": "tok" }
+            }
+        });
+        initialize(&server, "").await;
+        let (outbound, mut rx) = mpsc::unbounded_channel();
+        let response = server
+            .handle_message(&request.to_string(), "", &outbound)
+            .await;
+        assert!(response.unwrap().error.is_none());
+
+        let mut progress = Vec::new();
+        while let Ok(message) = rx.try_recv() {
+            let message: serde_json::Value = serde_json::from_str(&message).unwrap();
+            assert_eq!(message["method"], "notifications/progress");
+            assert_eq!(message["params"]["progressToken"], "tok");
+            progress.push(message["params"]["progress"].as_u64().unwrap());
+        }
+        assert!(progress.len() >= 2);
+        assert!(progress.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(*progress.last().unwrap(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_structured_tool_output() {
+        let dir = tempdir().unwrap();
+        let server = test_server(dir.path()).await;
+
+        let tools = call(&server, "tools/list", serde_json::json!({})).await;
+        let tools = tools["result"]["tools"].as_array().unwrap();
+        assert!(tools
+            .iter()
+            .all(|tool| tool["outputSchema"]["type"] == "object"));
+
+        let search = call(
+            &server,
+            "tools/call",
+            serde_json::json!({
+                "name": "search-patterns",
+                "arguments": { "query": "inject", "framework": "blazor-server" }
+            }),
+        )
+        .await;
+        let structured = &search["result"]["structuredContent"];
+        assert_eq!(structured["total"], 1);
+        assert_eq!(structured["results"][0]["pattern"]["id"], "di-service");
+        assert!(structured["results"][0]["score"].is_number());
+        assert!(search["result"]["content"][0]["text"].is_string());
+
+        let project = dir.path().join("demo");
+        fs::create_dir_all(project.join("src")).unwrap();
+        fs::write(project.join("Cargo.toml"), "[package]\nname = \"demo\"\n").unwrap();
+        fs::write(project.join("src/main.rs"), "fn main() {}").unwrap();
+
+        let analysis = call(
+            &server,
+            "tools/call",
+            serde_json::json!({
+                "name": "analyze-project",
+                "arguments": { "project_path": project.to_str().unwrap() }
+            }),
+        )
+        .await;
+        let structured = &analysis["result"]["structuredContent"];
+        assert_eq!(structured["project"]["name"], "demo");
+        assert_eq!(structured["project"]["project_type"], "Rust");
+        assert_eq!(structured["statistics"]["total_files"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_error_codes_and_tool_errors() {
+        let dir = tempdir().unwrap();
+        let server = test_server(dir.path()).await;
+
+        let unknown = call(&server, "does/not-exist", serde_json::json!({})).await;
+        assert_eq!(unknown["error"]["code"], METHOD_NOT_FOUND);
+
+        let ping = call(&server, "ping", serde_json::json!({})).await;
+        assert_eq!(ping["result"], serde_json::json!({}));
+
+        let unknown_tool = call(
+            &server,
+            "tools/call",
+            serde_json::json!({ "name": "nope", "arguments": {} }),
+        )
+        .await;
+        assert_eq!(unknown_tool["error"]["code"], INVALID_PARAMS);
+
+        // A tool that runs and fails is a successful call with isError set
+        let failed = call(
+            &server,
+            "tools/call",
+            serde_json::json!({
+                "name": "analyze-project",
+                "arguments": { "project_path": dir.path().join("missing").to_str().unwrap() }
+            }),
+        )
+        .await;
+        assert!(failed["error"].is_null());
+        assert_eq!(failed["result"]["isError"], true);
+        assert!(failed["result"]["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("does not exist"));
+
+        let missing = call(
+            &server,
+            "resources/read",
+            serde_json::json!({ "uri": "pattern://blazor-server/nope" }),
+        )
+        .await;
+        assert_eq!(missing["error"]["code"], RESOURCE_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_batches_and_malformed_messages() {
+        let dir = tempdir().unwrap();
+        let server = test_server(dir.path()).await;
+        initialize(&server, "").await;
+        let (outbound, _rx) = mpsc::unbounded_channel();
+
+        let batch = r#"[
+            {"jsonrpc":"2.0","id":1,"method":"ping"},
+            {"jsonrpc":"2.0","method":"notifications/initialized"},
+            {"jsonrpc":"2.0","id":2,"method":"nope"}
+        ]"#;
+        let response = server.handle_payload(batch, "", &outbound).await.unwrap();
+        let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+        let responses = response.as_array().unwrap();
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0]["id"], 1);
+        assert_eq!(responses[1]["error"]["code"], METHOD_NOT_FOUND);
+
+        let notifications = r#"[
+            {"jsonrpc":"2.0","method":"notifications/initialized"},
+            {"jsonrpc":"2.0","method":"ping"},
+            {"jsonrpc":"2.0","method":"tools/call","params":{"name":"nope"}},
+            {"jsonrpc":"2.0","method":"nope"}
+        ]"#;
+        assert!(server
+            .handle_payload(notifications, "", &outbound)
+            .await
+            .is_none());
+        assert!(server
+            .handle_message(r#"{"jsonrpc":"2.0","method":"ping"}"#, "", &outbound)
+            .await
+            .is_none());
+
+        let empty = server.handle_payload("[]", "", &outbound).await.unwrap();
+        assert!(empty.contains(&INVALID_REQUEST.to_string()));
+
+        let garbage = server
+            .handle_message("{not json", "", &outbound)
+            .await
+            .unwrap();
+        let garbage = serde_json::to_value(&garbage).unwrap();
+        assert_eq!(garbage["error"]["code"], PARSE_ERROR);
+        assert!(garbage["id"].is_null());
+        assert!(garbage.as_object().unwrap().contains_key("id"));
+
+        let invalid = server
+            .handle_message(r#"{"jsonrpc":"2.0","id":5}"#, "", &outbound)
+            .await
+            .unwrap();
+        let invalid = serde_json::to_value(&invalid).unwrap();
+        assert_eq!(invalid["error"]["code"], INVALID_REQUEST);
+        assert_eq!(invalid["id"], 5);
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle_and_version_negotiation() {
+        let dir = tempdir().unwrap();
+        let server = test_server(dir.path()).await;
+        let (outbound, _rx) = mpsc::unbounded_channel();
+
+        let list = r#"{"jsonrpc":"2.0","id":1,"method":"tools/list"}"#;
+        let early = server.handle_message(list, "a", &outbound).await.unwrap();
+        assert_eq!(early.error.unwrap().code, INVALID_REQUEST);
+
+        // Pings are allowed at any time
+        let ping = r#"{"jsonrpc":"2.0","id":2,"method":"ping"}"#;
+        let ping = server.handle_message(ping, "a", &outbound).await.unwrap();
+        assert!(ping.error.is_none());
+
+        let init = r#"{"jsonrpc":"2.0","id":3,"method":"initialize",
+            "params":{"protocolVersion":"2025-03-26","capabilities":{}}}"#;
+        let init = server.handle_message(init, "a", &outbound).await.unwrap();
+        let result = init.result.unwrap();
+        assert_eq!(result["protocolVersion"], "2025-03-26");
+        assert!(result["capabilities"]["tools"].is_object());
+        assert!(result["capabilities"]["resources"].is_object());
+        assert!(result["capabilities"]["prompts"].is_object());
+        assert!(result["capabilities"]["logging"].is_object());
+
+        // Still rejected until the client confirms with notifications/initialized
+        let pending = server.handle_message(list, "a", &outbound).await.unwrap();
+        assert_eq!(pending.error.unwrap().code, INVALID_REQUEST);
+
+        let initialized = r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#;
+        server.handle_message(initialized, "a", &outbound).await;
+        let ready = server.handle_message(list, "a", &outbound).await.unwrap();
+        assert!(ready.error.is_none());
+
+        // Other sessions are unaffected, and unknown versions get our newest one
+        let other = server.handle_message(list, "b", &outbound).await.unwrap();
+        assert!(other.error.is_some());
+        assert_eq!(
+            Server::negotiate_protocol_version("1999-01-01"),
+            SUPPORTED_PROTOCOL_VERSIONS[0]
+        );
+        assert_eq!(server.protocol_version("a").unwrap(), "2025-03-26");
+    }
+
+    #[tokio::test]
+    async fn test_set_level_forwards_logs() {
+        let dir = tempdir().unwrap();
+        let forwarder = LogForwarder::default();
+        let server = test_server(dir.path())
+            .await
+            .with_log_forwarder(forwarder.clone());
+
+        let invalid = call(
+            &server,
+            "logging/setLevel",
+            serde_json::json!({ "level": "loud" }),
+        )
+        .await;
+        assert_eq!(invalid["error"]["code"], INVALID_PARAMS);
+
+        let subscriber = tracing_subscriber::registry().with(forwarder.layer());
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let (outbound, mut rx) = mpsc::unbounded_channel();
+        initialize(&server, "").await;
+        let set_level =
+            r#"{"jsonrpc":"2.0","id":1,"method":"logging/setLevel","params":{"level":"debug"}}"#;
+        let response = server.handle_message(set_level, "", &outbound).await;
+        assert_eq!(response.unwrap().result.unwrap(), serde_json::json!({}));
+
+        // Searching a framework without patterns explains why nothing matched
+        server
+            .training_manager
+            .read()
+            .await
+            .search_patterns(&SearchCriteria {
+                query: None,
+                category: None,
+                framework: Some("fortran".to_string()),
+                tags: vec![],
+                min_score: 0.0,
+            });
+
+        let message: serde_json::Value = serde_json::from_str(&rx.try_recv().unwrap()).unwrap();
+        assert_eq!(message["method"], "notifications/message");
+        assert_eq!(message["params"]["level"], "debug");
+        assert!(message["params"]["data"]
+            .as_str()
+            .unwrap()
+            .contains("fortran"));
+
+        // So does a pattern file that fails to load
+        let broken = dir.path().join("patterns/broken-patterns.json");
+        fs::write(&broken, "{ \"patterns\": [").unwrap();
+        server.reload_pattern_file(&broken).await;
+        let warnings: Vec<serde_json::Value> = std::iter::from_fn(|| rx.try_recv().ok())
+            .map(|message| serde_json::from_str(&message).unwrap())
+            .filter(|message: &serde_json::Value| message["params"]["level"] == "warning")
+            .collect();
+        assert!(warnings[0]["params"]["data"]
+            .as_str()
+            .unwrap()
+            .contains("broken-patterns.json"));
+    }
+
+    /// Initializes a session whose unsolicited messages arrive on the returned receiver
+    async fn connect(server: &Server, session: &str) -> mpsc::UnboundedReceiver<String> {
+        let (outbound, rx) = mpsc::unbounded_channel();
+        let init = r#"{"jsonrpc":"2.0","id":0,"method":"initialize","params":{}}"#;
+        server.handle_message(init, session, &outbound).await;
+        let initialized = r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#;
+        server.handle_message(initialized, session, &outbound).await;
+        rx
+    }
+
+    fn drain(rx: &mut mpsc::UnboundedReceiver<String>) -> Vec<serde_json::Value> {
+        std::iter::from_fn(|| rx.try_recv().ok())
+            .map(|message| serde_json::from_str(&message).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_resource_change_notifications() {
+        let dir = tempdir().unwrap();
+        let server = test_server(dir.path()).await;
+        let mut subscriber = connect(&server, "a").await;
+        let mut bystander = connect(&server, "b").await;
+
+        let subscribe = r#"{"jsonrpc":"2.0","id":1,"method":"resources/subscribe",
+            "params":{"uri":"pattern://blazor-server/di-service"}}"#;
+        let (outbound, _rx) = mpsc::unbounded_channel();
+        let response = server.handle_message(subscribe, "a", &outbound).await;
+        assert!(response.unwrap().error.is_none());
+
+        // Editing an existing pattern only notifies subscribers
+        let file = dir.path().join("patterns/blazor-server-patterns.json");
+        fs::write(&file, PATTERN_JSON.replace("IDataService", "IOrderService")).unwrap();
+        server.reload_pattern_file(&file).await;
+
+        let messages = drain(&mut subscriber);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["method"], "notifications/resources/updated");
+        assert_eq!(
+            messages[0]["params"]["uri"],
+            "pattern://blazor-server/di-service"
+        );
+        assert!(drain(&mut bystander).is_empty());
+
+        // Reloading an unchanged file is silent
+        server.reload_pattern_file(&file).await;
+        assert!(drain(&mut subscriber).is_empty());
+
+        // A new pattern file changes the resource list for everyone
+        let other = dir.path().join("patterns/laravel-patterns.json");
+        fs::write(
+            &other,
+            PATTERN_JSON
+                .replace("di-service", "eloquent-scope")
+                .replace("blazor-server", "laravel"),
+        )
+        .unwrap();
+        server.reload_pattern_file(&other).await;
+        for rx in [&mut subscriber, &mut bystander] {
+            let messages = drain(rx);
+            assert_eq!(messages.len(), 1);
+            assert_eq!(
+                messages[0]["method"],
+                "notifications/resources/list_changed"
+            );
+        }
+        assert!(server
+            .training_manager
+            .read()
+            .await
+            .get_pattern_by_id("eloquent-scope")
+            .is_some());
+
+        // So does training a pattern through the tool
+        let train = call(
+            &server,
+            "tools/call",
+            serde_json::json!({
+                "name": "train-pattern",
+                "arguments": {
+                    "id": "state-container",
+                    "category": "state-management",
+                    "framework": "blazor-server",
+                    "title": "State container",
+                    "description": "Scoped state shared between components",
+                    "code": "public class AppState { }"
+                }
+            }),
+        )
+        .await;
+        assert_eq!(train["result"]["isError"], false);
+        let messages = drain(&mut bystander);
+        assert_eq!(
+            messages[0]["method"],
+            "notifications/resources/list_changed"
+        );
+
+        drain(&mut subscriber);
+
+        // Retraining an existing pattern only updates the resource
+        let retrain = call(
+            &server,
+            "tools/call",
+            serde_json::json!({
+                "name": "train-pattern",
+                "arguments": {
+                    "id": "di-service",
+                    "category": "dependency-injection",
+                    "framework": "blazor-server",
+                    "title": "Service injection",
+                    "description": "Inject a scoped service",
+                    "code": "@inject IStateService State"
+                }
+            }),
+        )
+        .await;
+        assert_eq!(retrain["result"]["isError"], false);
+        assert!(drain(&mut bystander).is_empty());
+        let messages = drain(&mut subscriber);
+        assert!(messages
+            .iter()
+            .all(|m| m["method"] != "notifications/resources/list_changed"));
+        assert!(messages.iter().any(|m| {
+            m["method"] == "notifications/resources/updated"
+                && m["params"]["uri"] == "pattern://blazor-server/di-service"
+        }));
+        let count = server
+            .training_manager
+            .read()
+            .await
+            .get_all_patterns()
+            .iter()
+            .filter(|p| p.id == "di-service")
+            .count();
+        assert_eq!(count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_watcher_reloads_pattern_files() {
+        let dir = tempdir().unwrap();
+        let server = Arc::new(test_server(dir.path()).await);
+        let mut rx = connect(&server, "").await;
+        watch::spawn(server.clone());
+
+        // The watcher registers in the background, so a write may land before
+        // it listens; write again until the reload is announced
+        let file = dir.path().join("patterns/razor-patterns.json");
+        let content = PATTERN_JSON.replace("di-service", "render-fragment");
+        let message = tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                fs::write(&file, &content).unwrap();
+                if let Ok(message) =
+                    tokio::time::timeout(Duration::from_millis(500), rx.recv()).await
+                {
+                    break message;
+                }
+            }
+        })
+        .await
+        .expect("no notification from the watcher")
+        .unwrap();
+        assert!(message.contains("notifications/resources/list_changed"));
+        assert!(server
+            .training_manager
+            .read()
+            .await
+            .get_pattern_by_id("render-fragment")
+            .is_some());
+    }
 }
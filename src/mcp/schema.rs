@@ -0,0 +1,244 @@
+//! JSON Schemas for the `structuredContent` returned by each tool.
+//!
+//! They describe the serde representation of the types in `crate::types` and
+//! are advertised as `outputSchema` in `tools/list`. Schemas stay open
+//! (no `additionalProperties: false`) so new fields don't break clients.
+
+use serde_json::{json, Value};
+
+pub(super) fn code_pattern() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "id": { "type": "string" },
+            "category": { "type": "string" },
+            "framework": { "type": "string" },
+            "version": { "type": "string" },
+            "title": { "type": "string" },
+            "description": { "type": "string" },
+            "code": { "type": "string" },
+            "tags": { "type": "array", "items": { "type": "string" } },
+            "usage_count": { "type": "integer" },
+            "relevance_score": { "type": "number" },
+            "created_at": { "type": "string", "format": "date-time" },
+            "updated_at": { "type": "string", "format": "date-time" }
+        },
+        "required": ["id", "category", "framework", "title", "description", "code", "tags"]
+    })
+}
+
+fn symbol() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "name": { "type": "string" },
+            "kind": {
+                "description": "Symbol kind name, or { \"Other\": <kind> } for language-specific kinds"
+            },
+            "modifiers": { "type": "array", "items": { "type": "string" } },
+            "children": { "type": "array", "items": { "type": "object" } },
+            "type_name": { "type": "string" },
+            "bases": { "type": "array", "items": { "type": "string" } },
+            "parameters": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "param_type": { "type": "string" },
+                        "modifiers": { "type": "array", "items": { "type": "string" } }
+                    },
+                    "required": ["name", "param_type"]
+                }
+            },
+            "location": {
+                "type": "object",
+                "description": "1-based span of the declaration; columns count bytes",
+                "properties": {
+                    "start_line": { "type": "integer" },
+                    "start_column": { "type": "integer" },
+                    "end_line": { "type": "integer" },
+                    "end_column": { "type": "integer" }
+                },
+                "required": ["start_line", "start_column", "end_line", "end_column"]
+            }
+        },
+        "required": ["name", "kind"]
+    })
+}
+
+fn project() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "path": { "type": "string" },
+            "name": { "type": "string" },
+            "project_type": {
+                "type": "string",
+                "enum": ["DotNet", "Rust", "Node", "Python", "Go", "Java", "Php", "Unknown"]
+            },
+            "version": { "type": ["string", "null"] },
+            "dependencies": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "version": { "type": "string" },
+                        "dev_only": { "type": "boolean" },
+                        "resolved": { "type": ["string", "null"] },
+                        "transitive": { "type": "boolean" }
+                    },
+                    "required": ["name", "version", "dev_only"]
+                }
+            },
+            "files": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "path": { "type": "string" },
+                        "language": { "type": "string" },
+                        "size_bytes": { "type": "integer" },
+                        "symbols": { "type": "array", "items": symbol() }
+                    },
+                    "required": ["path", "language", "size_bytes", "symbols"]
+                }
+            },
+            "metadata": {
+                "type": "object",
+                "properties": {
+                    "target_framework": { "type": ["string", "null"] },
+                    "target_frameworks": { "type": "array", "items": { "type": "string" } },
+                    "sdk": { "type": ["string", "null"] },
+                    "output_type": { "type": ["string", "null"] },
+                    "nullable": { "type": ["string", "null"] },
+                    "lang_version": { "type": ["string", "null"] },
+                    "implicit_usings": { "type": ["string", "null"] },
+                    "node_version": { "type": ["string", "null"] },
+                    "python_version": { "type": ["string", "null"] },
+                    "rust_edition": { "type": ["string", "null"] },
+                    "entry_point": { "type": ["string", "null"] },
+                    "build_command": { "type": ["string", "null"] },
+                    "extra": {
+                        "type": "object",
+                        "additionalProperties": { "type": "string" }
+                    }
+                }
+            },
+            "subprojects": {
+                "type": "array",
+                "description": "Projects of a solution, workspace members and nested projects of other types, each shaped like this object",
+                "items": { "type": "object" }
+            },
+            "references": { "type": "array", "items": { "type": "string" } }
+        },
+        "required": ["path", "name", "project_type", "dependencies", "files", "metadata"]
+    })
+}
+
+pub(super) fn analysis_result() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "project": project(),
+            "patterns": { "type": "array", "items": code_pattern() },
+            "suggestions": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "severity": { "type": "string", "enum": ["Info", "Warning", "Error"] },
+                        "category": { "type": "string" },
+                        "message": { "type": "string" },
+                        "file": { "type": ["string", "null"] },
+                        "line": { "type": ["integer", "null"] }
+                    },
+                    "required": ["severity", "category", "message"]
+                }
+            },
+            "statistics": {
+                "type": "object",
+                "properties": {
+                    "total_files": { "type": "integer" },
+                    "total_classes": { "type": "integer" },
+                    "total_methods": { "type": "integer" },
+                    "total_lines": { "type": "integer" },
+                    "framework_version": { "type": "string" },
+                    "package_count": { "type": "integer" }
+                },
+                "required": ["total_files", "total_classes", "total_methods", "package_count"]
+            }
+        },
+        "required": ["project", "patterns", "suggestions", "statistics"]
+    })
+}
+
+pub(super) fn pattern_list() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "framework": { "type": "string" },
+            "category": { "type": ["string", "null"] },
+            "patterns": { "type": "array", "items": code_pattern() }
+        },
+        "required": ["framework", "patterns"]
+    })
+}
+
+pub(super) fn search_results() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "total": { "type": "integer" },
+            "results": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "pattern": code_pattern(),
+                        "score": { "type": "number" }
+                    },
+                    "required": ["pattern", "score"]
+                }
+            }
+        },
+        "required": ["total", "results"]
+    })
+}
+
+pub(super) fn train_result() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "pattern": code_pattern(),
+            "uri": { "type": "string" }
+        },
+        "required": ["pattern", "uri"]
+    })
+}
+
+pub(super) fn statistics() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "total_patterns": { "type": "integer" },
+            "total_usage": { "type": "integer" },
+            "avg_relevance": { "type": "number" },
+            "categories": { "type": "array", "items": { "type": "string" } },
+            "frameworks": { "type": "array", "items": { "type": "string" } }
+        },
+        "required": ["total_patterns", "total_usage", "avg_relevance", "categories", "frameworks"]
+    })
+}
+
+pub(super) fn help() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "tools": { "type": "array", "items": { "type": "string" } },
+            "text": { "type": "string" }
+        },
+        "required": ["tools", "text"]
+    })
+}
@@ -0,0 +1,106 @@
+//! Watches the pattern directory and reloads changed pattern files.
+//!
+//! Editors tend to produce bursts of events for a single save (truncate,
+//! write, rename), so events are collected for a short quiet period and each
+//! affected `.json` file is reloaded once.
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use super::Server;
+
+/// Quiet period after the last file event before reloading
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Starts watching the server's pattern directory in the background.
+/// Does nothing (besides logging) if the directory cannot be watched.
+pub(super) fn spawn(server: Arc<Server>) {
+    let (events_tx, mut events) = mpsc::unbounded_channel::<notify::Result<notify::Event>>();
+
+    let mut watcher = match RecommendedWatcher::new(
+        move |event| {
+            let _ = events_tx.send(event);
+        },
+        notify::Config::default(),
+    ) {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            tracing::warn!("Pattern watcher unavailable: {}", e);
+            return;
+        }
+    };
+
+    tokio::spawn(async move {
+        let patterns_path = server
+            .training_manager
+            .read()
+            .await
+            .storage_path()
+            .to_path_buf();
+        if let Err(e) = watcher.watch(&patterns_path, RecursiveMode::Recursive) {
+            tracing::warn!(
+                "Not watching pattern directory {}: {}",
+                patterns_path.display(),
+                e
+            );
+            return;
+        }
+        tracing::info!("Watching {} for pattern changes", patterns_path.display());
+
+        // The watcher lives as long as this task; the loop ends when it is dropped
+        while let Some(event) = events.recv().await {
+            let mut changed = BTreeSet::new();
+            collect_pattern_files(event, &mut changed);
+
+            // Let the burst settle before touching the files
+            while let Ok(Some(event)) = tokio::time::timeout(DEBOUNCE, events.recv()).await {
+                collect_pattern_files(event, &mut changed);
+            }
+
+            for path in changed {
+                server.reload_pattern_file(&path).await;
+            }
+        }
+    });
+}
+
+impl Server {
+    /// Reloads one pattern file and tells clients what changed. A file that
+    /// fails to load is reported to clients, and its patterns stay as they were.
+    pub(super) async fn reload_pattern_file(&self, path: &Path) {
+        let changes = match self.training_manager.write().await.reload_file(path) {
+            Ok(changes) => changes,
+            Err(e) => {
+                tracing::warn!("Keeping previous patterns: {:#}", e);
+                return;
+            }
+        };
+        self.notify_patterns_changed(&changes);
+    }
+}
+
+fn collect_pattern_files(event: notify::Result<notify::Event>, changed: &mut BTreeSet<PathBuf>) {
+    let event = match event {
+        Ok(event) => event,
+        Err(e) => {
+            tracing::error!("Pattern watcher error: {}", e);
+            return;
+        }
+    };
+
+    // Access events (e.g. a client reading a file) don't change anything
+    if event.kind.is_access() {
+        return;
+    }
+
+    changed.extend(
+        event
+            .paths
+            .into_iter()
+            .filter(|p| p.extension().and_then(|s| s.to_str()) == Some("json")),
+    );
+}
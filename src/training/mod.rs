@@ -74,6 +74,8 @@ fn sanitize_framework_name(framework: &str) -> Result<String, String> {
 pub struct TrainingManager {
     patterns: Vec<CodePattern>,
     storage_path: PathBuf,
+    /// Ids of the patterns loaded from each pattern file, for incremental reloads
+    pattern_files: HashMap<PathBuf, Vec<String>>,
     // Index for fast lookups
     category_index: HashMap<String, Vec<usize>>,
     framework_index: HashMap<String, Vec<usize>>,
@@ -89,11 +91,28 @@ pub struct SearchCriteria {
     pub min_score: f32,
 }
 
+/// Effect of reloading one pattern file
+#[derive(Debug, Clone, Default)]
+pub struct PatternChanges {
+    pub added: Vec<CodePattern>,
+    /// New versions of patterns whose content changed
+    pub updated: Vec<CodePattern>,
+    /// Last known versions of patterns that are gone
+    pub removed: Vec<CodePattern>,
+}
+
+impl PatternChanges {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.updated.is_empty() && self.removed.is_empty()
+    }
+}
+
 impl TrainingManager {
     pub fn new(storage_path: impl Into<PathBuf>) -> Self {
         Self {
             patterns: vec![],
             storage_path: storage_path.into(),
+            pattern_files: HashMap::new(),
             category_index: HashMap::new(),
             framework_index: HashMap::new(),
         }
@@ -101,6 +120,7 @@ impl TrainingManager {
 
     pub async fn load_patterns(&mut self) -> Result<()> {
         self.patterns.clear();
+        self.pattern_files.clear();
         self.category_index.clear();
         self.framework_index.clear();
 
@@ -138,6 +158,18 @@ impl TrainingManager {
     }
 
     fn load_pattern_file(&mut self, path: &Path) -> Result<()> {
+        let patterns = Self::read_pattern_file(path)?;
+
+        self.pattern_files.insert(
+            path.to_path_buf(),
+            patterns.iter().map(|p| p.id.clone()).collect(),
+        );
+        self.patterns.extend(patterns);
+
+        Ok(())
+    }
+
+    fn read_pattern_file(path: &Path) -> Result<Vec<CodePattern>> {
         let content = fs::read_to_string(path).context("Failed to read pattern file")?;
 
         #[derive(serde::Deserialize)]
@@ -148,11 +180,69 @@ impl TrainingManager {
         let file: PatternFile =
             serde_json::from_str(&content).context("Failed to parse pattern JSON")?;
 
-        for pattern in file.patterns {
-            self.patterns.push(pattern);
+        Ok(file.patterns)
+    }
+
+    /// Re-reads a single pattern file after it was created, modified or
+    /// deleted, replacing only the patterns that came from it.
+    ///
+    /// Patterns are matched by id, so a pattern added in memory and later
+    /// saved to this file is not duplicated. If the file cannot be parsed
+    /// (e.g. it is half-written) the current patterns are kept.
+    pub fn reload_file(&mut self, path: &Path) -> Result<PatternChanges> {
+        let new_patterns = if path.exists() {
+            Self::read_pattern_file(path)
+                .context(format!("Failed to load pattern file: {:?}", path))?
+        } else {
+            Vec::new()
+        };
+
+        let previous_ids = self.pattern_files.remove(path).unwrap_or_default();
+        let new_ids: HashSet<&str> = new_patterns.iter().map(|p| p.id.as_str()).collect();
+
+        let mut changes = PatternChanges::default();
+        let mut previous: HashMap<String, CodePattern> = HashMap::new();
+        let mut kept = Vec::with_capacity(self.patterns.len());
+        for pattern in std::mem::take(&mut self.patterns) {
+            if new_ids.contains(pattern.id.as_str()) || previous_ids.contains(&pattern.id) {
+                previous.insert(pattern.id.clone(), pattern);
+            } else {
+                kept.push(pattern);
+            }
+        }
+        self.patterns = kept;
+
+        for pattern in &new_patterns {
+            match previous.remove(&pattern.id) {
+                None => changes.added.push(pattern.clone()),
+                Some(old) if old != *pattern => changes.updated.push(pattern.clone()),
+                Some(_) => {}
+            }
         }
+        changes.removed.extend(previous.into_values());
 
-        Ok(())
+        if !new_patterns.is_empty() {
+            self.pattern_files.insert(
+                path.to_path_buf(),
+                new_patterns.iter().map(|p| p.id.clone()).collect(),
+            );
+        }
+        self.patterns.extend(new_patterns);
+        self.rebuild_indexes();
+
+        tracing::info!(
+            "Reloaded {:?}: {} added, {} updated, {} removed",
+            path,
+            changes.added.len(),
+            changes.updated.len(),
+            changes.removed.len()
+        );
+        Ok(changes)
+    }
+
+    /// Directory the patterns are loaded from and saved to
+    pub fn storage_path(&self) -> &Path {
+        &self.storage_path
     }
 
     fn rebuild_indexes(&mut self) {
@@ -258,16 +348,17 @@ impl TrainingManager {
         Ok(())
     }
 
-    /// Adds a new pattern to the manager.
+    /// Adds a new pattern to the manager, or replaces the one with the same
+    /// framework and id.
     ///
     /// # Security
     /// The pattern's framework, id, and category are validated to prevent
     /// path traversal and other injection attacks.
     ///
     /// # Returns
-    /// - `Ok(())` if the pattern was added successfully
+    /// - `Ok(true)` if the pattern was added, `Ok(false)` if it replaced one
     /// - `Err` if the pattern contains invalid data
-    pub fn add_pattern(&mut self, mut pattern: CodePattern) -> Result<(), String> {
+    pub fn add_pattern(&mut self, mut pattern: CodePattern) -> Result<bool, String> {
         // SECURITY: Validate pattern before adding
         Self::validate_pattern(&pattern)?;
 
@@ -277,6 +368,17 @@ impl TrainingManager {
         }
         pattern.updated_at = Utc::now();
 
+        if let Some(existing) = self
+            .patterns
+            .iter_mut()
+            .find(|p| p.framework == pattern.framework && p.id == pattern.id)
+        {
+            pattern.created_at = existing.created_at;
+            *existing = pattern;
+            self.rebuild_indexes();
+            return Ok(false);
+        }
+
         let idx = self.patterns.len();
         self.patterns.push(pattern.clone());
 
@@ -291,7 +393,7 @@ impl TrainingManager {
             .or_default()
             .push(idx);
 
-        Ok(())
+        Ok(true)
     }
 
     pub fn search_patterns(&self, criteria: &SearchCriteria) -> Vec<(&CodePattern, f32)> {
@@ -302,6 +404,7 @@ impl TrainingManager {
             if let Some(indices) = self.framework_index.get(framework) {
                 candidates = indices.clone();
             } else {
+                tracing::debug!("No patterns for framework '{}'", framework);
                 return vec![]; // No patterns for this framework
             }
         } else {
@@ -314,6 +417,7 @@ impl TrainingManager {
                 let cat_set: HashSet<usize> = cat_indices.iter().copied().collect();
                 candidates.retain(|idx| cat_set.contains(idx));
             } else {
+                tracing::debug!("No patterns for category '{}'", category);
                 return vec![]; // No patterns for this category
             }
         }
@@ -326,7 +430,17 @@ impl TrainingManager {
                 let score = self.score_pattern(pattern, criteria);
                 (pattern, score)
             })
-            .filter(|(_, score)| *score >= criteria.min_score)
+            .filter(|(pattern, score)| {
+                if *score < criteria.min_score {
+                    tracing::debug!(
+                        "Pattern '{}' scored {:.2}, below min_score {:.2}",
+                        pattern.id,
+                        score,
+                        criteria.min_score
+                    );
+                }
+                *score >= criteria.min_score
+            })
             .collect();
 
         // Sort by score descending
@@ -412,6 +526,14 @@ impl TrainingManager {
         self.patterns.iter().find(|p| p.id == id)
     }
 
+    /// Get the pattern a `pattern://{framework}/{id}` URI names; the same ID
+    /// may exist under several frameworks
+    pub fn get_pattern(&self, framework: &str, id: &str) -> Option<&CodePattern> {
+        self.patterns
+            .iter()
+            .find(|p| p.framework == framework && p.id == id)
+    }
+
     /// Update pattern usage count
     #[allow(dead_code)]
     pub fn increment_usage(&mut self, pattern_id: &str) -> Result<()> {
@@ -45,14 +45,51 @@ pub struct Project {
     pub files: Vec<SourceFile>,
     /// Language-specific metadata
     pub metadata: ProjectMetadata,
+    /// Projects of a solution, workspace members and nested projects of
+    /// other types; their files are not repeated in `files`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subprojects: Vec<Project>,
+    /// Names of the sibling projects this one references (`ProjectReference`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub references: Vec<String>,
+}
+
+impl Project {
+    /// Source files of this project and of all its sub-projects
+    pub fn all_files(&self) -> Vec<&SourceFile> {
+        let mut files: Vec<&SourceFile> = self.files.iter().collect();
+        for subproject in &self.subprojects {
+            files.extend(subproject.all_files());
+        }
+        files
+    }
+
+    /// Dependencies the project declares, without transitive ones
+    pub fn direct_dependencies(&self) -> impl Iterator<Item = &Dependency> {
+        self.dependencies.iter().filter(|d| !d.transitive)
+    }
 }
 
 /// Generic dependency
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Dependency {
     pub name: String,
+    /// Version or range as declared, or as locked for a transitive dependency
     pub version: String,
     pub dev_only: bool,
+    /// Version the lockfile pins
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved: Option<String>,
+    /// Required by another dependency rather than by the project itself
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub transitive: bool,
+}
+
+impl Dependency {
+    /// The locked version when known, else the declared one
+    pub fn version_in_use(&self) -> &str {
+        self.resolved.as_deref().unwrap_or(&self.version)
+    }
 }
 
 /// Generic source file
@@ -72,9 +109,31 @@ pub struct Symbol {
     pub kind: SymbolKind,
     pub modifiers: Vec<String>,
     pub children: Vec<Symbol>,
+    /// Declared type: a method's return type, a property's or field's type
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub type_name: Option<String>,
+    /// Base class and implemented interfaces, as written
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bases: Vec<String>,
+    /// Parameters of methods and functions, where the extractor records them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<Parameter>,
+    /// Where the declaration is, when the extractor knows
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<Location>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Span of a declaration in its file. Lines and columns are 1-based;
+/// columns count bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Location {
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum SymbolKind {
     Class,
     Interface,
@@ -91,11 +150,85 @@ pub enum SymbolKind {
     Other(String),
 }
 
+impl SymbolKind {
+    /// Type declarations, counted as classes in the statistics
+    pub fn is_type(&self) -> bool {
+        matches!(
+            self,
+            SymbolKind::Class
+                | SymbolKind::Interface
+                | SymbolKind::Enum
+                | SymbolKind::Struct
+                | SymbolKind::Trait
+                | SymbolKind::Component
+        )
+    }
+
+    /// Functions and methods, counted as methods in the statistics
+    pub fn is_callable(&self) -> bool {
+        matches!(self, SymbolKind::Function | SymbolKind::Method)
+    }
+}
+
+impl Symbol {
+    /// Counts the symbols in `symbols` and all their children whose kind
+    /// matches `predicate`
+    pub fn count(symbols: &[Symbol], predicate: fn(&SymbolKind) -> bool) -> usize {
+        symbols
+            .iter()
+            .map(|s| usize::from(predicate(&s.kind)) + Self::count(&s.children, predicate))
+            .sum()
+    }
+
+    /// Visits the symbols in `symbols` and all their children, parents first
+    pub fn walk<'a>(symbols: &'a [Symbol], visit: &mut dyn FnMut(&'a Symbol)) {
+        for symbol in symbols {
+            visit(symbol);
+            Self::walk(&symbol.children, visit);
+        }
+    }
+
+    /// Whether an annotation, attribute or decorator modifier is named
+    /// `name`, ignoring its arguments. `@Service` matches `@Service`;
+    /// `[Parameter]` and `Parameter` match a `[Parameter]` attribute.
+    pub fn has_annotation(&self, name: &str) -> bool {
+        let name = annotation_name(name);
+        self.modifiers
+            .iter()
+            .any(|m| (m.starts_with('@') || m.starts_with('[')) && annotation_name(m) == name)
+    }
+}
+
+/// `@Service` → `Service`, `[Route("/x")]` → `Route`
+fn annotation_name(annotation: &str) -> &str {
+    let name = annotation.trim_start_matches(['@', '[']);
+    name.split(['(', ']']).next().unwrap_or(name).trim()
+}
+
 /// Language-specific metadata
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ProjectMetadata {
     /// For .NET: target framework (net8.0, etc.)
     pub target_framework: Option<String>,
+    /// For .NET: every target framework of a multi-targeting project, the
+    /// first one being `target_framework`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub target_frameworks: Vec<String>,
+    /// For .NET: project SDK (Microsoft.NET.Sdk.Web, Microsoft.NET.Sdk.Worker, etc.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sdk: Option<String>,
+    /// For .NET: `OutputType` (Exe, Library, WinExe)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_type: Option<String>,
+    /// For .NET: nullable context (enable, disable, warnings, annotations)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nullable: Option<String>,
+    /// For .NET: C# language version (12, latest, preview, etc.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lang_version: Option<String>,
+    /// For .NET: `ImplicitUsings` (enable, disable)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub implicit_usings: Option<String>,
     /// For Node: node version
     pub node_version: Option<String>,
     /// For Python: python version
@@ -111,73 +244,111 @@ pub struct ProjectMetadata {
 }
 
 // ============================================================================
-// Legacy .NET-specific types (kept for compatibility)
+// C# parser output, mapped into `Symbol`s by the generic analyzer
 // ============================================================================
 
-/// Represents a .NET project
-#[allow(dead_code)]
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DotNetProject {
-    pub path: PathBuf,
-    pub name: String,
-    pub target_framework: String,
-    pub language_version: String,
-    pub packages: Vec<NuGetPackage>,
-    pub project_references: Vec<PathBuf>,
-    pub files: Vec<CSharpFile>,
-}
-
-/// NuGet package reference
-#[allow(dead_code)]
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct NuGetPackage {
-    pub name: String,
-    pub version: String,
-}
-
 /// C# source file
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CSharpFile {
     pub path: PathBuf,
+    /// First namespace declared in the file
     pub namespace: Option<String>,
     pub usings: Vec<String>,
     pub classes: Vec<ClassInfo>,
     pub interfaces: Vec<InterfaceInfo>,
+    pub enums: Vec<EnumInfo>,
 }
 
-/// Class information
+/// Which kind of C# type a [`ClassInfo`] describes
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum TypeKind {
+    Class,
+    Struct,
+    Record,
+    RecordStruct,
+}
+
+/// Class, struct or record information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClassInfo {
     pub name: String,
+    /// Enclosing namespace, nested ones joined with `.`
+    pub namespace: Option<String>,
+    pub kind: TypeKind,
     pub modifiers: Vec<String>,
+    /// Attributes as written, without brackets (e.g. `Route("/counter")`)
+    pub attributes: Vec<String>,
+    pub type_parameters: Vec<String>,
     pub base_class: Option<String>,
     pub interfaces: Vec<String>,
+    pub constructors: Vec<MethodInfo>,
     pub methods: Vec<MethodInfo>,
     pub properties: Vec<PropertyInfo>,
+    pub fields: Vec<FieldInfo>,
+    pub events: Vec<FieldInfo>,
+    pub location: Location,
 }
 
 /// Interface information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InterfaceInfo {
     pub name: String,
+    /// Enclosing namespace, nested ones joined with `.`
+    pub namespace: Option<String>,
+    pub modifiers: Vec<String>,
+    pub attributes: Vec<String>,
+    pub type_parameters: Vec<String>,
+    /// Extended interfaces
+    pub interfaces: Vec<String>,
     pub methods: Vec<MethodInfo>,
+    pub properties: Vec<PropertyInfo>,
+    pub location: Location,
+}
+
+/// Enum information
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnumInfo {
+    pub name: String,
+    /// Enclosing namespace, nested ones joined with `.`
+    pub namespace: Option<String>,
+    pub modifiers: Vec<String>,
+    pub attributes: Vec<String>,
+    /// Explicit underlying type (`enum Flags : byte`)
+    pub underlying_type: Option<String>,
+    pub members: Vec<EnumMemberInfo>,
+    pub location: Location,
 }
 
-/// Method information
+/// Enum member information
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnumMemberInfo {
+    pub name: String,
+    pub location: Location,
+}
+
+/// Method or constructor information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MethodInfo {
     pub name: String,
+    /// Return type as written (`Task<User?>`); empty for constructors
     pub return_type: String,
     pub parameters: Vec<Parameter>,
     pub modifiers: Vec<String>,
+    pub attributes: Vec<String>,
+    pub type_parameters: Vec<String>,
     pub is_async: bool,
+    pub location: Location,
 }
 
 /// Parameter information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Parameter {
     pub name: String,
+    /// Type as written, including nullable annotations (`string?`)
     pub param_type: String,
+    /// `ref`, `out`, `in`, `params`, `this`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modifiers: Vec<String>,
 }
 
 /// Property information
@@ -185,12 +356,26 @@ pub struct Parameter {
 pub struct PropertyInfo {
     pub name: String,
     pub prop_type: String,
+    pub modifiers: Vec<String>,
+    pub attributes: Vec<String>,
     pub has_getter: bool,
+    /// `set` or `init` accessor
     pub has_setter: bool,
+    pub location: Location,
 }
 
-/// Code pattern for training
+/// Field or event information
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldInfo {
+    pub name: String,
+    pub field_type: String,
+    pub modifiers: Vec<String>,
+    pub attributes: Vec<String>,
+    pub location: Location,
+}
+
+/// Code pattern for training
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CodePattern {
     pub id: String,
     pub category: String,
@@ -215,16 +400,6 @@ pub struct AnalysisResult {
     pub statistics: Statistics,
 }
 
-/// Legacy analysis result for .NET (kept for compatibility)
-#[allow(dead_code)]
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DotNetAnalysisResult {
-    pub project: DotNetProject,
-    pub patterns: Vec<CodePattern>,
-    pub suggestions: Vec<Suggestion>,
-    pub statistics: Statistics,
-}
-
 /// Code suggestion
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Suggestion {
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use mcp_context_rust::analyzer::{CSharpAnalyzer, ProjectAnalyzer};
+use mcp_context_rust::analyzer::{CSharpAnalyzer, GenericAnalyzer};
 use std::fs;
 
 #[tokio::test]
@@ -82,10 +82,13 @@ namespace MyApp.Components
 }
 
 #[tokio::test]
-async fn test_project_analyzer() -> Result<()> {
+async fn test_dotnet_project_analysis() -> Result<()> {
+    use mcp_context_rust::context::ContextBuilder;
+
     // Create a temporary project structure
     let temp_dir = tempfile::tempdir()?;
-    let project_path = temp_dir.path();
+    let project_path = &temp_dir.path().join("TestProject");
+    fs::create_dir_all(project_path)?;
 
     // Create a sample .csproj file
     let csproj_content = r#"<Project Sdk="Microsoft.NET.Sdk.Web">
@@ -107,30 +110,796 @@ async fn test_project_analyzer() -> Result<()> {
     fs::create_dir_all(project_path.join("Services"))?;
     fs::write(
         project_path.join("Services/DataService.cs"),
-        "namespace MyApp.Services { public class DataService { } }",
+        "namespace MyApp.Services { public class DataService { public async void Save() { } } }",
+    )?;
+    fs::create_dir_all(project_path.join("Components"))?;
+    fs::write(
+        project_path.join("Components/Counter.cs"),
+        r#"namespace MyApp.Components;
+
+public sealed class Counter : ComponentBase, IDisposable
+{
+    [Parameter] public int Start { get; set; }
+
+    protected override void OnInitialized() { }
+    public void Dispose() { }
+}"#,
     )?;
 
     // Analyze the project
-    let analyzer = ProjectAnalyzer::new(vec![]);
-    let result = analyzer.analyze(project_path).await?;
+    let project = GenericAnalyzer::analyze(project_path)?;
+
+    assert_eq!(project.name, "TestProject");
+    assert_eq!(
+        project.metadata.target_framework.as_deref(),
+        Some("net10.0")
+    );
+    assert_eq!(project.metadata.target_frameworks, vec!["net10.0"]);
+    assert_eq!(
+        project.metadata.sdk.as_deref(),
+        Some("Microsoft.NET.Sdk.Web")
+    );
+    assert_eq!(project.metadata.nullable.as_deref(), Some("enable"));
+    assert_eq!(project.metadata.implicit_usings.as_deref(), Some("enable"));
+    assert_eq!(project.dependencies.len(), 2);
+    assert!(project
+        .dependencies
+        .iter()
+        .any(|d| d.name == "Microsoft.AspNetCore.Components"));
+
+    // The C# parser's output reaches the generic symbols
+    let file = |name: &str| {
+        project
+            .files
+            .iter()
+            .find(|f| f.path.ends_with(name))
+            .expect(name)
+    };
+    let namespace = &file("DataService.cs").symbols[0];
+    assert_eq!(namespace.name, "MyApp.Services");
+    assert_eq!(namespace.children[0].name, "DataService");
+
+    let counter = &file("Counter.cs").symbols[0].children[0];
+    assert_eq!(counter.bases, vec!["ComponentBase", "IDisposable"]);
+    assert!(counter.children[0].has_annotation("Parameter"));
+    assert_eq!(counter.children[0].type_name.as_deref(), Some("int"));
+
+    let analysis = ContextBuilder::new()
+        .build_generic_analysis(project)
+        .await?;
+    assert_eq!(analysis.statistics.total_classes, 2);
+    assert_eq!(analysis.statistics.total_methods, 3);
+    let lifecycle = analysis
+        .suggestions
+        .iter()
+        .find(|s| s.category == "blazor-lifecycle" && s.message.contains("'Counter'"))
+        .expect("blazor-lifecycle suggestion");
+    assert_eq!(lifecycle.line, Some(7));
+
+    let context = ContextBuilder::new().build_generic_context_string(&analysis);
+    assert!(context.contains("**SDK:** Microsoft.NET.Sdk.Web"));
+    assert!(context.contains("**Build:** Nullable enable, ImplicitUsings enable"));
+    assert!(context.contains("- public sealed class Counter : ComponentBase, IDisposable"));
+    assert!(context.contains("- [Parameter] public property Start: int"));
+    assert!(context.contains("- protected override method OnInitialized(): void"));
+    assert!(context.contains("(`Components/Counter.cs:7`)"));
+    assert!(analysis
+        .suggestions
+        .iter()
+        .any(|s| s.category == "async-patterns" && s.message.contains("'Save'")));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_generic_analyzer_reports_progress_and_cancels() -> Result<()> {
+    use mcp_context_rust::analyzer::{AnalysisControl, AnalysisPhase};
+    use std::sync::{Arc, Mutex};
+
+    let temp_dir = tempfile::tempdir()?;
+    let project_path = &temp_dir.path().join("web");
+    fs::create_dir_all(project_path)?;
+    fs::write(project_path.join("package.json"), r#"{"name": "web"}"#)?;
+    fs::create_dir_all(project_path.join("src"))?;
+    fs::write(project_path.join("src/index.js"), "export default 1;")?;
+    fs::write(project_path.join("src/app.ts"), "export const app = 1;")?;
+
+    let updates = Arc::new(Mutex::new(Vec::new()));
+    let sink = updates.clone();
+    let control = AnalysisControl::new().with_progress(move |p| sink.lock().unwrap().push(*p));
+
+    let project = GenericAnalyzer::analyze_with(project_path, &control)?;
+    assert_eq!(project.files.len(), 2);
+
+    let updates = updates.lock().unwrap().clone();
+    assert_eq!(updates.first().unwrap().phase, AnalysisPhase::Detecting);
+    assert!(updates
+        .iter()
+        .any(|p| p.phase == AnalysisPhase::Discovering && p.files_seen == 2));
+    assert!(updates
+        .iter()
+        .any(|p| p.phase == AnalysisPhase::Parsing && p.files_parsed == 2));
+    let last = updates.last().unwrap();
+    assert_eq!(last.phase, AnalysisPhase::Done);
+    assert_eq!((last.files_seen, last.files_parsed), (2, 2));
+
+    // A cancelled control aborts the walk
+    let cancelled = AnalysisControl::new();
+    cancelled.cancel();
+    assert!(GenericAnalyzer::analyze_with(project_path, &cancelled).is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_generic_analyzer_extracts_rust_symbols() -> Result<()> {
+    use mcp_context_rust::context::ContextBuilder;
+
+    let temp_dir = tempfile::tempdir()?;
+    let project_path = &temp_dir.path().join("service");
+    fs::create_dir_all(project_path.join("src"))?;
+    fs::write(
+        project_path.join("Cargo.toml"),
+        "[package]\nname = \"service\"\nversion = \"0.1.0\"\n",
+    )?;
+    fs::write(
+        project_path.join("src/lib.rs"),
+        r#"
+pub struct Order { id: u64 }
+pub trait Repository { fn find(&self, id: u64) -> Option<Order>; }
+impl Repository for Order { fn find(&self, _id: u64) -> Option<Order> { None } }
+pub fn total() -> u64 { 0 }
+"#,
+    )?;
+
+    let project = GenericAnalyzer::analyze(project_path)?;
+    let symbols = &project.files[0].symbols;
+    assert_eq!(symbols.len(), 4);
+    assert_eq!(symbols[2].name, "Repository for Order");
+
+    let analysis = ContextBuilder::new()
+        .build_generic_analysis(project)
+        .await?;
+    assert_eq!(analysis.statistics.total_classes, 2);
+    assert_eq!(analysis.statistics.total_methods, 3);
+
+    let context = ContextBuilder::new().build_generic_context_string(&analysis);
+    assert!(context.contains("## Code Outline"));
+    assert!(context.contains("- pub struct Order"));
+    assert!(context.contains("- impl Repository for Order"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_generic_analyzer_lists_frontend_components() -> Result<()> {
+    use mcp_context_rust::context::ContextBuilder;
+
+    let temp_dir = tempfile::tempdir()?;
+    let project_path = &temp_dir.path().join("web");
+    fs::create_dir_all(project_path.join("src/components"))?;
+    fs::write(
+        project_path.join("package.json"),
+        r#"{ "name": "web", "version": "1.0.0", "dependencies": { "react": "^18.0.0" } }"#,
+    )?;
+    fs::write(
+        project_path.join("src/components/Header.tsx"),
+        r#"
+export interface HeaderProps { title: string }
+export function Header({ title }: HeaderProps) { return <h1>{title}</h1>; }
+"#,
+    )?;
+    fs::write(
+        project_path.join("src/components/Counter.vue"),
+        "<template><p /></template>\n<script setup>\nconst step = 1;\n</script>\n",
+    )?;
+
+    let project = GenericAnalyzer::analyze(project_path)?;
+    let analysis = ContextBuilder::new()
+        .build_generic_analysis(project)
+        .await?;
+    let context = ContextBuilder::new().build_generic_context_string(&analysis);
+
+    assert!(context.contains("- export component Header"));
+    assert!(context.contains("- export interface HeaderProps"));
+    assert!(context.contains("- setup component Counter"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_generic_analyzer_detects_spring_stereotypes() -> Result<()> {
+    use mcp_context_rust::context::ContextBuilder;
 
-    println!("\n=== Project Analysis Result ===");
-    println!("Project: {}", result.name);
-    println!("Framework: {}", result.target_framework);
-    println!("Packages: {}", result.packages.len());
+    let temp_dir = tempfile::tempdir()?;
+    let project_path = &temp_dir.path().join("users");
+    let package_dir = project_path.join("src/main/java/com/example");
+    fs::create_dir_all(&package_dir)?;
+    fs::write(
+        project_path.join("pom.xml"),
+        "<project><artifactId>users</artifactId><version>1.0</version></project>",
+    )?;
+    fs::write(
+        package_dir.join("UserController.java"),
+        r#"
+package com.example;
+
+@RestController
+public class UserController {
+    @Autowired
+    private UserService service;
+
+    @GetMapping("/users")
+    public List<User> list() { return service.findAll(); }
+}
+"#,
+    )?;
 
-    for package in &result.packages {
-        println!("  - {} ({})", package.name, package.version);
+    let project = GenericAnalyzer::analyze(project_path)?;
+    let analysis = ContextBuilder::new()
+        .build_generic_analysis(project)
+        .await?;
+
+    // No Spring dependency is declared; the stereotype alone identifies it
+    let injection = analysis
+        .suggestions
+        .iter()
+        .find(|s| s.message.contains("@Autowired"))
+        .expect("field injection suggestion");
+    assert!(injection.message.contains("(service)"));
+    assert!(injection
+        .file
+        .as_ref()
+        .is_some_and(|f| f.ends_with("UserController.java")));
+
+    let context = ContextBuilder::new().build_generic_context_string(&analysis);
+    assert!(context.contains("- @RestController public class UserController"));
+    assert!(context.contains("- @GetMapping(\"/users\") public method list"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_generic_analyzer_parses_razor_components() -> Result<()> {
+    use mcp_context_rust::context::ContextBuilder;
+
+    let temp_dir = tempfile::tempdir()?;
+    let project_path = &temp_dir.path().join("BlazorApp");
+    fs::create_dir_all(project_path.join("Pages"))?;
+    fs::write(
+        project_path.join("BlazorApp.csproj"),
+        r#"<Project Sdk="Microsoft.NET.Sdk.Web">
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+  </PropertyGroup>
+</Project>"#,
+    )?;
+    fs::write(
+        project_path.join("Pages/FetchData.razor"),
+        r#"@page "/fetchdata"
+@inject IWeatherService Weather
+
+<WeatherTable Items="forecasts" />
+
+@code {
+    private WeatherForecast[]? forecasts;
+
+    protected override void OnInitialized()
+    {
+        forecasts = Weather.GetForecasts();
     }
+}
+"#,
+    )?;
 
-    // Assertions
-    assert_eq!(result.name, "TestProject");
-    assert_eq!(result.target_framework, "net10.0");
-    assert_eq!(result.packages.len(), 2);
-    assert!(result
-        .packages
+    let project = GenericAnalyzer::analyze(project_path)?;
+    let analysis = ContextBuilder::new()
+        .build_generic_analysis(project)
+        .await?;
+
+    // No Components package reference: the .razor file identifies Blazor
+    let lifecycle = analysis
+        .suggestions
+        .iter()
+        .find(|s| s.category == "blazor-lifecycle")
+        .expect("blazor-lifecycle suggestion");
+    assert!(lifecycle.message.contains("'FetchData'"));
+    assert_eq!(lifecycle.line, Some(9));
+
+    let context = ContextBuilder::new().build_generic_context_string(&analysis);
+    assert!(context.contains("- @page \"/fetchdata\" component FetchData"));
+    assert!(context.contains("- @inject property Weather: IWeatherService"));
+    assert!(context.contains("- uses WeatherTable"));
+    assert!(context.contains("(`Pages/FetchData.razor:9`)"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_generic_analyzer_analyzes_solution_projects() -> Result<()> {
+    use mcp_context_rust::context::ContextBuilder;
+
+    let temp_dir = tempfile::tempdir()?;
+    let root = &temp_dir.path().join("Shop");
+    fs::create_dir_all(root.join("src/Web/Pages"))?;
+    fs::create_dir_all(root.join("src/Shared"))?;
+    fs::write(
+        root.join("Shop.sln"),
+        r#"Microsoft Visual Studio Solution File, Format Version 12.00
+Project("{FAE04EC0-301F-11D3-BF4B-00C04F79EFBC}") = "Web", "src\Web\Web.csproj", "{A1}"
+EndProject
+Project("{FAE04EC0-301F-11D3-BF4B-00C04F79EFBC}") = "Shared", "src\Shared\Shared.csproj", "{B2}"
+EndProject
+"#,
+    )?;
+    fs::write(
+        root.join("src/Web/Web.csproj"),
+        r#"<Project Sdk="Microsoft.NET.Sdk.Web">
+  <PropertyGroup>
+    <TargetFramework>net9.0</TargetFramework>
+  </PropertyGroup>
+  <ItemGroup>
+    <PackageReference Include="Microsoft.AspNetCore.Components.Web" Version="9.0.0" />
+    <PackageReference Include="Serilog" Version="4.0.0" />
+  </ItemGroup>
+  <ItemGroup>
+    <ProjectReference Include="..\Shared\Shared.csproj" />
+  </ItemGroup>
+</Project>"#,
+    )?;
+    fs::write(
+        root.join("src/Shared/Shared.csproj"),
+        r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>net8.0</TargetFramework>
+  </PropertyGroup>
+  <ItemGroup>
+    <PackageReference Include="Serilog" Version="4.0.0" />
+  </ItemGroup>
+</Project>"#,
+    )?;
+    fs::write(
+        root.join("src/Web/Pages/Home.razor"),
+        "@page \"/\"\n<h1>Home</h1>\n",
+    )?;
+    fs::write(
+        root.join("src/Shared/Order.cs"),
+        "namespace Shop.Shared; public record Order(int Id);",
+    )?;
+
+    let project = GenericAnalyzer::analyze(root)?;
+    assert_eq!(project.name, "Shop");
+    assert!(project.files.is_empty());
+
+    let names: Vec<&str> = project
+        .subprojects
+        .iter()
+        .map(|p| p.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["Web", "Shared"]);
+
+    let web = &project.subprojects[0];
+    assert_eq!(web.metadata.target_framework.as_deref(), Some("net9.0"));
+    assert_eq!(web.dependencies.len(), 2);
+    assert_eq!(web.references, vec!["Shared"]);
+    assert_eq!(web.files.len(), 1);
+    assert!(web.files[0].path.ends_with("Home.razor"));
+
+    let shared = &project.subprojects[1];
+    assert_eq!(shared.metadata.target_framework.as_deref(), Some("net8.0"));
+    assert!(shared.references.is_empty());
+    assert_eq!(shared.files[0].symbols[0].children[0].name, "Order");
+
+    // Packages used by several projects are listed once for the solution
+    assert_eq!(project.dependencies.len(), 2);
+
+    let analysis = ContextBuilder::new()
+        .build_generic_analysis(project)
+        .await?;
+    assert_eq!(analysis.statistics.total_files, 2);
+    assert_eq!(analysis.statistics.total_classes, 2);
+
+    let context = ContextBuilder::new().build_generic_context_string(&analysis);
+    assert!(context.contains("## Projects"));
+    assert!(context.contains("### Web (`src/Web`)"));
+    assert!(context.contains("- Target Framework: net8.0"));
+    assert!(context.contains("- References: Shared"));
+    assert!(context.contains("### src/Shared/Order.cs"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dotnet_multi_targeting_project_metadata() -> Result<()> {
+    use mcp_context_rust::context::ContextBuilder;
+
+    let temp_dir = tempfile::tempdir()?;
+    let project_path = &temp_dir.path().join("Jobs");
+    fs::create_dir_all(project_path)?;
+    fs::write(
+        project_path.join("Jobs.csproj"),
+        r#"<Project Sdk="Microsoft.NET.Sdk.Worker">
+  <PropertyGroup>
+    <TargetFrameworks>net8.0;net9.0</TargetFrameworks>
+    <OutputType>Exe</OutputType>
+    <LangVersion>latest</LangVersion>
+  </PropertyGroup>
+  <ItemGroup>
+    <PackageReference Include="Microsoft.AspNetCore.Components.Web" Version="8.0.0" />
+  </ItemGroup>
+</Project>"#,
+    )?;
+    fs::write(
+        project_path.join("Worker.cs"),
+        "public class Worker : BackgroundService { protected override void OnInitialized() { } }",
+    )?;
+
+    let project = GenericAnalyzer::analyze(project_path)?;
+    assert_eq!(project.metadata.target_frameworks, vec!["net8.0", "net9.0"]);
+    assert_eq!(project.metadata.target_framework.as_deref(), Some("net8.0"));
+    assert_eq!(
+        project.metadata.sdk.as_deref(),
+        Some("Microsoft.NET.Sdk.Worker")
+    );
+    assert_eq!(project.metadata.output_type.as_deref(), Some("Exe"));
+    assert_eq!(project.metadata.lang_version.as_deref(), Some("latest"));
+    assert_eq!(project.metadata.nullable, None);
+
+    // A Components package alone no longer makes a project Blazor: the
+    // Worker SDK selects the worker patterns
+    let patterns_path = temp_dir.path().join("patterns");
+    fs::create_dir_all(&patterns_path)?;
+    let pattern = |id: &str, framework: &str| {
+        format!(
+            r#"{{"id": "{id}", "category": "patterns", "framework": "{framework}",
+                "version": "8.0", "title": "{id}", "description": "", "code": "",
+                "tags": [], "usage_count": 0, "relevance_score": 0.9,
+                "created_at": "2025-10-25T00:00:00Z", "updated_at": "2025-10-25T00:00:00Z"}}"#
+        )
+    };
+    fs::write(
+        patterns_path.join("patterns.json"),
+        format!(
+            r#"{{"patterns": [{}, {}]}}"#,
+            pattern("blazor-1", "blazor-server"),
+            pattern("worker-1", "worker-service")
+        ),
+    )?;
+    let mut manager = mcp_context_rust::training::TrainingManager::new(patterns_path);
+    manager.load_patterns().await?;
+
+    let analysis = ContextBuilder::new()
+        .with_training_manager(manager)
+        .build_generic_analysis(project)
+        .await?;
+    let ids: Vec<&str> = analysis.patterns.iter().map(|p| p.id.as_str()).collect();
+    assert_eq!(ids, vec!["worker-1"]);
+
+    let context = ContextBuilder::new().build_generic_context_string(&analysis);
+    assert!(context.contains("**Target Framework:** net8.0, net9.0"));
+    assert!(context.contains("**SDK:** Microsoft.NET.Sdk.Worker"));
+    assert!(context.contains("**Build:** OutputType Exe, LangVersion latest"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_generic_analyzer_reads_cargo_lock() -> Result<()> {
+    use mcp_context_rust::context::ContextBuilder;
+
+    let temp_dir = tempfile::tempdir()?;
+    let project_path = &temp_dir.path().join("service");
+    fs::create_dir_all(project_path)?;
+    fs::write(
+        project_path.join("Cargo.toml"),
+        r#"[package]
+name = "service"
+version = "0.1.0"
+
+[dependencies]
+tokio = { version = "1", features = ["full"] }
+serde = "1.0"
+"#,
+    )?;
+    fs::write(
+        project_path.join("Cargo.lock"),
+        r#"version = 3
+
+[[package]]
+name = "mio"
+version = "0.8.11"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "serde"
+version = "1.0.203"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "service"
+version = "0.1.0"
+dependencies = ["serde", "tokio"]
+
+[[package]]
+name = "tokio"
+version = "1.38.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+dependencies = ["mio"]
+"#,
+    )?;
+
+    let project = GenericAnalyzer::analyze(project_path)?;
+    let tokio = project
+        .dependencies
+        .iter()
+        .find(|d| d.name == "tokio")
+        .unwrap();
+    assert_eq!(tokio.version, "1");
+    assert_eq!(tokio.resolved.as_deref(), Some("1.38.0"));
+    assert!(!tokio.transitive);
+
+    let mio = project
+        .dependencies
+        .iter()
+        .find(|d| d.name == "mio")
+        .unwrap();
+    assert!(mio.transitive);
+    assert_eq!(mio.version_in_use(), "0.8.11");
+    assert!(!project.dependencies.iter().any(|d| d.name == "service"));
+
+    let analysis = ContextBuilder::new()
+        .build_generic_analysis(project)
+        .await?;
+    assert_eq!(analysis.statistics.package_count, 2);
+
+    let context = ContextBuilder::new().build_generic_context_string(&analysis);
+    assert!(context.contains("- tokio (1 → 1.38.0)"));
+    assert!(context.contains("- serde (1.0 → 1.0.203)"));
+    assert!(context.contains("### Transitive\n- mio (0.8.11)"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_generic_analyzer_reads_pyproject_and_go_indirect_requires() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let api = &temp_dir.path().join("api");
+    fs::create_dir_all(api)?;
+    fs::write(
+        api.join("pyproject.toml"),
+        r#"[project]
+name = "api"
+version = "0.1.0"
+dependencies = ["fastapi[standard]>=0.110", "SQLAlchemy"]
+
+[dependency-groups]
+dev = ["pytest>=8"]
+"#,
+    )?;
+    fs::write(
+        api.join("uv.lock"),
+        r#"version = 1
+
+[[package]]
+name = "api"
+version = "0.1.0"
+source = { editable = "." }
+
+[[package]]
+name = "fastapi"
+version = "0.111.0"
+source = { registry = "https://pypi.org/simple" }
+
+[[package]]
+name = "sqlalchemy"
+version = "2.0.30"
+source = { registry = "https://pypi.org/simple" }
+
+[[package]]
+name = "pytest"
+version = "8.2.2"
+source = { registry = "https://pypi.org/simple" }
+
+[[package]]
+name = "starlette"
+version = "0.37.2"
+source = { registry = "https://pypi.org/simple" }
+"#,
+    )?;
+
+    let project = GenericAnalyzer::analyze(api)?;
+    let summary: Vec<(&str, &str, Option<&str>, bool, bool)> = project
+        .dependencies
         .iter()
-        .any(|p| p.name == "Microsoft.AspNetCore.Components"));
+        .map(|d| {
+            (
+                d.name.as_str(),
+                d.version.as_str(),
+                d.resolved.as_deref(),
+                d.dev_only,
+                d.transitive,
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("fastapi", ">=0.110", Some("0.111.0"), false, false),
+            ("SQLAlchemy", "*", Some("2.0.30"), false, false),
+            ("pytest", ">=8", Some("8.2.2"), true, false),
+            ("starlette", "0.37.2", Some("0.37.2"), false, true),
+        ]
+    );
+
+    let worker = &temp_dir.path().join("worker");
+    fs::create_dir_all(worker)?;
+    fs::write(
+        worker.join("go.mod"),
+        r#"module example.com/worker
+
+go 1.22
+
+require github.com/gin-gonic/gin v1.10.0
+
+require (
+	github.com/bytedance/sonic v1.11.6 // indirect
+)
+"#,
+    )?;
+
+    let project = GenericAnalyzer::analyze(worker)?;
+    let direct: Vec<&str> = project
+        .direct_dependencies()
+        .map(|d| d.name.as_str())
+        .collect();
+    assert_eq!(direct, vec!["github.com/gin-gonic/gin"]);
+    assert!(project.dependencies[1].transitive);
+    assert_eq!(project.dependencies[1].version_in_use(), "v1.11.6");
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_generic_analyzer_analyzes_polyglot_repository() -> Result<()> {
+    use mcp_context_rust::context::ContextBuilder;
+    use mcp_context_rust::types::ProjectType;
+
+    let temp_dir = tempfile::tempdir()?;
+    let root = &temp_dir.path().join("shop");
+    fs::create_dir_all(root.join("backend/Api"))?;
+    fs::create_dir_all(root.join("frontend/src"))?;
+    fs::create_dir_all(root.join("scripts"))?;
+    fs::write(
+        root.join("backend/Api/Api.csproj"),
+        r#"<Project Sdk="Microsoft.NET.Sdk.Web">
+  <PropertyGroup>
+    <TargetFramework>net9.0</TargetFramework>
+  </PropertyGroup>
+  <ItemGroup>
+    <PackageReference Include="Serilog" Version="4.0.0" />
+  </ItemGroup>
+</Project>"#,
+    )?;
+    fs::write(
+        root.join("backend/Api/OrdersController.cs"),
+        "namespace Api; public class OrdersController { }",
+    )?;
+    fs::write(
+        root.join("frontend/package.json"),
+        r#"{ "name": "web", "version": "2.1.0", "dependencies": { "react": "^18.2.0" } }"#,
+    )?;
+    fs::write(
+        root.join("frontend/src/App.tsx"),
+        "export function App() { return null; }",
+    )?;
+    // Loose script outside any project
+    fs::write(root.join("scripts/deploy.js"), "console.log('deploy');")?;
+
+    let project = GenericAnalyzer::analyze(root)?;
+    assert_eq!(project.project_type, ProjectType::Unknown);
+    assert!(project.files.is_empty());
+
+    let types: Vec<(&str, &ProjectType)> = project
+        .subprojects
+        .iter()
+        .map(|p| (p.name.as_str(), &p.project_type))
+        .collect();
+    assert_eq!(
+        types,
+        vec![("Api", &ProjectType::DotNet), ("web", &ProjectType::Node)]
+    );
+
+    let api = &project.subprojects[0];
+    assert_eq!(api.dependencies[0].name, "Serilog");
+    assert_eq!(api.files.len(), 1);
+    assert!(api.files[0].path.ends_with("OrdersController.cs"));
+
+    let web = &project.subprojects[1];
+    assert_eq!(web.dependencies[0].name, "react");
+    assert_eq!(web.files.len(), 1);
+    assert!(web.files[0].path.ends_with("App.tsx"));
+
+    let builder = ContextBuilder::new();
+    let analysis = builder.build_generic_analysis(project).await?;
+    assert_eq!(analysis.statistics.total_files, 2);
+
+    let context = builder.build_generic_context_string(&analysis);
+    assert!(context.contains("## Projects"));
+    assert!(context.contains("### Api (`backend/Api`)\n- Type: dotnet\n"));
+    assert!(context.contains("### web (`frontend`)\n- Type: node\n- Version: 2.1.0\n"));
+    assert!(context.contains("- Dependencies: react (^18.2.0)"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_generic_analyzer_analyzes_cargo_workspace() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let root = &temp_dir.path().join("workspace");
+    fs::create_dir_all(root.join("crates/core/src"))?;
+    fs::create_dir_all(root.join("crates/cli/src"))?;
+    fs::write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/*\"]\n",
+    )?;
+    fs::write(
+        root.join("crates/core/Cargo.toml"),
+        "[package]\nname = \"core\"\nversion = \"0.1.0\"\n\n[dependencies]\nserde = \"1.0\"\n",
+    )?;
+    fs::write(
+        root.join("crates/cli/Cargo.toml"),
+        "[package]\nname = \"cli\"\nversion = \"0.1.0\"\n\n[dependencies]\ncore = { path = \"../core\" }\n",
+    )?;
+    fs::write(root.join("crates/core/src/lib.rs"), "pub struct Order;")?;
+    fs::write(root.join("crates/cli/src/main.rs"), "fn main() {}")?;
+    fs::write(
+        root.join("Cargo.lock"),
+        r#"version = 3
+
+[[package]]
+name = "cli"
+version = "0.1.0"
+dependencies = ["core"]
+
+[[package]]
+name = "core"
+version = "0.1.0"
+dependencies = ["serde"]
+
+[[package]]
+name = "serde"
+version = "1.0.203"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "serde_derive"
+version = "1.0.203"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#,
+    )?;
+
+    let project = GenericAnalyzer::analyze(root)?;
+    assert_eq!(project.name, "workspace");
+    let names: Vec<&str> = project
+        .subprojects
+        .iter()
+        .map(|p| p.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["cli", "core"]);
+
+    // Members resolve against the shared lockfile without listing it all
+    let core = &project.subprojects[1];
+    assert_eq!(core.dependencies.len(), 1);
+    assert_eq!(core.dependencies[0].resolved.as_deref(), Some("1.0.203"));
+    assert!(core.files[0].path.ends_with("lib.rs"));
+    assert!(project.subprojects[0].files[0].path.ends_with("main.rs"));
+
+    // The root keeps only what no member declares
+    let root_dependencies: Vec<&str> = project
+        .dependencies
+        .iter()
+        .map(|d| d.name.as_str())
+        .collect();
+    assert_eq!(root_dependencies, vec!["serde_derive"]);
+    assert!(project.files.is_empty());
+    Ok(())
+}
@@ -482,3 +482,69 @@ async fn test_statistics() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_reload_file_reports_changes() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let patterns_path = temp_dir.path().join("patterns");
+    fs::create_dir_all(&patterns_path)?;
+
+    let pattern_json = |id: &str, title: &str| {
+        format!(
+            r#"{{"patterns": [{{
+                "id": "{}", "category": "testing", "framework": "blazor-server",
+                "version": "10.0", "title": "{}", "description": "d", "code": "c",
+                "tags": [], "usage_count": 0, "relevance_score": 0.8,
+                "created_at": "2025-10-25T00:00:00Z", "updated_at": "2025-10-25T00:00:00Z"
+            }}]}}"#,
+            id, title
+        )
+    };
+
+    let first = patterns_path.join("first-patterns.json");
+    let second = patterns_path.join("second-patterns.json");
+    fs::write(&first, pattern_json("one", "One"))?;
+    fs::write(&second, pattern_json("two", "Two"))?;
+
+    let mut manager = TrainingManager::new(&patterns_path);
+    manager.load_patterns().await?;
+    assert_eq!(manager.get_all_patterns().len(), 2);
+
+    // Modified file: only its pattern is replaced
+    fs::write(&first, pattern_json("one", "One (edited)"))?;
+    let changes = manager.reload_file(&first)?;
+    assert_eq!(changes.updated.len(), 1);
+    assert!(changes.added.is_empty() && changes.removed.is_empty());
+    assert_eq!(
+        manager.get_pattern_by_id("one").unwrap().title,
+        "One (edited)"
+    );
+    assert_eq!(manager.get_all_patterns().len(), 2);
+
+    // Pattern renamed inside the file: old id removed, new id added
+    fs::write(&first, pattern_json("uno", "Uno"))?;
+    let changes = manager.reload_file(&first)?;
+    assert_eq!(changes.added[0].id, "uno");
+    assert_eq!(changes.removed[0].id, "one");
+
+    // Invalid JSON keeps the current patterns
+    fs::write(&first, "{ not json")?;
+    assert!(manager.reload_file(&first).is_err());
+    assert!(manager.get_pattern_by_id("uno").is_some());
+
+    // Deleted file removes its patterns, and indexes follow
+    fs::remove_file(&second)?;
+    let changes = manager.reload_file(&second)?;
+    assert_eq!(changes.removed[0].id, "two");
+    assert!(manager.get_pattern_by_id("two").is_none());
+    let criteria = SearchCriteria {
+        query: None,
+        category: None,
+        framework: Some("blazor-server".to_string()),
+        tags: vec![],
+        min_score: 0.0,
+    };
+    assert_eq!(manager.search_patterns(&criteria).len(), 1);
+
+    Ok(())
+}